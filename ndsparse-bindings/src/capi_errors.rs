@@ -0,0 +1,24 @@
+//! Error reporting of the C API.
+//!
+//! Fallible functions return a null pointer or `false` and store the message of the originating
+//! error, which can then be retrieved through `ndsparse_last_error`.
+
+use std::{cell::RefCell, ffi::CString, os::raw::c_char, ptr};
+
+thread_local! {
+  static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+/// Message of the last error that happened in the current thread or null if there is none.
+///
+/// The returned string is owned by the library and remains valid until the next call of a
+/// fallible function in the same thread.
+#[no_mangle]
+pub extern "C" fn ndsparse_last_error() -> *const c_char {
+  LAST_ERROR.with(|cell| cell.borrow().as_ref().map_or(ptr::null(), |msg| msg.as_ptr()))
+}
+
+pub(crate) fn set_last_error(err: impl ToString) {
+  let msg = CString::new(err.to_string()).ok();
+  LAST_ERROR.with(|cell| *cell.borrow_mut() = msg);
+}
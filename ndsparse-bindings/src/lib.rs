@@ -7,11 +7,39 @@
 // Auto-generated code
 #![allow(clippy::all, clippy::restriction, unused_qualifications, unsafe_code)]
 
-use ndsparse::csl::Csl;
+#[cfg(feature = "with-capi")]
+pub mod capi_errors;
 #[cfg(feature = "with-pyo3")]
-use pyo3::{exceptions, prelude::*};
+pub mod pyo3_errors;
+#[cfg(feature = "with-uniffi")]
+pub mod uniffi_errors;
+#[cfg(feature = "with-wasm-bindgen")]
+pub mod wasm_errors;
+
+#[cfg(feature = "with-capi")]
+use capi_errors::set_last_error;
+use ndsparse::{coo::Coo, csl::Csl};
+#[cfg(feature = "with-pyo3")]
+use numpy::{npyffi, Element, PyArray1, PY_ARRAY_API};
+#[cfg(feature = "with-pyo3")]
+use pyo3::{
+  exceptions, prelude::*, types::PyType, PyMappingProtocol, PyNativeType, PyObjectProtocol,
+};
+#[cfg(feature = "with-pyo3")]
+use pyo3_errors::py_err;
+#[cfg(feature = "with-uniffi")]
+use std::sync::Arc;
+#[cfg(feature = "with-pyo3")]
+use std::{ffi::c_void, mem, os::raw::c_int, ptr};
+#[cfg(feature = "with-uniffi")]
+use uniffi_errors::{uniffi_err, NdsparseError};
 #[cfg(feature = "with-wasm-bindgen")]
 use wasm_bindgen::prelude::*;
+#[cfg(feature = "with-wasm-bindgen")]
+use wasm_errors::js_err;
+
+#[cfg(feature = "with-uniffi")]
+uniffi::setup_scaffolding!();
 
 macro_rules! create_csl {
   (
@@ -23,11 +51,34 @@ macro_rules! create_csl {
     $dims:literal
   ) => {
     #[cfg_attr(feature = "with-pyo3", pyclass)]
+    #[cfg_attr(feature = "with-uniffi", derive(uniffi::Object))]
     #[cfg_attr(feature = "with-wasm-bindgen", wasm_bindgen)]
     #[derive(Debug)]
     /// Wrapper around [`Csl`](ndsparse::csl::Csl).
     pub struct $struct_name {
       csl: Csl<$data_storage, $indcs_storage, $offs_storage, $dims>,
+      /// If `true`, `__getitem__` raises `KeyError` instead of returning `None` for missing
+      /// entries.
+      #[cfg(feature = "with-pyo3")]
+      #[pyo3(get, set)]
+      strict: bool,
+    }
+
+    #[cfg(any(
+      feature = "with-capi",
+      feature = "with-pyo3",
+      feature = "with-uniffi",
+      feature = "with-wasm-bindgen"
+    ))]
+    impl $struct_name {
+      // Wraps `csl` with the default settings of every binding.
+      fn from_csl(csl: Csl<$data_storage, $indcs_storage, $offs_storage, $dims>) -> Self {
+        Self {
+          csl,
+          #[cfg(feature = "with-pyo3")]
+          strict: false,
+        }
+      }
     }
 
     // Generic
@@ -40,24 +91,146 @@ macro_rules! create_csl {
         self.csl.clear()
       }
 
-      /// Wrapper around [`data`](ndsparse::csl::Csl#method.data).
-      pub fn data_vec(&self) -> Vec<$data_ty> {
-        self.csl.data().to_vec()
+      /// Wrapper around [`nnz`](ndsparse::csl::Csl#method.nnz).
+      pub fn nnz(&self) -> usize {
+        self.csl.nnz()
       }
+    }
 
-      /// Wrapper around [`indcs`](ndsparse::csl::Csl#method.indcs).
-      pub fn indcs_vec(&self) -> Vec<usize> {
-        self.csl.indcs().to_vec()
+    // C
+
+    #[cfg(feature = "with-capi")]
+    impl $struct_name {
+      /// Wrapper around [`new`](ndsparse::csl::Csl#method.new) that copies the provided arrays
+      /// into a heap-allocated handle, which must be released with `free`. `dims` must point to
+      /// as many elements as the number of dimensions. Returns null on error.
+      ///
+      /// # Safety
+      ///
+      /// Each pointer must be valid for reads of its respective length.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_new")]
+      pub unsafe extern "C" fn c_new(
+        dims: *const usize,
+        data: *const $data_ty,
+        data_len: usize,
+        indcs: *const usize,
+        indcs_len: usize,
+        offs: *const usize,
+        offs_len: usize,
+      ) -> *mut $struct_name {
+        let dims: [usize; $dims] = cl_traits::create_array(|idx| *dims.add(idx));
+        let data = c_slice(data, data_len).to_vec();
+        let indcs = c_slice(indcs, indcs_len).to_vec();
+        let offs = c_slice(offs, offs_len).to_vec();
+        match Csl::new(dims, data, indcs, offs) {
+          Ok(csl) => Box::into_raw(Box::new($struct_name::from_csl(csl))),
+          Err(err) => {
+            set_last_error(err);
+            std::ptr::null_mut()
+          }
+        }
       }
 
-      /// Wrapper around [`offs`](ndsparse::csl::Csl#method.offs).
-      pub fn offs_vec(&self) -> Vec<usize> {
-        self.csl.offs().to_vec()
+      /// Wrapper around [`clear`](ndsparse::csl::Csl#method.clear).
+      ///
+      /// # Safety
+      ///
+      /// `this` must be a valid handle.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_clear")]
+      pub unsafe extern "C" fn c_clear(this: *mut $struct_name) {
+        (*this).csl.clear()
+      }
+
+      /// Pointer to the first element of [`data`](ndsparse::csl::Csl#method.data), whose length
+      /// is written into `len`.
+      ///
+      /// # Safety
+      ///
+      /// `this` must be a valid handle and `len` must be valid for writes. The returned pointer
+      /// is invalidated by any function that modifies the handle.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_data")]
+      pub unsafe extern "C" fn c_data(
+        this: *const $struct_name,
+        len: *mut usize,
+      ) -> *const $data_ty {
+        c_raw_parts((*this).csl.data(), len)
+      }
+
+      /// Pointer to the [`dims`](ndsparse::csl::Csl#method.dims) array.
+      ///
+      /// # Safety
+      ///
+      /// `this` must be a valid handle.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_dims")]
+      pub unsafe extern "C" fn c_dims(this: *const $struct_name) -> *const usize {
+        (*this).csl.dims().as_ptr()
+      }
+
+      /// Releases a handle created by `new`. Null is ignored.
+      ///
+      /// # Safety
+      ///
+      /// `this` must be null or a handle that wasn't released before.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_free")]
+      pub unsafe extern "C" fn c_free(this: *mut $struct_name) {
+        if !this.is_null() {
+          drop(Box::from_raw(this));
+        }
+      }
+
+      /// Pointer to the first element of [`indcs`](ndsparse::csl::Csl#method.indcs), whose
+      /// length is written into `len`.
+      ///
+      /// # Safety
+      ///
+      /// Same as `data`.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_indcs")]
+      pub unsafe extern "C" fn c_indcs(this: *const $struct_name, len: *mut usize) -> *const usize {
+        c_raw_parts((*this).csl.indcs(), len)
       }
 
       /// Wrapper around [`nnz`](ndsparse::csl::Csl#method.nnz).
-      pub fn nnz(&self) -> usize {
-        self.csl.nnz()
+      ///
+      /// # Safety
+      ///
+      /// `this` must be a valid handle.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_nnz")]
+      pub unsafe extern "C" fn c_nnz(this: *const $struct_name) -> usize {
+        (*this).csl.nnz()
+      }
+
+      /// Pointer to the first element of [`offs`](ndsparse::csl::Csl#method.offs), whose length
+      /// is written into `len`.
+      ///
+      /// # Safety
+      ///
+      /// Same as `data`.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_offs")]
+      pub unsafe extern "C" fn c_offs(this: *const $struct_name, len: *mut usize) -> *const usize {
+        c_raw_parts((*this).csl.offs(), len)
+      }
+
+      /// Wrapper around [`value`](ndsparse::csl::Csl#method.value). Writes the element into
+      /// `out` and returns `true` if `indcs` is a stored entry.
+      ///
+      /// # Safety
+      ///
+      /// `this` must be a valid handle, `indcs` must point to as many elements as the number of
+      /// dimensions and `out` must be valid for writes.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_value")]
+      pub unsafe extern "C" fn c_value(
+        this: *const $struct_name,
+        indcs: *const usize,
+        out: *mut $data_ty,
+      ) -> bool {
+        let indcs: [usize; $dims] = cl_traits::create_array(|idx| *indcs.add(idx));
+        match (*this).csl.value(indcs) {
+          Some(value) => {
+            *out = *value;
+            true
+          }
+          None => false,
+        }
       }
     }
 
@@ -74,14 +247,72 @@ macro_rules! create_csl {
         indcs: $indcs_storage,
         offs: $offs_storage,
       ) -> PyResult<Self> {
-        let map_err = |e| exceptions::PyTypeError::new_err(format!("{:?}", e));
-        let csl = Csl::new(dims, data, indcs, offs).map_err(map_err)?;
-        Ok($struct_name { csl })
+        let csl = Csl::new(dims, data, indcs, offs).map_err(py_err)?;
+        Ok($struct_name::from_csl(csl))
+      }
+
+      /// Read-only NumPy view of [`data`](ndsparse::csl::Csl#method.data) without copying.
+      #[name = "data_vec"]
+      pub fn py_data_vec(slf: &PyCell<Self>) -> &PyArray1<$data_ty> {
+        py_view(slf, slf.borrow().csl.data())
+      }
+
+      /// Builds a structure from nested sequences with one level for each dimension, e.g., a
+      /// list of lists for two dimensions. Zeros are not stored.
+      #[classmethod]
+      pub fn from_dense(_cls: &PyType, dense: &PyAny) -> PyResult<Self> {
+        let (dims, pairs) = py_dense::<$data_ty, $dims>(dense)?;
+        let csl = Coo::new(dims, pairs).and_then(|coo| coo.to_csl()).map_err(py_err)?;
+        Ok($struct_name::from_csl(csl))
+      }
+
+      /// Builds a structure from `(indices, value)` pairs in any order. If `dims` isn't
+      /// provided, the length of each dimension is its greatest index plus one.
+      #[args(dims = "None")]
+      #[classmethod]
+      pub fn from_triplets(
+        _cls: &PyType,
+        entries: Vec<(Vec<usize>, $data_ty)>,
+        dims: Option<[usize; $dims]>,
+      ) -> PyResult<Self> {
+        let mut pairs = py_pairs::<$data_ty, $dims>(entries)?;
+        pairs.sort_unstable_by_key(|pair| pair.0);
+        let dims = dims.unwrap_or_else(|| {
+          let mut rslt = [0; $dims];
+          for (indcs, _) in &pairs {
+            for (dim, idx) in rslt.iter_mut().zip(indcs.iter()) {
+              *dim = (*dim).max(idx + 1);
+            }
+          }
+          rslt
+        });
+        let csl = Coo::new(dims, pairs).and_then(|coo| coo.to_csl()).map_err(py_err)?;
+        Ok($struct_name::from_csl(csl))
+      }
+
+      /// Read-only NumPy view of [`indcs`](ndsparse::csl::Csl#method.indcs) without copying.
+      #[name = "indcs_vec"]
+      pub fn py_indcs_vec(slf: &PyCell<Self>) -> &PyArray1<usize> {
+        py_view(slf, slf.borrow().csl.indcs())
+      }
+
+      /// Read-only NumPy view of [`offs`](ndsparse::csl::Csl#method.offs) without copying.
+      #[name = "offs_vec"]
+      pub fn py_offs_vec(slf: &PyCell<Self>) -> &PyArray1<usize> {
+        py_view(slf, slf.borrow().csl.offs())
+      }
+
+      /// Wrapper around
+      /// [`new_random_with_seed`](ndsparse::csl::Csl#method.new_random_with_seed).
+      #[classmethod]
+      pub fn random(_cls: &PyType, dims: [usize; $dims], nnz: usize, seed: u64) -> PyResult<Self> {
+        let csl = Csl::new_random_with_seed(dims, nnz, seed).map_err(py_err)?;
+        Ok($struct_name::from_csl(csl))
       }
 
       /// Wrapper around [`truncate`](ndsparse::csl::Csl#method.truncate).
-      pub fn truncate(&mut self, dims: [usize; $dims]) {
-        self.csl.truncate(dims)
+      pub fn truncate(&mut self, dims: [usize; $dims]) -> PyResult<()> {
+        self.csl.truncate(dims).map_err(py_err)
       }
 
       /// Wrapper around [`value`](ndsparse::csl::Csl#method.value).
@@ -90,6 +321,89 @@ macro_rules! create_csl {
       }
     }
 
+    #[cfg(feature = "with-pyo3")]
+    #[pyproto]
+    impl PyMappingProtocol for $struct_name {
+      fn __getitem__(&self, key: PyIndcs) -> PyResult<Option<$data_ty>> {
+        let indcs = py_indcs(self.csl.dims(), key)?;
+        match self.csl.value(indcs).copied() {
+          None if self.strict => Err(exceptions::PyKeyError::new_err(format!("{:?}", indcs))),
+          value => Ok(value),
+        }
+      }
+
+      fn __len__(&self) -> usize {
+        self.csl.nnz()
+      }
+
+      fn __setitem__(&mut self, key: PyIndcs, value: $data_ty) -> PyResult<()> {
+        let indcs = py_indcs(self.csl.dims(), key)?;
+        let err = || exceptions::PyKeyError::new_err(format!("{:?} isn't a stored entry", indcs));
+        *self.csl.value_mut(indcs).ok_or_else(err)? = value;
+        Ok(())
+      }
+    }
+
+    #[cfg(feature = "with-pyo3")]
+    #[pyproto]
+    impl PyObjectProtocol for $struct_name {
+      fn __repr__(&self) -> String {
+        format!(
+          "{}(dims={:?}, data={:?}, indcs={:?}, offs={:?})",
+          stringify!($struct_name),
+          self.csl.dims(),
+          self.csl.data(),
+          self.csl.indcs(),
+          self.csl.offs()
+        )
+      }
+    }
+
+    // UniFFI
+    //
+    // Objects are shared through `Arc`, therefore, methods that would modify the structure
+    // return a modified copy instead.
+
+    #[cfg(feature = "with-uniffi")]
+    #[uniffi::export]
+    impl $struct_name {
+      /// Wrapper around [`new`](ndsparse::csl::Csl#method.new).
+      #[uniffi::constructor(name = "new")]
+      pub fn new_u64(
+        dims: Vec<u64>,
+        data: $data_storage,
+        indcs: Vec<u64>,
+        offs: Vec<u64>,
+      ) -> Result<Arc<Self>, NdsparseError> {
+        let dims: [usize; $dims] = uniffi_array(dims)?;
+        let indcs = indcs.into_iter().map(|idx| idx as usize).collect();
+        let offs = offs.into_iter().map(|off| off as usize).collect();
+        let csl = Csl::new(dims, data, indcs, offs).map_err(uniffi_err)?;
+        Ok(Arc::new($struct_name::from_csl(csl)))
+      }
+
+      /// Wrapper around [`nnz`](ndsparse::csl::Csl#method.nnz).
+      #[uniffi::method(name = "nnz")]
+      pub fn nnz_u64(&self) -> u64 {
+        self.csl.nnz() as u64
+      }
+
+      /// Wrapper around [`truncate`](ndsparse::csl::Csl#method.truncate) that returns a
+      /// truncated copy.
+      #[uniffi::method(name = "truncate")]
+      pub fn truncate_u64(&self, dims: Vec<u64>) -> Result<Arc<Self>, NdsparseError> {
+        let mut csl = self.csl.clone();
+        csl.truncate(uniffi_array(dims)?).map_err(uniffi_err)?;
+        Ok(Arc::new($struct_name::from_csl(csl)))
+      }
+
+      /// Wrapper around [`value`](ndsparse::csl::Csl#method.value).
+      #[uniffi::method(name = "value")]
+      pub fn value_u64(&self, indcs: Vec<u64>) -> Option<$data_ty> {
+        self.csl.value(uniffi_array(indcs).ok()?).copied()
+      }
+    }
+
     // wasm-bindgen
 
     #[cfg(feature = "with-wasm-bindgen")]
@@ -104,9 +418,26 @@ macro_rules! create_csl {
         offs: $offs_storage,
       ) -> Result<$struct_name, JsValue> {
         let dims: [usize; $dims] = from_vec_to_array(dims_vec)?;
-        let map_err = |e| JsValue::from_str(&format!("{:?}", e));
-        let csl = Csl::new(dims, data, indcs, offs).map_err(map_err)?;
-        Ok($struct_name { csl })
+        let csl = Csl::new(dims, data, indcs, offs).map_err(js_err)?;
+        Ok($struct_name::from_csl(csl))
+      }
+
+      /// Builds a structure from an array of `[indices, value]` pairs in any order, e.g.,
+      /// `[[[1, 0], 2], [[0, 2], 1]]`, through [`to_csl`](ndsparse::coo::Coo#method.to_csl).
+      pub fn from_triplets_js(
+        dims_vec: Vec<usize>,
+        entries: JsValue,
+      ) -> Result<$struct_name, JsValue> {
+        let dims: [usize; $dims] = from_vec_to_array(dims_vec)?;
+        let mut pairs = js_pairs(entries)?;
+        pairs.sort_unstable_by_key(|pair| pair.0);
+        let csl = Coo::new(dims, pairs).and_then(|coo| coo.to_csl()).map_err(js_err)?;
+        Ok($struct_name::from_csl(csl))
+      }
+
+      /// Wrapper around [`data`](ndsparse::csl::Csl#method.data).
+      pub fn data_vec(&self) -> Vec<$data_ty> {
+        self.csl.data().to_vec()
       }
 
       /// Wrapper around [`dims`](ndsparse::csl::Csl#method.dims).
@@ -114,10 +445,53 @@ macro_rules! create_csl {
         self.csl.dims().to_vec()
       }
 
+      /// Array of `[indices, value]` pairs of all stored entries, usable with spread or `for..of`.
+      pub fn entries(&self) -> JsValue {
+        let rslt = Array::new();
+        let data = self.csl.data();
+        for (indcs, data_idx) in stored_entries(self.csl.dims(), self.csl.indcs(), self.csl.offs())
+        {
+          let js_indcs = Array::new();
+          for idx in indcs.iter().copied() {
+            js_indcs.push(&JsValue::from_f64(idx as f64));
+          }
+          let pair = Array::new();
+          pair.push(&js_indcs.into());
+          pair.push(&JsValue::from(data[data_idx]));
+          rslt.push(&pair.into());
+        }
+        rslt.into()
+      }
+
+      /// Wrapper around [`indcs`](ndsparse::csl::Csl#method.indcs).
+      pub fn indcs_vec(&self) -> Vec<usize> {
+        self.csl.indcs().to_vec()
+      }
+
+      /// Wrapper around [`offs`](ndsparse::csl::Csl#method.offs).
+      pub fn offs_vec(&self) -> Vec<usize> {
+        self.csl.offs().to_vec()
+      }
+
+      /// Object with the `dims`, `data`, `indcs` and `offs` fields. Also used by `JSON.stringify`.
+      #[wasm_bindgen(js_name = toJSON)]
+      pub fn to_json(&self) -> Result<JsValue, JsValue> {
+        let mut s = String::new();
+        s.push_str("{\"dims\":");
+        push_json_array(&mut s, self.csl.dims());
+        s.push_str(",\"data\":");
+        push_json_array(&mut s, self.csl.data());
+        s.push_str(",\"indcs\":");
+        push_json_array(&mut s, self.csl.indcs());
+        s.push_str(",\"offs\":");
+        push_json_array(&mut s, self.csl.offs());
+        s.push('}');
+        json_parse(&s)
+      }
+
       /// Wrapper around [`truncate`](ndsparse::csl::Csl#method.truncate).
       pub fn truncate_vec(&mut self, dims_vec: Vec<usize>) -> Result<(), JsValue> {
-        self.csl.truncate(from_vec_to_array(dims_vec)?);
-        Ok(())
+        self.csl.truncate(from_vec_to_array(dims_vec)?).map_err(js_err)
       }
 
       /// Wrapper around [`value`](ndsparse::csl::Csl#method.value).
@@ -128,6 +502,228 @@ macro_rules! create_csl {
   };
 }
 
+macro_rules! create_coo {
+  ($struct_name:ident, $data_ty:ty, $dims:literal) => {
+    #[cfg_attr(feature = "with-pyo3", pyclass)]
+    #[cfg_attr(feature = "with-uniffi", derive(uniffi::Object))]
+    #[cfg_attr(feature = "with-wasm-bindgen", wasm_bindgen)]
+    #[derive(Debug)]
+    /// Wrapper around [`Coo`](ndsparse::coo::Coo).
+    pub struct $struct_name {
+      coo: Coo<Vec<([usize; $dims], $data_ty)>, $dims>,
+    }
+
+    // Generic
+
+    #[cfg_attr(feature = "with-pyo3", pymethods)]
+    #[cfg_attr(feature = "with-wasm-bindgen", wasm_bindgen)]
+    impl $struct_name {
+      /// Number of stored entries.
+      pub fn nnz(&self) -> usize {
+        self.coo.data().len()
+      }
+    }
+
+    // C
+
+    #[cfg(feature = "with-capi")]
+    impl $struct_name {
+      /// Wrapper around [`new`](ndsparse::coo::Coo#method.new) that returns a heap-allocated
+      /// handle, which must be released with `free`. `indcs` is a flattened array with the
+      /// indices of each one of the `nnz` elements of `data`. Returns null on error.
+      ///
+      /// # Safety
+      ///
+      /// `dims` must point to as many elements as the number of dimensions, `data` must be valid
+      /// for reads of `nnz` elements and `indcs` must be valid for reads of `nnz` times the
+      /// number of dimensions.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_new")]
+      pub unsafe extern "C" fn c_new(
+        dims: *const usize,
+        indcs: *const usize,
+        data: *const $data_ty,
+        nnz: usize,
+      ) -> *mut $struct_name {
+        let dims: [usize; $dims] = cl_traits::create_array(|idx| *dims.add(idx));
+        let indcs = c_slice(indcs, nnz * $dims);
+        let pairs = c_slice(data, nnz)
+          .iter()
+          .enumerate()
+          .map(|(data_idx, value)| {
+            (cl_traits::create_array(|idx| indcs[data_idx * $dims + idx]), *value)
+          })
+          .collect();
+        match Coo::new(dims, pairs) {
+          Ok(coo) => Box::into_raw(Box::new($struct_name { coo })),
+          Err(err) => {
+            set_last_error(err);
+            std::ptr::null_mut()
+          }
+        }
+      }
+
+      /// Pointer to the [`dims`](ndsparse::coo::Coo#method.dims) array.
+      ///
+      /// # Safety
+      ///
+      /// `this` must be a valid handle.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_dims")]
+      pub unsafe extern "C" fn c_dims(this: *const $struct_name) -> *const usize {
+        (*this).coo.dims().as_ptr()
+      }
+
+      /// Releases a handle created by `new`. Null is ignored.
+      ///
+      /// # Safety
+      ///
+      /// `this` must be null or a handle that wasn't released before.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_free")]
+      pub unsafe extern "C" fn c_free(this: *mut $struct_name) {
+        if !this.is_null() {
+          drop(Box::from_raw(this));
+        }
+      }
+
+      /// Number of stored entries.
+      ///
+      /// # Safety
+      ///
+      /// `this` must be a valid handle.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_nnz")]
+      pub unsafe extern "C" fn c_nnz(this: *const $struct_name) -> usize {
+        (*this).coo.data().len()
+      }
+
+      /// Wrapper around [`value`](ndsparse::coo::Coo#method.value). Writes the element into
+      /// `out` and returns `true` if `indcs` is a stored entry.
+      ///
+      /// # Safety
+      ///
+      /// `this` must be a valid handle, `indcs` must point to as many elements as the number of
+      /// dimensions and `out` must be valid for writes.
+      #[export_name = concat!("ndsparse_", stringify!($struct_name), "_value")]
+      pub unsafe extern "C" fn c_value(
+        this: *const $struct_name,
+        indcs: *const usize,
+        out: *mut $data_ty,
+      ) -> bool {
+        let indcs: [usize; $dims] = cl_traits::create_array(|idx| *indcs.add(idx));
+        match (*this).coo.value(indcs) {
+          Some(value) => {
+            *out = *value;
+            true
+          }
+          None => false,
+        }
+      }
+    }
+
+    // PyO3
+
+    #[cfg(feature = "with-pyo3")]
+    #[pymethods]
+    impl $struct_name {
+      #[new]
+      /// Wrapper around [`new`](ndsparse::coo::Coo#method.new).
+      pub fn new(dims: [usize; $dims], data: Vec<(Vec<usize>, $data_ty)>) -> PyResult<Self> {
+        let coo = Coo::new(dims, py_pairs(data)?).map_err(py_err)?;
+        Ok($struct_name { coo })
+      }
+
+      /// Wrapper around [`data`](ndsparse::coo::Coo#method.data).
+      pub fn data_vec(&self) -> Vec<(Vec<usize>, $data_ty)> {
+        self.coo.data().iter().map(|(indcs, value)| (indcs.to_vec(), *value)).collect()
+      }
+
+      /// Wrapper around [`value`](ndsparse::coo::Coo#method.value).
+      pub fn value(&self, dims: [usize; $dims]) -> Option<$data_ty> {
+        self.coo.value(dims).copied()
+      }
+    }
+
+    // UniFFI
+
+    #[cfg(feature = "with-uniffi")]
+    #[uniffi::export]
+    impl $struct_name {
+      /// Wrapper around [`new`](ndsparse::coo::Coo#method.new) where `indcs` contains the
+      /// indices of each element of `data`.
+      #[uniffi::constructor(name = "new")]
+      pub fn new_u64(
+        dims: Vec<u64>,
+        indcs: Vec<Vec<u64>>,
+        data: Vec<$data_ty>,
+      ) -> Result<Arc<Self>, NdsparseError> {
+        if indcs.len() != data.len() {
+          return Err(NdsparseError::Other("Indices and data have different lengths".into()));
+        }
+        let dims: [usize; $dims] = uniffi_array(dims)?;
+        let mut pairs = Vec::with_capacity(data.len());
+        for (indcs_vec, value) in indcs.into_iter().zip(data) {
+          pairs.push((uniffi_array(indcs_vec)?, value));
+        }
+        let coo = Coo::new(dims, pairs).map_err(uniffi_err)?;
+        Ok(Arc::new($struct_name { coo }))
+      }
+
+      /// Number of stored entries.
+      #[uniffi::method(name = "nnz")]
+      pub fn nnz_u64(&self) -> u64 {
+        self.coo.data().len() as u64
+      }
+
+      /// Wrapper around [`value`](ndsparse::coo::Coo#method.value).
+      #[uniffi::method(name = "value")]
+      pub fn value_u64(&self, indcs: Vec<u64>) -> Option<$data_ty> {
+        self.coo.value(uniffi_array(indcs).ok()?).copied()
+      }
+    }
+
+    // wasm-bindgen
+
+    #[cfg(feature = "with-wasm-bindgen")]
+    #[wasm_bindgen]
+    impl $struct_name {
+      #[wasm_bindgen(constructor)]
+      /// Wrapper around [`new`](ndsparse::coo::Coo#method.new) where `data` is an array of
+      /// `[indices, value]` pairs.
+      pub fn new_vec(dims_vec: Vec<usize>, data: JsValue) -> Result<$struct_name, JsValue> {
+        let dims: [usize; $dims] = from_vec_to_array(dims_vec)?;
+        let pairs = js_pairs(data)?;
+        let coo = Coo::new(dims, pairs).map_err(js_err)?;
+        Ok($struct_name { coo })
+      }
+
+      /// Wrapper around [`data`](ndsparse::coo::Coo#method.data) that returns an array of
+      /// `[indices, value]` pairs.
+      pub fn data(&self) -> JsValue {
+        let rslt = Array::new();
+        for (indcs, value) in self.coo.data() {
+          let js_indcs = Array::new();
+          for idx in indcs.iter().copied() {
+            js_indcs.push(&JsValue::from_f64(idx as f64));
+          }
+          let pair = Array::new();
+          pair.push(&js_indcs.into());
+          pair.push(&JsValue::from(*value));
+          rslt.push(&pair.into());
+        }
+        rslt.into()
+      }
+
+      /// Wrapper around [`dims`](ndsparse::coo::Coo#method.dims).
+      pub fn dims_vec(&self) -> Vec<usize> {
+        self.coo.dims().to_vec()
+      }
+
+      /// Wrapper around [`value`](ndsparse::coo::Coo#method.value).
+      pub fn value_vec(&self, dims_vec: Vec<usize>) -> Option<$data_ty> {
+        self.coo.value(from_vec_to_array(dims_vec).ok()?).copied()
+      }
+    }
+  };
+}
+
 create_csl!(Csl0VecI32, i32, Vec<i32>, Vec<usize>, Vec<usize>, 0);
 create_csl!(Csl1VecI32, i32, Vec<i32>, Vec<usize>, Vec<usize>, 1);
 create_csl!(Csl2VecI32, i32, Vec<i32>, Vec<usize>, Vec<usize>, 2);
@@ -146,6 +742,412 @@ create_csl!(Csl5VecF64, f64, Vec<f64>, Vec<usize>, Vec<usize>, 5);
 create_csl!(Csl6VecF64, f64, Vec<f64>, Vec<usize>, Vec<usize>, 6);
 create_csl!(Csl7VecF64, f64, Vec<f64>, Vec<usize>, Vec<usize>, 7);
 
+create_coo!(Coo1VecI32, i32, 1);
+create_coo!(Coo2VecI32, i32, 2);
+create_coo!(Coo3VecI32, i32, 3);
+create_coo!(Coo4VecI32, i32, 4);
+create_coo!(Coo5VecI32, i32, 5);
+create_coo!(Coo6VecI32, i32, 6);
+create_coo!(Coo7VecI32, i32, 7);
+
+create_coo!(Coo1VecF64, f64, 1);
+create_coo!(Coo2VecF64, f64, 2);
+create_coo!(Coo3VecF64, f64, 3);
+create_coo!(Coo4VecF64, f64, 4);
+create_coo!(Coo5VecF64, f64, 5);
+create_coo!(Coo6VecF64, f64, 6);
+create_coo!(Coo7VecF64, f64, 7);
+
+/// Python module with all structures and exceptions.
+#[cfg(feature = "with-pyo3")]
+#[pymodule]
+fn ndsparse_bindings(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+  m.add_class::<Csl0VecI32>()?;
+  m.add_class::<Csl1VecI32>()?;
+  m.add_class::<Csl2VecI32>()?;
+  m.add_class::<Csl3VecI32>()?;
+  m.add_class::<Csl4VecI32>()?;
+  m.add_class::<Csl5VecI32>()?;
+  m.add_class::<Csl6VecI32>()?;
+  m.add_class::<Csl7VecI32>()?;
+  m.add_class::<Csl0VecF64>()?;
+  m.add_class::<Csl1VecF64>()?;
+  m.add_class::<Csl2VecF64>()?;
+  m.add_class::<Csl3VecF64>()?;
+  m.add_class::<Csl4VecF64>()?;
+  m.add_class::<Csl5VecF64>()?;
+  m.add_class::<Csl6VecF64>()?;
+  m.add_class::<Csl7VecF64>()?;
+  m.add_class::<Coo1VecI32>()?;
+  m.add_class::<Coo2VecI32>()?;
+  m.add_class::<Coo3VecI32>()?;
+  m.add_class::<Coo4VecI32>()?;
+  m.add_class::<Coo5VecI32>()?;
+  m.add_class::<Coo6VecI32>()?;
+  m.add_class::<Coo7VecI32>()?;
+  m.add_class::<Coo1VecF64>()?;
+  m.add_class::<Coo2VecF64>()?;
+  m.add_class::<Coo3VecF64>()?;
+  m.add_class::<Coo4VecF64>()?;
+  m.add_class::<Coo5VecF64>()?;
+  m.add_class::<Coo6VecF64>()?;
+  m.add_class::<Coo7VecF64>()?;
+  pyo3_errors::add_exceptions(py, m)
+}
+
+/// Structures of half-precision data. Only available to the C API because the other targets
+/// don't understand `bf16` or `f16`.
+#[cfg(all(
+  feature = "with-half",
+  not(any(feature = "with-pyo3", feature = "with-uniffi", feature = "with-wasm-bindgen"))
+))]
+pub mod half_structures {
+  use super::*;
+  use half::{bf16, f16};
+
+  create_csl!(Csl0VecBf16, bf16, Vec<bf16>, Vec<usize>, Vec<usize>, 0);
+  create_csl!(Csl1VecBf16, bf16, Vec<bf16>, Vec<usize>, Vec<usize>, 1);
+  create_csl!(Csl2VecBf16, bf16, Vec<bf16>, Vec<usize>, Vec<usize>, 2);
+  create_csl!(Csl3VecBf16, bf16, Vec<bf16>, Vec<usize>, Vec<usize>, 3);
+  create_csl!(Csl4VecBf16, bf16, Vec<bf16>, Vec<usize>, Vec<usize>, 4);
+  create_csl!(Csl5VecBf16, bf16, Vec<bf16>, Vec<usize>, Vec<usize>, 5);
+  create_csl!(Csl6VecBf16, bf16, Vec<bf16>, Vec<usize>, Vec<usize>, 6);
+  create_csl!(Csl7VecBf16, bf16, Vec<bf16>, Vec<usize>, Vec<usize>, 7);
+
+  create_csl!(Csl0VecF16, f16, Vec<f16>, Vec<usize>, Vec<usize>, 0);
+  create_csl!(Csl1VecF16, f16, Vec<f16>, Vec<usize>, Vec<usize>, 1);
+  create_csl!(Csl2VecF16, f16, Vec<f16>, Vec<usize>, Vec<usize>, 2);
+  create_csl!(Csl3VecF16, f16, Vec<f16>, Vec<usize>, Vec<usize>, 3);
+  create_csl!(Csl4VecF16, f16, Vec<f16>, Vec<usize>, Vec<usize>, 4);
+  create_csl!(Csl5VecF16, f16, Vec<f16>, Vec<usize>, Vec<usize>, 5);
+  create_csl!(Csl6VecF16, f16, Vec<f16>, Vec<usize>, Vec<usize>, 6);
+  create_csl!(Csl7VecF16, f16, Vec<f16>, Vec<usize>, Vec<usize>, 7);
+
+  create_coo!(Coo1VecBf16, bf16, 1);
+  create_coo!(Coo2VecBf16, bf16, 2);
+  create_coo!(Coo3VecBf16, bf16, 3);
+  create_coo!(Coo4VecBf16, bf16, 4);
+  create_coo!(Coo5VecBf16, bf16, 5);
+  create_coo!(Coo6VecBf16, bf16, 6);
+  create_coo!(Coo7VecBf16, bf16, 7);
+
+  create_coo!(Coo1VecF16, f16, 1);
+  create_coo!(Coo2VecF16, f16, 2);
+  create_coo!(Coo3VecF16, f16, 3);
+  create_coo!(Coo4VecF16, f16, 4);
+  create_coo!(Coo5VecF16, f16, 5);
+  create_coo!(Coo6VecF16, f16, 6);
+  create_coo!(Coo7VecF16, f16, 7);
+}
+
+/// Pointer to the first element of `slice` and its length.
+#[cfg(feature = "with-capi")]
+unsafe fn c_raw_parts<T>(slice: &[T], len: *mut usize) -> *const T {
+  *len = slice.len();
+  slice.as_ptr()
+}
+
+/// Same as `slice::from_raw_parts` but `ptr` can be null when `len` is zero.
+#[cfg(feature = "with-capi")]
+unsafe fn c_slice<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+  if len == 0 {
+    &[]
+  } else {
+    std::slice::from_raw_parts(ptr, len)
+  }
+}
+
+/// Indices received from Python, e.g., `csl[1]` or `csl[0, -1]`.
+#[cfg(feature = "with-pyo3")]
+#[derive(Debug, FromPyObject)]
+pub enum PyIndcs {
+  /// A single index
+  One(isize),
+  /// A tuple or list of indices
+  Many(Vec<isize>),
+}
+
+/// Converts Python indices into valid indices. Negative values are counted from the end of their
+/// respective dimension.
+#[cfg(feature = "with-pyo3")]
+fn py_indcs<const N: usize>(dims: &[usize; N], key: PyIndcs) -> PyResult<[usize; N]> {
+  let vec = match key {
+    PyIndcs::One(idx) => vec![idx],
+    PyIndcs::Many(vec) => vec,
+  };
+  if vec.len() != N {
+    let msg = format!("Expected {} indices, got {}", N, vec.len());
+    return Err(exceptions::PyIndexError::new_err(msg));
+  }
+  cl_traits::try_create_array(|idx| {
+    let (dim, py_idx) = (dims[idx], vec[idx]);
+    let normalized = if py_idx < 0 { dim as isize + py_idx } else { py_idx };
+    if normalized < 0 || normalized as usize >= dim {
+      let msg = format!("Index {} is out of bounds for dimension of length {}", py_idx, dim);
+      return Err(exceptions::PyIndexError::new_err(msg));
+    }
+    Ok(normalized as usize)
+  })
+}
+
+/// Collects the non-zero elements of nested sequences with `N` levels and the length of each
+/// level. Ragged sequences are rejected.
+#[cfg(feature = "with-pyo3")]
+fn py_dense<'py, T, const N: usize>(
+  dense: &'py PyAny,
+) -> PyResult<([usize; N], Vec<([usize; N], T)>)>
+where
+  T: Default + FromPyObject<'py> + PartialEq,
+{
+  fn level<'py, T, const N: usize>(
+    obj: &'py PyAny,
+    depth: usize,
+    indcs: &mut [usize; N],
+    dims: &mut [usize; N],
+    pairs: &mut Vec<([usize; N], T)>,
+  ) -> PyResult<()>
+  where
+    T: Default + FromPyObject<'py> + PartialEq,
+  {
+    if depth == N {
+      let value: T = obj.extract()?;
+      if value != T::default() {
+        pairs.push((*indcs, value));
+      }
+      return Ok(());
+    }
+    let len = obj.len()?;
+    if indcs[..depth].iter().all(|&idx| idx == 0) {
+      dims[depth] = len;
+    } else if dims[depth] != len {
+      return Err(exceptions::PyValueError::new_err("Nested sequences must not be ragged"));
+    }
+    for (idx, item) in obj.iter()?.enumerate() {
+      indcs[depth] = idx;
+      level(item?, depth + 1, indcs, dims, pairs)?;
+    }
+    indcs[depth] = 0;
+    Ok(())
+  }
+
+  let mut dims = [0; N];
+  let mut pairs = Vec::new();
+  level(dense, 0, &mut [0; N], &mut dims, &mut pairs)?;
+  Ok((dims, pairs))
+}
+
+/// Converts `(indices, value)` pairs received from Python.
+#[cfg(feature = "with-pyo3")]
+fn py_pairs<T, const N: usize>(data: Vec<(Vec<usize>, T)>) -> PyResult<Vec<([usize; N], T)>> {
+  let mut pairs = Vec::with_capacity(data.len());
+  for (indcs_vec, value) in data {
+    let indcs = cl_traits::try_create_array(|idx| indcs_vec.get(idx).copied().ok_or(()))
+      .map_err(|_| exceptions::PyTypeError::new_err("Insufficient to fill array"))?;
+    pairs.push((indcs, value));
+  }
+  Ok(pairs)
+}
+
+/// Read-only NumPy array that views `slice` without copying. `owner` becomes the base object of
+/// the array, which keeps the underlying memory alive for as long as the array exists.
+///
+/// The exposed methods never reallocate the inner storages, therefore, the view is always valid
+/// but it may show elements that were logically removed by methods like `clear` or `truncate`.
+#[cfg(feature = "with-pyo3")]
+fn py_view<'py, T>(owner: &'py PyAny, slice: &[T]) -> &'py PyArray1<T>
+where
+  T: Element,
+{
+  let mut dims = [slice.len() as npyffi::npy_intp];
+  unsafe {
+    let ptr = PY_ARRAY_API.PyArray_New(
+      PY_ARRAY_API.get_type_object(npyffi::NpyTypes::PyArray_Type),
+      1,
+      dims.as_mut_ptr(),
+      T::npy_type() as c_int,
+      ptr::null_mut(),
+      slice.as_ptr() as *mut c_void,
+      mem::size_of::<T>() as c_int,
+      npyffi::NPY_ARRAY_ALIGNED,
+      ptr::null_mut(),
+    );
+    let _ = PY_ARRAY_API.PyArray_SetBaseObject(ptr as *mut _, owner.into_ptr());
+    PyArray1::from_owned_ptr(owner.py(), ptr)
+  }
+}
+
+#[cfg(feature = "with-uniffi")]
+fn uniffi_array<const N: usize>(vec: Vec<u64>) -> Result<[usize; N], NdsparseError> {
+  let f = |idx| vec.get(idx).map(|&elem| elem as usize).ok_or(());
+  cl_traits::try_create_array(f)
+    .map_err(|_| NdsparseError::Other("Insufficient to fill array".into()))
+}
+
+#[cfg(feature = "with-wasm-bindgen")]
+#[wasm_bindgen]
+extern "C" {
+  type Array;
+
+  #[wasm_bindgen(constructor)]
+  fn new() -> Array;
+
+  #[wasm_bindgen(method, structural, indexing_getter)]
+  fn get(this: &Array, idx: u32) -> JsValue;
+
+  #[wasm_bindgen(static_method_of = Array, js_name = isArray)]
+  fn is_array(value: &JsValue) -> bool;
+
+  #[wasm_bindgen(method, getter)]
+  fn length(this: &Array) -> u32;
+
+  #[wasm_bindgen(method)]
+  fn push(this: &Array, value: &JsValue) -> u32;
+
+  #[wasm_bindgen(catch, js_name = parse, js_namespace = JSON)]
+  fn json_parse(s: &str) -> Result<JsValue, JsValue>;
+}
+
+/// Number that can be written as a JSON value.
+#[cfg(feature = "with-wasm-bindgen")]
+trait JsonNumber {
+  fn push_json(&self, s: &mut String);
+}
+
+#[cfg(feature = "with-wasm-bindgen")]
+impl JsonNumber for f64 {
+  fn push_json(&self, s: &mut String) {
+    if self.is_finite() {
+      s.push_str(&format!("{:?}", self));
+    } else {
+      s.push_str("null");
+    }
+  }
+}
+
+#[cfg(feature = "with-wasm-bindgen")]
+impl JsonNumber for i32 {
+  fn push_json(&self, s: &mut String) {
+    s.push_str(&self.to_string());
+  }
+}
+
+#[cfg(feature = "with-wasm-bindgen")]
+impl JsonNumber for usize {
+  fn push_json(&self, s: &mut String) {
+    s.push_str(&self.to_string());
+  }
+}
+
+#[cfg(feature = "with-wasm-bindgen")]
+fn push_json_array<T>(s: &mut String, slice: &[T])
+where
+  T: JsonNumber,
+{
+  s.push('[');
+  for (idx, elem) in slice.iter().enumerate() {
+    if idx > 0 {
+      s.push(',');
+    }
+    elem.push_json(s);
+  }
+  s.push(']');
+}
+
+/// Full indices and data index of each stored entry.
+#[cfg(feature = "with-wasm-bindgen")]
+fn stored_entries<'a, const N: usize>(
+  dims: &'a [usize; N],
+  indcs: &'a [usize],
+  offs: &'a [usize],
+) -> impl Iterator<Item = ([usize; N], usize)> + 'a {
+  let first = offs.first().copied().unwrap_or_default();
+  let lines = if N == 0 { &offs[..0] } else { offs };
+  lines.windows(2).enumerate().flat_map(move |(line_idx, w)| {
+    (w[0] - first..w[1] - first).map(move |data_idx| {
+      let mut rslt = [0; N];
+      let mut remaining = line_idx;
+      // Zero dimensions don't take part in the numbering of lines.
+      for (r, &dim) in rslt.iter_mut().zip(dims.iter()).rev().skip(1) {
+        if dim != 0 {
+          *r = remaining % dim;
+          remaining /= dim;
+        }
+      }
+      if let Some(last) = rslt.last_mut() {
+        *last = indcs[data_idx];
+      }
+      (rslt, data_idx)
+    })
+  })
+}
+
+#[cfg(feature = "with-wasm-bindgen")]
+fn js_array_iter(value: JsValue) -> Result<impl Iterator<Item = JsValue>, JsValue> {
+  if !Array::is_array(&value) {
+    return Err(JsValue::from_str("Expected an array"));
+  }
+  let array: Array = value.unchecked_into();
+  Ok((0..array.length()).map(move |idx| array.get(idx)))
+}
+
+/// Parses an array of `[indices, value]` pairs.
+#[cfg(feature = "with-wasm-bindgen")]
+fn js_pairs<T, const N: usize>(data: JsValue) -> Result<Vec<([usize; N], T)>, JsValue>
+where
+  T: JsNumber,
+{
+  let mut pairs = Vec::new();
+  for pair in js_array_iter(data)? {
+    let mut pair_iter = js_array_iter(pair)?;
+    let js_indcs = pair_iter.next().ok_or_else(|| JsValue::from_str("Missing indices"))?;
+    let mut indcs_vec = Vec::with_capacity(N);
+    for js_idx in js_array_iter(js_indcs)? {
+      indcs_vec.push(js_usize(&js_idx)?);
+    }
+    let js_value = pair_iter.next().ok_or_else(|| JsValue::from_str("Missing value"))?;
+    let value = js_value.as_f64().ok_or_else(|| JsValue::from_str("Invalid value"))?;
+    pairs.push((from_vec_to_array(indcs_vec)?, T::from_js_number(value)?));
+  }
+  Ok(pairs)
+}
+
+/// Data types that can be created from JS numbers.
+#[cfg(feature = "with-wasm-bindgen")]
+trait JsNumber: Sized {
+  fn from_js_number(number: f64) -> Result<Self, JsValue>;
+}
+
+#[cfg(feature = "with-wasm-bindgen")]
+impl JsNumber for f64 {
+  fn from_js_number(number: f64) -> Result<Self, JsValue> {
+    Ok(number)
+  }
+}
+
+/// `NaN`, infinities, fractional and out of range numbers are rejected instead of being silently
+/// truncated or saturated.
+#[cfg(feature = "with-wasm-bindgen")]
+impl JsNumber for i32 {
+  fn from_js_number(number: f64) -> Result<Self, JsValue> {
+    let is_in_range = number >= f64::from(i32::MIN) && number <= f64::from(i32::MAX);
+    if !number.is_finite() || number.fract() != 0.0 || !is_in_range {
+      return Err(JsValue::from_str("Values must be 32-bit integers"));
+    }
+    Ok(number as i32)
+  }
+}
+
+/// Converts a JS number into an index. `NaN`, infinities, negative and fractional numbers are
+/// rejected instead of being silently truncated or saturated.
+#[cfg(feature = "with-wasm-bindgen")]
+fn js_usize(value: &JsValue) -> Result<usize, JsValue> {
+  let number = value.as_f64().ok_or_else(|| JsValue::from_str("Invalid index"))?;
+  if !number.is_finite() || number < 0.0 || number.fract() != 0.0 || number > usize::MAX as f64 {
+    return Err(JsValue::from_str("Indices must be non-negative integers"));
+  }
+  Ok(number as usize)
+}
+
 #[cfg(feature = "with-wasm-bindgen")]
 fn from_vec_to_array<const N: usize>(vec: Vec<usize>) -> Result<[usize; N], JsValue> {
   let f = |idx| vec.get(idx).copied().ok_or(());
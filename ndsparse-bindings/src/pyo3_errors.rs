@@ -0,0 +1,113 @@
+//! Python exceptions that mirror the error hierarchy of `ndsparse`.
+//!
+//! Every variant of `CooError`, `CslError` and `CslLineConstructorError` has its own class that
+//! inherits from the exception of its respective enum, which in turn inherits from
+//! `NdsparseError`.
+
+use ndsparse::{coo, csl};
+use pyo3::{create_exception, exceptions::PyException, types::PyModule, PyErr, PyResult, Python};
+
+create_exception!(ndsparse_bindings, NdsparseError, PyException);
+create_exception!(ndsparse_bindings, CooError, NdsparseError);
+create_exception!(ndsparse_bindings, CslError, NdsparseError);
+create_exception!(ndsparse_bindings, CslLineConstructorError, NdsparseError);
+create_exception!(ndsparse_bindings, InsufficientCapacityError, NdsparseError);
+
+macro_rules! create_variant_exceptions {
+  (
+    $fn_name:ident,
+    $add_fn_name:ident,
+    $module:ident::$enum:ident,
+    $parent:ident,
+    $($variant:ident => $class:ident),* $(,)?
+  ) => {
+    $(create_exception!(ndsparse_bindings, $class, $parent);)*
+
+    fn $fn_name(err: $module::$enum) -> PyErr {
+      let msg = err.to_string();
+      match err {
+        $($module::$enum::$variant { .. } => $class::new_err(msg),)*
+        _ => $parent::new_err(msg),
+      }
+    }
+
+    fn $add_fn_name(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+      $(m.add(stringify!($class), py.get_type::<$class>())?;)*
+      Ok(())
+    }
+  };
+}
+
+create_variant_exceptions!(
+  coo_py_err,
+  add_coo_exceptions,
+  coo::CooError,
+  CooError,
+  DuplicatedIndices => CooDuplicatedIndicesError,
+  InvalidArrayLength => CooInvalidArrayLengthError,
+  InvalidChunkSize => CooInvalidChunkSizeError,
+  InvalidIndcs => CooInvalidIndcsError,
+  InvalidIndcsOrder => CooInvalidIndcsOrderError,
+  InvalidPermutation => CooInvalidPermutationError,
+);
+
+create_variant_exceptions!(
+  csl_py_err,
+  add_csl_exceptions,
+  csl::CslError,
+  CslError,
+  DataIndcsLengthGreaterThanDimsLength => CslDataIndcsLengthGreaterThanDimsLengthError,
+  DiffDataIndcsLength => CslDiffDataIndcsLengthError,
+  DiffDims => CslDiffDimsError,
+  DimsProductOverflow => CslDimsProductOverflowError,
+  DuplicatedIndices => CslDuplicatedIndicesError,
+  IndcsGreaterThanEqualDimLength => CslIndcsGreaterThanEqualDimLengthError,
+  IndexOverflow => CslIndexOverflowError,
+  InnermostDimsZero => CslInnermostDimsZeroError,
+  InvalidArrayLength => CslInvalidArrayLengthError,
+  InvalidAxis => CslInvalidAxisError,
+  InvalidChunkSize => CslInvalidChunkSizeError,
+  InvalidDenseLength => CslInvalidDenseLengthError,
+  InvalidIterDim => CslInvalidIterDimError,
+  InvalidMapping => CslInvalidMappingError,
+  InvalidOffsetsLength => CslInvalidOffsetsLengthError,
+  InvalidOffsetsOrder => CslInvalidOffsetsOrderError,
+  InvalidPermutation => CslInvalidPermutationError,
+  LastOffsetDifferentNnz => CslLastOffsetDifferentNnzError,
+  OffsLengthOverflow => CslOffsLengthOverflowError,
+  UnsortedIndices => CslUnsortedIndicesError,
+);
+
+create_variant_exceptions!(
+  csl_line_constructor_py_err,
+  add_csl_line_constructor_exceptions,
+  csl::CslLineConstructorError,
+  CslLineConstructorError,
+  DimsOverflow => CslLineConstructorDimsOverflowError,
+  EmptyDimension => CslLineConstructorEmptyDimensionError,
+  MaxNumOfLines => CslLineConstructorMaxNumOfLinesError,
+  UnsortedIndices => CslLineConstructorUnsortedIndicesError,
+);
+
+/// Adds all exceptions to the Python module `m`.
+pub(crate) fn add_exceptions(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+  m.add("NdsparseError", py.get_type::<NdsparseError>())?;
+  m.add("CooError", py.get_type::<CooError>())?;
+  m.add("CslError", py.get_type::<CslError>())?;
+  m.add("CslLineConstructorError", py.get_type::<CslLineConstructorError>())?;
+  m.add("InsufficientCapacityError", py.get_type::<InsufficientCapacityError>())?;
+  add_coo_exceptions(py, m)?;
+  add_csl_exceptions(py, m)?;
+  add_csl_line_constructor_exceptions(py, m)
+}
+
+/// Converts an `ndsparse` error into the most specific Python exception.
+pub(crate) fn py_err(err: ndsparse::Error) -> PyErr {
+  match err {
+    ndsparse::Error::Coo(e) => coo_py_err(e),
+    ndsparse::Error::Csl(e) => csl_py_err(e),
+    ndsparse::Error::CslLineConstructor(e) => csl_line_constructor_py_err(e),
+    ndsparse::Error::InsufficientCapacity => InsufficientCapacityError::new_err(err.to_string()),
+    _ => NdsparseError::new_err(err.to_string()),
+  }
+}
@@ -0,0 +1,43 @@
+//! UniFFI error that mirrors the error hierarchy of `ndsparse`.
+//!
+//! Kotlin and Swift receive a sealed class/enum with one case for each originating enum and the
+//! message of the variant.
+
+use core::fmt;
+
+/// Any error raised by the UniFFI wrappers
+#[derive(Debug, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum NdsparseError {
+  /// CooError
+  Coo(String),
+  /// CslError
+  Csl(String),
+  /// CslLineConstructorError
+  CslLineConstructor(String),
+  /// Any other error, including invalid arguments
+  Other(String),
+}
+
+impl fmt::Display for NdsparseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match *self {
+      Self::Coo(ref x) => write!(f, "Coo({})", x),
+      Self::Csl(ref x) => write!(f, "Csl({})", x),
+      Self::CslLineConstructor(ref x) => write!(f, "CslLineConstructor({})", x),
+      Self::Other(ref x) => write!(f, "{}", x),
+    }
+  }
+}
+
+impl std::error::Error for NdsparseError {}
+
+/// Converts an `ndsparse` error into the most specific UniFFI error.
+pub(crate) fn uniffi_err(err: ndsparse::Error) -> NdsparseError {
+  match err {
+    ndsparse::Error::Coo(e) => NdsparseError::Coo(e.to_string()),
+    ndsparse::Error::Csl(e) => NdsparseError::Csl(e.to_string()),
+    ndsparse::Error::CslLineConstructor(e) => NdsparseError::CslLineConstructor(e.to_string()),
+    _ => NdsparseError::Other(err.to_string()),
+  }
+}
@@ -0,0 +1,59 @@
+//! JavaScript `Error` subclasses that mirror the error hierarchy of `ndsparse`.
+//!
+//! All thrown errors are instances of `NdsparseError` and carry a `code` property with the name
+//! of the originating variant, e.g., `err instanceof CslError && err.code === "DiffDims"`.
+
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(inline_js = "
+export class NdsparseError extends Error {
+  constructor(message, code) {
+    super(message);
+    this.name = new.target.name;
+    this.code = code;
+  }
+}
+export class CooError extends NdsparseError {}
+export class CslError extends NdsparseError {}
+export class CslLineConstructorError extends NdsparseError {}
+")]
+extern "C" {
+  type NdsparseError;
+
+  #[wasm_bindgen(constructor)]
+  fn new(message: &str, code: &str) -> NdsparseError;
+
+  type CooError;
+
+  #[wasm_bindgen(constructor)]
+  fn new(message: &str, code: &str) -> CooError;
+
+  type CslError;
+
+  #[wasm_bindgen(constructor)]
+  fn new(message: &str, code: &str) -> CslError;
+
+  type CslLineConstructorError;
+
+  #[wasm_bindgen(constructor)]
+  fn new(message: &str, code: &str) -> CslLineConstructorError;
+}
+
+/// Converts an `ndsparse` error into the most specific JavaScript error.
+pub(crate) fn js_err(err: ndsparse::Error) -> JsValue {
+  let msg = err.to_string();
+  match err {
+    ndsparse::Error::Coo(e) => CooError::new(&msg, &variant_name(&e)).into(),
+    ndsparse::Error::Csl(e) => CslError::new(&msg, &variant_name(&e)).into(),
+    ndsparse::Error::CslLineConstructor(e) => {
+      CslLineConstructorError::new(&msg, &variant_name(&e)).into()
+    }
+    _ => NdsparseError::new(&msg, &variant_name(&err)).into(),
+  }
+}
+
+// Variants with context are debug-formatted as `Variant { field: value }`, only the name matters.
+fn variant_name(err: &impl core::fmt::Debug) -> String {
+  let debug = format!("{:?}", err);
+  debug.split(|c: char| c == ' ' || c == '(').next().unwrap_or_default().into()
+}
@@ -5,7 +5,7 @@
   clippy::panic
 )]
 
-use ndsparse::csl::{Csl, CslRef};
+use ndsparse::csl::{Csl, CslLineConstructorError, CslRef};
 
 /// CslArrayVec
 pub type CslArrayVec<DATA, const D: usize, const NNZ: usize, const OFFS: usize> = Csl<
@@ -26,5 +26,9 @@ fn main() -> ndsparse::Result<()> {
   small_vec.constructor()?.next_outermost_dim(5)?.push_line([(0, 1), (3, 2)].iter().copied())?;
   assert!(array_vec.line([0, 0]) == Some(CslRef::new([5], &[1, 2][..], &[0, 3][..], &[0, 2][..])?));
   assert!(array_vec.line([0, 0]) == small_vec.line([0, 0]));
+  let full = (0..5).map(|idx| (idx, 3));
+  let err = CslLineConstructorError::InsufficientCapacity { consumed: 4 };
+  assert!(array_vec.constructor()?.try_push_line(full).err() == Some(ndsparse::Error::from(err)));
+  assert!(array_vec.data() == [1, 2]);
   Ok(())
 }
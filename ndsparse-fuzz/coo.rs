@@ -24,5 +24,7 @@ fuzz_target!(|values: Values| {
     return;
   };
 
+  assert_eq!(coo.validate(), Ok(()));
+
   let _ = coo.value(values.value);
 });
@@ -2,6 +2,7 @@
 
 #![no_main]
 
+use core::convert::TryFrom;
 use libfuzzer_sys::fuzz_target;
 use ndsparse::csl::CslVec;
 use rayon::prelude::*;
@@ -15,6 +16,7 @@ struct Values {
   indcs: Vec<usize>,
   line: Array,
   offs: Vec<usize>,
+  sub_dim: (usize, usize),
   value: Array,
 }
 
@@ -27,12 +29,27 @@ fuzz_target!(|values: Values| {
     return;
   };
 
+  assert_eq!(csl.validate(), Ok(()));
+
+  let max_nnz = csl.checked_max_nnz().and_then(|elem| usize::try_from(elem).ok());
+  assert!(max_nnz.filter(|&elem| csl.nnz() <= elem).is_some());
+
   let _ = csl.line(values.line);
 
   let _ = csl.value(values.value);
 
+  let (sub_dim_start, sub_dim_end) = values.sub_dim;
+  if let Some(r) = csl.sub_dim::<2>(sub_dim_start..sub_dim_end) {
+    let _ = r.line([0, 0]);
+  }
+  if let Some(r) = csl.sub_dim::<3>(sub_dim_start..sub_dim_end) {
+    let _ = r.value([0, 0, 0]);
+  }
+
   if let Ok(r) = csl.outermost_line_iter() {
-    r.for_each(|_| {});
+    r.for_each(|line| {
+      let _ = line.sub_dim::<2>(sub_dim_start..sub_dim_end);
+    });
   } else {
     return;
   };
@@ -0,0 +1,26 @@
+//! Valid COO
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ndsparse::{coo::CooVec, csl::CslVec};
+
+type Array = [usize; 3];
+
+#[derive(Debug, arbitrary::Arbitrary)]
+struct Values {
+  coo: CooVec<i32, 3>,
+  value: Array,
+}
+
+fuzz_target!(|values: Values| {
+  let coo = values.coo;
+
+  assert_eq!(coo.validate(), Ok(()));
+
+  let _ = coo.value(values.value);
+
+  let csl: CslVec<i32, 3> = if let Ok(r) = coo.to_csl() { r } else { return };
+  assert_eq!(csl.validate(), Ok(()));
+  assert_eq!(csl.nnz(), coo.data().len());
+});
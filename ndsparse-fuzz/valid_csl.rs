@@ -0,0 +1,37 @@
+//! Valid CSL
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ndsparse::csl::CslVec;
+
+type Array = [usize; 3];
+
+#[derive(Debug, arbitrary::Arbitrary)]
+struct Values {
+  csl: CslVec<i32, 3>,
+  line: Array,
+  sub_dim: (usize, usize),
+  value: Array,
+}
+
+fuzz_target!(|values: Values| {
+  let csl = values.csl;
+
+  assert_eq!(csl.validate(), Ok(()));
+
+  let _ = csl.line(values.line);
+
+  let _ = csl.value(values.value);
+
+  let (sub_dim_start, sub_dim_end) = values.sub_dim;
+  if let Some(r) = csl.sub_dim::<2>(sub_dim_start..sub_dim_end) {
+    let _ = r.line([0, 0]);
+  }
+
+  if let Ok(r) = csl.outermost_line_iter() {
+    r.for_each(|line| {
+      let _ = line.sub_dim::<2>(sub_dim_start..sub_dim_end);
+    });
+  }
+});
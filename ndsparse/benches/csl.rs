@@ -0,0 +1,76 @@
+//! CSL benchmarks
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ndsparse::csl::CslVec;
+
+const DIMS_2: [usize; 2] = [1_000, 1_000];
+const DIMS_6: [usize; 6] = [4, 5, 6, 7, 8, 1_000];
+const NNZ_PER_LINE: usize = 10;
+
+// Deterministic storages with `NNZ_PER_LINE` evenly spaced elements per line.
+fn parts<const D: usize>(dims: [usize; D]) -> (Vec<f64>, Vec<usize>, Vec<usize>) {
+  let innermost = dims.last().copied().unwrap_or_default();
+  let lines: usize = dims.iter().rev().skip(1).product();
+  let step = innermost / NNZ_PER_LINE;
+  let mut data = Vec::with_capacity(lines * NNZ_PER_LINE);
+  let mut indcs = Vec::with_capacity(lines * NNZ_PER_LINE);
+  let mut offs = Vec::with_capacity(lines + 1);
+  offs.push(0);
+  for line in 0..lines {
+    for idx in 0..NNZ_PER_LINE {
+      data.push((line + idx) as f64);
+      indcs.push(idx * step + line % step);
+    }
+    offs.push(data.len());
+  }
+  (data, indcs, offs)
+}
+
+fn csl<const D: usize>(dims: [usize; D]) -> CslVec<f64, D> {
+  let (data, indcs, offs) = parts(dims);
+  CslVec::new(dims, data, indcs, offs).unwrap()
+}
+
+fn construction(c: &mut Criterion) {
+  let (data, indcs, offs) = parts(DIMS_2);
+  c.bench_function("construction", |b| {
+    b.iter(|| CslVec::new(DIMS_2, data.clone(), indcs.clone(), offs.clone()).unwrap())
+  });
+}
+
+fn iteration(c: &mut Criterion) {
+  let csl = csl(DIMS_2);
+  c.bench_function("iteration", |b| {
+    b.iter(|| csl.outermost_line_iter().unwrap().map(|line| line.nnz()).sum::<usize>())
+  });
+}
+
+fn spmv(c: &mut Criterion) {
+  let csl = csl(DIMS_2);
+  let x: Vec<f64> = (0..DIMS_2[1]).map(|idx| idx as f64).collect();
+  let mut y = vec![0.0; DIMS_2[0]];
+  c.bench_function("spmv", |b| {
+    b.iter(|| {
+      for (elem, line) in y.iter_mut().zip(csl.outermost_line_iter().unwrap()) {
+        *elem = line.indcs().iter().zip(line.data()).map(|(&idx, value)| value * x[idx]).sum();
+      }
+      black_box(&y);
+    })
+  });
+}
+
+fn value_lookup(c: &mut Criterion) {
+  let csl = csl(DIMS_6);
+  c.bench_function("value_lookup_6d", |b| {
+    b.iter(|| {
+      let mut sum = 0.0;
+      for idx in 0..DIMS_6[4] {
+        sum += csl.value(black_box([3, 4, 5, 6, idx, 0])).copied().unwrap_or_default();
+      }
+      sum
+    })
+  });
+}
+
+criterion_group!(benches, construction, iteration, spmv, value_lookup);
+criterion_main!(benches);
@@ -1,13 +1,28 @@
 //! COO (Coordinate) format for N-dimensions.
 
+#[cfg(all(feature = "alloc", feature = "with-arbitrary"))]
+mod coo_arbitrary;
 mod coo_error;
+mod coo_iter;
+#[cfg(feature = "with-quickcheck")]
+mod coo_quickcheck;
+#[cfg(feature = "with-rayon")]
+mod coo_rayon;
 mod coo_utils;
 
+use crate::{
+  csl::{correct_offs_len, has_innermost_dims_zero, line_idx, outermost_stride, Csl, CslError},
+  MemoryUsage,
+};
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
-use cl_traits::Storage;
-pub use coo_error::*;
+use cl_traits::{Insert, Push, Storage, Truncate};
 use coo_utils::*;
+use core::{
+  mem,
+  ops::{AddAssign, Range},
+};
+pub use {coo_error::*, coo_iter::*};
 
 /// COO backed by a static array.
 pub type CooArray<DATA, const D: usize, const DT: usize> = Coo<[([usize; D], DATA); DT], D>;
@@ -24,6 +39,10 @@ pub type CooVec<DATA, const D: usize> = Coo<Vec<([usize; D], DATA)>, D>;
 
 /// Base structure for all COO* variants.
 ///
+/// Stored items are always sorted in lexicographic order of their indices, i.e., sorted by the
+/// outermost dimension first and by the innermost dimension last. This invariant is what allows
+/// cheap conversions into line based structures like [`Csl`](crate::csl::Csl).
+///
 /// # Types
 ///
 /// * `DA`: Data Array
@@ -42,7 +61,7 @@ impl<DS, const D: usize> Coo<DS, D> {
   /// # Example
   ///
   /// ```rust
-  /// use ndsparse::doc_tests::coo_array_5;
+  /// use ndsparse::fixtures::coo_array_5;
   /// assert_eq!(coo_array_5().dims(), &[2, 3, 4, 3, 3]);
   /// ```
   #[inline]
@@ -74,35 +93,130 @@ where
   /// ```
   #[inline]
   pub fn new(dims: [usize; D], data: DS) -> crate::Result<Self> {
-    if !crate::utils::are_in_ascending_order(data.as_ref(), |a, b| [&a.0, &b.0]) {
-      return Err(CooError::InvalidIndcsOrder.into());
-    }
-    let has_invalid_indcs = !data.as_ref().iter().all(|&(indcs, _)| {
-      indcs.iter().zip(dims.iter()).all(
-        |(data_idx, dim)| {
-          if dim == &0 {
-            true
-          } else {
-            data_idx < dim
-          }
-        },
-      )
-    });
-    if has_invalid_indcs {
-      return Err(CooError::InvalidIndcs.into());
-    }
-    if !does_not_have_duplicates_sorted(data.as_ref(), |a, b| a.0[..] != b.0[..]) {
-      return Err(CooError::DuplicatedIndices.into());
-    }
+    validate(&dims, data.as_ref())?;
     Ok(Self { data, dims })
   }
 
+  /// Smallest and greatest stored index of each dimension or `None` if there are no stored
+  /// elements, i.e., the tight extent of the occupied space.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::coo::CooArray;
+  /// let coo = CooArray::new([10, 10], [([2, 5], 1), ([4, 3], 2), ([6, 7], 3)]).unwrap();
+  /// assert_eq!(coo.bounds(), Some([(2, 6), (3, 7)]));
+  /// assert_eq!(CooArray::<i32, 2, 0>::new([10, 10], []).unwrap().bounds(), None);
+  /// ```
+  #[inline]
+  pub fn bounds(&self) -> Option<[(usize, usize); D]> {
+    bounds(self.data.as_ref())
+  }
+
+  /// Consecutive hyperslabs of `chunk_size` outermost indices. The last one can be shorter and
+  /// slabs without elements are also yielded.
+  ///
+  /// Each item borrows the elements of its slab and keeps the indices and the dimensions of
+  /// `self`, therefore, nothing is copied or allocated.
+  ///
+  /// # Arguments
+  ///
+  /// * `chunk_size`: Number of outermost indices of each slab
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::coo_array_5;
+  /// let coo = coo_array_5();
+  /// let mut chunks = coo.chunks_by_outer(1).unwrap();
+  /// assert_eq!(chunks.next().map(|chunk| chunk.data().len()), Some(4));
+  /// let last = chunks.next().unwrap();
+  /// assert_eq!(last.dims(), &[2, 3, 4, 3, 3]);
+  /// assert_eq!(last.value([1, 2, 3, 2, 2]), Some(&7));
+  /// assert!(chunks.next().is_none());
+  /// ```
+  #[inline]
+  pub fn chunks_by_outer<'a>(
+    &'a self,
+    chunk_size: usize,
+  ) -> crate::Result<impl Iterator<Item = CooRef<'a, DATA, D>> + 'a>
+  where
+    DATA: 'a,
+  {
+    if chunk_size == 0 {
+      return Err(CooError::InvalidChunkSize.into());
+    }
+    let data = self.data.as_ref();
+    let dims = self.dims;
+    let outermost = |elem: &([usize; D], DATA)| elem.0.first().copied().unwrap_or_default();
+    let outermost_len = match dims.first().copied() {
+      Some(0) => data.last().map(|elem| outermost(elem) + 1).unwrap_or_default(),
+      Some(len) => len,
+      None => 0,
+    };
+    let num_chunks = outermost_len / chunk_size + usize::from(outermost_len % chunk_size != 0);
+    Ok((0..num_chunks).map(move |chunk| {
+      let start = chunk * chunk_size;
+      let end = start.saturating_add(chunk_size);
+      let begin = data.partition_point(|elem| outermost(elem) < start);
+      let finish = data.partition_point(|elem| outermost(elem) < end);
+      Coo { data: data.get(begin..finish).unwrap_or_default(), dims }
+    }))
+  }
+
+  /// Number of stored elements whose indices satisfy `cb`.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::coo_array_5;
+  /// assert_eq!(coo_array_5().count_where(|indcs| indcs[0] == 1), 3);
+  /// ```
+  #[inline]
+  pub fn count_where<F>(&self, mut cb: F) -> usize
+  where
+    F: FnMut(&[usize; D]) -> bool,
+  {
+    self.data.as_ref().iter().filter(|(indcs, _)| cb(indcs)).count()
+  }
+
+  /// Copies the elements whose indices are within the inclusive `bounds` of each dimension into
+  /// a new structure whose indices start at the lower bounds.
+  ///
+  /// # Arguments
+  ///
+  /// * `bounds`: Smallest and greatest index of each dimension, like the ones returned by
+  ///   [`bounds`](#method.bounds)
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::coo::CooArray;
+  /// let coo = CooArray::new([10, 10], [([2, 5], 1), ([4, 3], 2), ([6, 7], 3)])?;
+  /// let tight = coo.crop(coo.bounds().unwrap_or_default())?;
+  /// assert_eq!(tight.dims(), &[5, 5]);
+  /// assert_eq!(tight.data(), &[([0, 2], 1), ([2, 0], 2), ([4, 4], 3)]);
+  /// let window = coo.crop([(3, 6), (0, 9)])?;
+  /// assert_eq!(window.dims(), &[4, 10]);
+  /// assert_eq!(window.data(), &[([1, 3], 2), ([3, 7], 3)]);
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn crop(&self, bounds: [(usize, usize); D]) -> crate::Result<CooVec<DATA, D>>
+  where
+    DATA: Clone,
+  {
+    crop(&self.dims, self.data.as_ref(), bounds)
+  }
+
   /// The data that is being stored.
   ///
   /// # Example
   ///
   /// ```rust
-  /// use ndsparse::doc_tests::coo_array_5;
+  /// use ndsparse::fixtures::coo_array_5;
   /// assert_eq!(coo_array_5().data().first(), Some(&([0, 0, 1, 1, 2].into(), 1)));
   /// ```
   #[inline]
@@ -110,6 +224,352 @@ where
     self.data.as_ref()
   }
 
+  /// Iterator over the indices and values of all stored elements.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::coo_array_5;
+  /// let coo = coo_array_5();
+  /// assert_eq!(coo.iter().nth(6), Some((&[1, 2, 3, 2, 2], &7)));
+  /// assert_eq!(coo.iter().len(), 7);
+  /// ```
+  #[inline]
+  pub fn iter(&self) -> CooIterRef<'_, DATA, D> {
+    CooIterRef::new(self.data.as_ref())
+  }
+
+  /// Number of bytes occupied by the stored elements. Indices are stored alongside the data,
+  /// therefore, `offs_bytes` is always zero and the padding of each element is part of
+  /// `overhead`.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use core::mem::{size_of, size_of_val};
+  /// use ndsparse::fixtures::coo_array_5;
+  /// let coo = coo_array_5();
+  /// let usage = coo.memory_usage();
+  /// assert_eq!(usage.data_bytes, 7 * size_of::<i32>());
+  /// assert_eq!(usage.indcs_bytes, 7 * size_of::<[usize; 5]>());
+  /// assert_eq!(usage.offs_bytes, 0);
+  /// assert!(usage.overhead >= size_of_val(&coo));
+  /// ```
+  #[inline]
+  pub fn memory_usage(&self) -> MemoryUsage {
+    let len = self.data.as_ref().len();
+    let data_bytes = len.saturating_mul(mem::size_of::<DATA>());
+    let indcs_bytes = len.saturating_mul(mem::size_of::<[usize; D]>());
+    let elements_bytes = len.saturating_mul(mem::size_of::<([usize; D], DATA)>());
+    let padding = elements_bytes.saturating_sub(data_bytes).saturating_sub(indcs_bytes);
+    MemoryUsage {
+      data_bytes,
+      indcs_bytes,
+      offs_bytes: 0,
+      overhead: mem::size_of::<Self>().saturating_add(padding),
+    }
+  }
+
+  /// Number of stored elements whose indices are contained by the respective `ranges`.
+  ///
+  /// # Arguments
+  ///
+  /// * `ranges`: Range of each dimension
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::coo_array_5;
+  /// let coo = coo_array_5();
+  /// assert_eq!(coo.nnz_in_slice([0..2, 1..3, 0..4, 0..3, 0..3]), 6);
+  /// assert_eq!(coo.nnz_in_slice([1..2, 0..3, 3..4, 0..3, 2..3]), 2);
+  /// ```
+  #[inline]
+  pub fn nnz_in_slice(&self, ranges: [Range<usize>; D]) -> usize {
+    let is_contained =
+      |indcs: &[usize; D]| indcs.iter().zip(ranges.iter()).all(|(idx, range)| range.contains(idx));
+    self.count_where(is_contained)
+  }
+
+  /// Parallel version of [`iter`](#method.iter) using `rayon`.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-rayon", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-rayon"), doc = "```ignore")]
+  /// use ndsparse::fixtures::coo_array_5;
+  /// use rayon::prelude::*;
+  /// let coo = coo_array_5();
+  /// assert_eq!(coo.par_iter().map(|(_, value)| value).sum::<i32>(), 28);
+  /// assert_eq!(coo.par_iter().skip(6).collect::<Vec<_>>(), vec![(&[1, 2, 3, 2, 2], &7)]);
+  /// ```
+  #[cfg(feature = "with-rayon")]
+  #[inline]
+  pub fn par_iter(&self) -> crate::ParallelIteratorWrapper<CooIterRef<'_, DATA, D>> {
+    crate::ParallelIteratorWrapper(self.iter())
+  }
+
+  /// Sums all stored elements in parallel using `rayon`.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-rayon", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-rayon"), doc = "```ignore")]
+  /// use ndsparse::fixtures::coo_array_5;
+  /// assert_eq!(coo_array_5().par_sum(), 28);
+  /// ```
+  #[cfg(feature = "with-rayon")]
+  #[inline]
+  pub fn par_sum(&self) -> DATA
+  where
+    DATA: Clone + Send + Sync + core::iter::Sum,
+  {
+    use rayon::prelude::*;
+    self.data.as_ref().par_iter().map(|elem| elem.1.clone()).sum()
+  }
+
+  /// Reorders the outermost dimension according to `perm`, i.e., the new outermost index `idx`
+  /// will contain the elements of the old outermost index `perm[idx]`.
+  ///
+  /// # Arguments
+  ///
+  /// * `perm`: Permutation of all outermost indices
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::fixtures::coo_array_5;
+  /// let coo = coo_array_5();
+  /// let permuted = coo.permute_outermost(&[1, 0])?;
+  /// assert_eq!(permuted.value([0, 1, 0, 2, 1]), Some(&5));
+  /// assert_eq!(permuted.value([1, 0, 1, 1, 2]), Some(&1));
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn permute_outermost(&self, perm: &[usize]) -> crate::Result<CooVec<DATA, D>>
+  where
+    DATA: Clone,
+  {
+    permute_outermost(self.dims, self.data.as_ref(), perm)
+  }
+
+  /// Iterator over the stored elements whose indices are contained by the respective `ranges`.
+  ///
+  /// The elements of the outermost range are located with binary searches, hence only the
+  /// elements of the selected outermost indices are visited.
+  ///
+  /// # Arguments
+  ///
+  /// * `ranges`: Range of each dimension
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::coo_array_5;
+  /// let coo = coo_array_5();
+  /// let mut iter = coo.range_query([1..2, 0..3, 3..4, 0..3, 2..3]);
+  /// assert_eq!(iter.next(), Some((&[1, 2, 3, 0, 2], &6)));
+  /// assert_eq!(iter.next(), Some((&[1, 2, 3, 2, 2], &7)));
+  /// assert_eq!(iter.next(), None);
+  /// assert_eq!(coo.range_query([0..2, 1..3, 0..4, 0..3, 0..3]).count(), 6);
+  /// ```
+  #[inline]
+  pub fn range_query<'a>(
+    &'a self,
+    ranges: [Range<usize>; D],
+  ) -> impl Iterator<Item = (&'a [usize; D], &'a DATA)> + 'a
+  where
+    DATA: 'a,
+  {
+    let data = self.data.as_ref();
+    let outermost = |elem: &([usize; D], DATA)| elem.0.first().copied().unwrap_or_default();
+    let (start, end) =
+      ranges.first().map(|range| (range.start, range.end)).unwrap_or((0, usize::MAX));
+    let begin = data.partition_point(|elem| outermost(elem) < start);
+    let finish = data.partition_point(|elem| outermost(elem) < end);
+    data.get(begin..finish).unwrap_or_default().iter().filter_map(move |(indcs, value)| {
+      let mut iter = indcs.iter().zip(ranges.iter()).skip(1);
+      iter.all(|(idx, range)| range.contains(idx)).then_some((indcs, value))
+    })
+  }
+
+  /// Cyclically shifts the stored elements by `amount` positions along `axis`, like
+  /// `numpy.roll`. Elements that go beyond the last position are reintroduced at the first
+  /// position and negative amounts shift towards the first position.
+  ///
+  /// # Arguments
+  ///
+  /// * `axis`: Dimension whose indices will be shifted
+  /// * `amount`: Number of positions
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::fixtures::coo_array_5;
+  /// let coo = coo_array_5();
+  /// let rolled = coo.roll(1, 1)?;
+  /// assert_eq!(rolled.value([0, 0, 2, 0, 1]), Some(&4));
+  /// assert_eq!(rolled.value([0, 1, 1, 1, 2]), Some(&1));
+  /// assert_eq!(rolled.roll(1, -1)?.data(), coo.data());
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn roll(&self, axis: usize, amount: isize) -> crate::Result<CooVec<DATA, D>>
+  where
+    DATA: Clone,
+  {
+    roll(&self.dims, self.data.as_ref(), axis, amount)
+  }
+
+  /// Removes the dimension `axis`, whose length must be one, and returns a structure with `TD`
+  /// dimensions, i.e., `D - 1`.
+  ///
+  /// # Arguments
+  ///
+  /// * `axis`: Dimension that will be removed
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::coo::CooArray;
+  /// let coo = CooArray::new([1, 3], [([0, 1], 8), ([0, 2], 9)])?;
+  /// let squeezed = coo.squeeze::<1>(0)?;
+  /// assert_eq!(squeezed.dims(), &[3]);
+  /// assert_eq!(squeezed.data(), &[([1], 8), ([2], 9)]);
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn squeeze<const TD: usize>(&self, axis: usize) -> crate::Result<CooVec<DATA, TD>>
+  where
+    DATA: Clone,
+  {
+    squeeze(&self.dims, self.data.as_ref(), axis)
+  }
+
+  /// Converts this instance into an equivalent CSL structure.
+  ///
+  /// Because all items are already sorted line by line, the conversion is performed in a single
+  /// pass with `O(nnz)` complexity and without any intermediate allocation.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{csl::CslVec, fixtures::coo_array_5};
+  /// let coo = coo_array_5();
+  /// let csl: CslVec<i32, 5> = coo.to_csl()?;
+  /// assert_eq!(csl.nnz(), coo.data().len());
+  /// assert!(coo.data().iter().all(|(indcs, value)| csl.value(*indcs) == Some(value)));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn to_csl<DS2, IS2, OS2>(&self) -> crate::Result<Csl<DS2, IS2, OS2, D>>
+  where
+    DATA: Clone,
+    DS2: AsRef<[DATA]> + Default + Push<Input = DATA> + Storage<Item = DATA>,
+    IS2: AsRef<[usize]> + Default + Push<Input = usize>,
+    OS2: AsRef<[usize]> + Default + Push<Input = usize>,
+  {
+    let outermost_stride = outermost_stride(&self.dims);
+    let mut csl = Csl { dims: self.dims, outermost_stride, ..Default::default() };
+    let last_dim = if let Some(r) = self.dims.last() {
+      *r
+    } else {
+      return Ok(csl);
+    };
+    if has_innermost_dims_zero(&self.dims) {
+      return Err(CslError::InnermostDimsZero.into());
+    }
+    let offs_len = correct_offs_len(&self.dims)?;
+    let _ = csl.offs.push(0).map_err(|_err| crate::Error::InsufficientCapacity)?;
+    let mut curr_line = 0;
+    for (nnz, (indcs, value)) in self.data().iter().enumerate() {
+      let innermost_idx = indcs.last().copied().unwrap_or_default();
+      if innermost_idx >= last_dim {
+        let idx = innermost_idx;
+        return Err(CslError::IndcsGreaterThanEqualDimLength { dim_len: last_dim, idx }.into());
+      }
+      let line = line_idx(&self.dims, indcs).ok_or(CslError::OffsLengthOverflow)?;
+      if line >= offs_len.saturating_sub(1) {
+        let found = line.saturating_add(2);
+        return Err(CslError::InvalidOffsetsLength { expected: offs_len, found }.into());
+      }
+      while curr_line < line {
+        let _ = csl.offs.push(nnz).map_err(|_err| crate::Error::InsufficientCapacity)?;
+        curr_line += 1;
+      }
+      let _ = csl.indcs.push(innermost_idx).map_err(|_err| crate::Error::InsufficientCapacity)?;
+      let _ = csl.data.push(value.clone()).map_err(|_err| crate::Error::InsufficientCapacity)?;
+    }
+    let nnz = self.data().len();
+    while csl.offs.as_ref().len() < offs_len {
+      let _ = csl.offs.push(nnz).map_err(|_err| crate::Error::InsufficientCapacity)?;
+    }
+    Ok(csl)
+  }
+
+  /// Splits the elements into the "indices matrix + values vector" layout of `torch.sparse` and
+  /// TensorFlow's `SparseTensor`, which is the reverse of
+  /// [`from_split_arrays`](CooVec#method.from_split_arrays).
+  ///
+  /// Indices are returned as a row-major `[D, nnz]` matrix, i.e., the indices of the first
+  /// dimension of all elements are followed by the indices of the second dimension and so on.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::coo::CooArray;
+  /// let coo = CooArray::new([2, 3], [([0, 2], 1), ([1, 0], 2), ([1, 1], 3)]).unwrap();
+  /// let (indices, values) = coo.to_split_arrays();
+  /// assert_eq!(indices, [0, 1, 1, 2, 0, 1]);
+  /// assert_eq!(values, [1, 2, 3]);
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn to_split_arrays(&self) -> (Vec<usize>, Vec<DATA>)
+  where
+    DATA: Clone,
+  {
+    let data = self.data.as_ref();
+    let mut indices = Vec::with_capacity(data.len().saturating_mul(D));
+    for axis in 0..D {
+      indices.extend(data.iter().map(|(indcs, _)| indcs.get(axis).copied().unwrap_or_default()));
+    }
+    (indices, data.iter().map(|(_, value)| value.clone()).collect())
+  }
+
+  /// Inserts a new dimension of length one at `axis` and returns a structure with `TD`
+  /// dimensions, i.e., `D + 1`. Stored elements have index zero in `axis`.
+  ///
+  /// # Arguments
+  ///
+  /// * `axis`: Position of the new dimension, from `0` to `D`
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::fixtures::{coo_array_5, coo_vec_5};
+  /// let coo = coo_array_5();
+  /// let unsqueezed = coo.unsqueeze::<6>(5)?;
+  /// assert_eq!(unsqueezed.dims(), &[2, 3, 4, 3, 3, 1]);
+  /// assert_eq!(unsqueezed.value([0, 2, 2, 0, 1, 0]), Some(&4));
+  /// assert_eq!(unsqueezed.squeeze::<5>(5)?, coo_vec_5());
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn unsqueeze<const TD: usize>(&self, axis: usize) -> crate::Result<CooVec<DATA, TD>>
+  where
+    DATA: Clone,
+  {
+    unsqueeze(&self.dims, self.data.as_ref(), axis)
+  }
+
   /// If any, retrieves an immutable data reference of a given set of indices.
   ///
   /// # Arguments
@@ -119,14 +579,28 @@ where
   /// # Example
   ///
   /// ```rust
-  /// use ndsparse::doc_tests::coo_array_5;
+  /// use ndsparse::fixtures::coo_array_5;
   /// let coo = coo_array_5();
   /// assert_eq!(coo.value([0, 0, 0, 0, 0]), None);
   /// assert_eq!(coo.value([0, 2, 2, 0, 1]), Some(&4));
   /// ```
   #[inline]
   pub fn value(&self, indcs: [usize; D]) -> Option<&DATA> {
-    value(indcs, &self.data.as_ref())
+    value(indcs, self.data.as_ref())
+  }
+
+  /// Verifies all the invariants that are also verified by [`new`](#method.new), which is useful
+  /// for structures that didn't go through `new`, e.g., deserialized ones.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::coo_array_5;
+  /// assert_eq!(coo_array_5().validate(), Ok(()));
+  /// ```
+  #[inline]
+  pub fn validate(&self) -> crate::Result<()> {
+    validate(&self.dims, self.data.as_ref())
   }
 }
 
@@ -134,6 +608,166 @@ impl<DATA, DS, const D: usize> Coo<DS, D>
 where
   DS: AsMut<[<DS as Storage>::Item]> + Storage<Item = ([usize; D], DATA)>,
 {
+  /// Adds `delta` to the element located at `indcs` or inserts `delta` at its sorted position if
+  /// there is no such element, which requires storages that can grow.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::coo::{CooError, CooVec};
+  /// let mut coo = CooVec::new([2, 2], vec![([0, 1], 1)])?;
+  /// coo.add_assign_at([0, 1], 2)?;
+  /// coo.add_assign_at([0, 0], 3)?;
+  /// assert_eq!(coo.data(), &[([0, 0], 3), ([0, 1], 3)]);
+  /// let err = CooError::InvalidIndcs { data_idx: 2 };
+  /// assert_eq!(coo.add_assign_at([2, 0], 1), Err(ndsparse::Error::Coo(err)));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn add_assign_at(&mut self, indcs: [usize; D], delta: DATA) -> crate::Result<()>
+  where
+    DATA: AddAssign,
+    DS: Insert<Input = (usize, ([usize; D], DATA))>,
+  {
+    let data = self.data.as_mut();
+    match data.binary_search_by(|value| value.0.cmp(&indcs)) {
+      Ok(idx) => {
+        if let Some(elem) = data.get_mut(idx) {
+          elem.1 += delta;
+        }
+      }
+      Err(idx) => {
+        let is_out_of_bounds =
+          indcs.iter().zip(self.dims.iter()).any(|(elem_idx, dim)| dim != &0 && elem_idx >= dim);
+        if is_out_of_bounds {
+          return Err(CooError::InvalidIndcs { data_idx: idx }.into());
+        }
+        let rslt = self.data.insert((idx, (indcs, delta)));
+        let _ = rslt.map_err(|_err| crate::Error::InsufficientCapacity)?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Removes all stored elements equal to `DATA::default()`, e.g., explicit zeros.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::coo::CooVec;
+  /// let mut coo = CooVec::new([2, 2], vec![([0, 0], 0), ([0, 1], 2), ([1, 1], 0)])?;
+  /// coo.compress();
+  /// assert_eq!(coo.data(), &[([0, 1], 2)]);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn compress(&mut self)
+  where
+    DATA: Default + PartialEq,
+    DS: Truncate<Input = usize>,
+  {
+    let zero = DATA::default();
+    self.retain(|_, value| value != &zero);
+  }
+
+  /// Iterator over the indices and mutable values of all stored elements.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::coo_array_5;
+  /// let mut coo = coo_array_5();
+  /// coo.iter_mut().for_each(|(indcs, value)| *value = indcs[0] as i32);
+  /// assert_eq!(coo.value([1, 2, 3, 2, 2]), Some(&1));
+  /// ```
+  #[inline]
+  pub fn iter_mut(&mut self) -> CooIterMut<'_, DATA, D> {
+    CooIterMut::new(self.data.as_mut())
+  }
+
+  /// Parallel version of [`iter_mut`](#method.iter_mut) using `rayon`.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-rayon", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-rayon"), doc = "```ignore")]
+  /// use core::cell::Cell;
+  /// use ndsparse::{coo::CooVec, fixtures::coo_array_5};
+  /// use rayon::prelude::*;
+  /// let mut coo = coo_array_5();
+  /// coo.par_iter_mut().for_each(|(_, value)| *value *= 2);
+  /// assert_eq!(coo.value([1, 2, 3, 2, 2]), Some(&14));
+  /// // Values only need to be `Send`
+  /// let mut cells = CooVec::new([2], vec![([0], Cell::new(1)), ([1], Cell::new(2))]).unwrap();
+  /// cells.par_iter_mut().for_each(|(_, cell)| cell.set(cell.get() * 2));
+  /// assert_eq!(cells.value([1]).map(Cell::get), Some(4));
+  /// ```
+  #[cfg(feature = "with-rayon")]
+  #[inline]
+  pub fn par_iter_mut(&mut self) -> crate::ParallelIteratorWrapper<CooIterMut<'_, DATA, D>> {
+    crate::ParallelIteratorWrapper(self.iter_mut())
+  }
+
+  /// Keeps only the elements for which `cb` returns `true`, in place.
+  ///
+  /// # Arguments
+  ///
+  /// * `cb`: Callback that receives the indices and the value of an element
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::fixtures::coo_vec_5;
+  /// let mut coo = coo_vec_5();
+  /// coo.retain(|indcs, value| indcs[0] == 1 || *value == 1);
+  /// assert_eq!(coo.data(), &[([0, 0, 1, 1, 2], 1), ([1, 1, 0, 2, 1], 5), ([1, 2, 3, 0, 2], 6), ([1, 2, 3, 2, 2], 7)]);
+  /// ```
+  #[inline]
+  pub fn retain<F>(&mut self, mut cb: F)
+  where
+    DS: Truncate<Input = usize>,
+    F: FnMut([usize; D], &DATA) -> bool,
+  {
+    let data = self.data.as_mut();
+    let mut curr = 0;
+    for read in 0..data.len() {
+      let keep = if let Some((indcs, value)) = data.get(read) { cb(*indcs, value) } else { break };
+      if keep {
+        data.swap(curr, read);
+        curr += 1;
+      }
+    }
+    let _ = self.data.truncate(curr);
+  }
+
+  /// Overwrites the value of an already stored element, which keeps the sort order intact and
+  /// works with storages that can't grow like [`CooArray`].
+  ///
+  /// Returns [`InsufficientCapacity`](crate::Error::InsufficientCapacity) if `indcs` isn't stored
+  /// because there is no free slot to place a new element.
+  ///
+  /// # Arguments
+  ///
+  /// * `indcs`: Indices of the stored element
+  /// * `value`: New value
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::coo_array_5;
+  /// let mut coo = coo_array_5();
+  /// assert_eq!(coo.try_set([0, 2, 2, 0, 1], 40), Ok(()));
+  /// assert_eq!(coo.value([0, 2, 2, 0, 1]), Some(&40));
+  /// assert_eq!(coo.try_set([0, 0, 0, 0, 0], 1), Err(ndsparse::Error::InsufficientCapacity));
+  /// ```
+  #[inline]
+  pub fn try_set(&mut self, indcs: [usize; D], value: DATA) -> crate::Result<()> {
+    let elem = self.value_mut(indcs).ok_or(crate::Error::InsufficientCapacity)?;
+    *elem = value;
+    Ok(())
+  }
+
   /// Mutable version of [`value`](#method.value).
   #[inline]
   pub fn value_mut(&mut self, indcs: [usize; D]) -> Option<&mut DATA> {
@@ -141,6 +775,243 @@ where
   }
 }
 
+impl<DATA, const D: usize, const DT: usize> CooArray<DATA, D, DT> {
+  /// Creates a valid array-backed instance from the elements of `iter`, which don't need to be
+  /// sorted. Useful for `no_std` environments without `alloc` because nothing is allocated.
+  ///
+  /// Returns [`InsufficientCapacity`](crate::Error::InsufficientCapacity) if `iter` yields more
+  /// than `DT` elements and [`InvalidArrayLength`](CooError::InvalidArrayLength) if it yields
+  /// less.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  /// * `iter`: Indices and values of all elements
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::coo::CooArray;
+  /// let coo = CooArray::<_, 2, 2>::fill_from_iter([2, 2], [([1, 1], 9), ([0, 1], 8)]);
+  /// assert_eq!(coo, CooArray::new([2, 2], [([0, 1], 8), ([1, 1], 9)]));
+  /// let coo = CooArray::<_, 2, 1>::fill_from_iter([2, 2], [([1, 1], 9), ([0, 1], 8)]);
+  /// assert_eq!(coo, Err(ndsparse::Error::InsufficientCapacity));
+  /// ```
+  #[inline]
+  pub fn fill_from_iter<I>(dims: [usize; D], iter: I) -> crate::Result<Self>
+  where
+    I: IntoIterator<Item = ([usize; D], DATA)>,
+  {
+    let mut iter = iter.into_iter();
+    let mut data: [([usize; D], DATA); DT] = cl_traits::try_create_array(|idx| {
+      iter.next().ok_or(idx)
+    })
+    .map_err(|found| crate::Error::from(CooError::InvalidArrayLength { expected: DT, found }))?;
+    if iter.next().is_some() {
+      return Err(crate::Error::InsufficientCapacity);
+    }
+    data.sort_unstable_by_key(|elem| elem.0);
+    Self::new(dims, data)
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<DATA, const D: usize> CooRef<'_, DATA, D> {
+  /// Owned copy of the view.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{coo::CooRef, fixtures::coo_vec_5};
+  /// let coo = coo_vec_5();
+  /// let view = CooRef::new([1, 3, 4, 3, 3], &coo.data()[..2])?;
+  /// let owned = view.to_vec_coo();
+  /// drop(coo);
+  /// assert_eq!(owned.data(), &[([0, 0, 1, 1, 2], 1), ([0, 1, 0, 1, 1], 2)]);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn to_vec_coo(&self) -> CooVec<DATA, D>
+  where
+    DATA: Clone,
+  {
+    CooVec { data: self.data.to_vec(), dims: self.dims }
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<DATA, const D: usize> CooVec<DATA, D> {
+  /// Creates a valid instance from the "indices matrix + values vector" layout of `torch.sparse`
+  /// and TensorFlow's `SparseTensor`.
+  ///
+  /// `indices` is a row-major `[D, nnz]` matrix, which is the same as the concatenation of `D`
+  /// separate arrays of `nnz` indices. TensorFlow stores `[nnz, D]` matrices, so its indices
+  /// should be transposed beforehand. Elements don't need to be sorted but duplicated entries are
+  /// rejected, therefore, uncoalesced PyTorch tensors should be coalesced first.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  /// * `indices`: Flattened indices of all elements, one dimension after another
+  /// * `values`: Data of all elements
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::coo::{CooError, CooVec};
+  /// let coo = CooVec::from_split_arrays([2, 3], &[1, 0, 1, 0, 2, 1], &[2, 1, 3])?;
+  /// assert_eq!(coo.data(), &[([0, 2], 1), ([1, 0], 2), ([1, 1], 3)]);
+  /// assert_eq!(coo.to_split_arrays(), (vec![0, 1, 1, 2, 0, 1], vec![1, 2, 3]));
+  /// let err = CooError::InvalidArrayLength { expected: 6, found: 5 };
+  /// let rslt = CooVec::from_split_arrays([2, 3], &[1, 0, 1, 0, 2], &[2, 1, 3]);
+  /// assert_eq!(rslt, Err(ndsparse::Error::Coo(err)));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn from_split_arrays(
+    dims: [usize; D],
+    indices: &[usize],
+    values: &[DATA],
+  ) -> crate::Result<Self>
+  where
+    DATA: Clone,
+  {
+    let nnz = values.len();
+    let expected = nnz.saturating_mul(D);
+    if indices.len() != expected {
+      return Err(CooError::InvalidArrayLength { expected, found: indices.len() }.into());
+    }
+    let mut data: Vec<_> = values
+      .iter()
+      .enumerate()
+      .map(|(data_idx, value)| {
+        let indcs = cl_traits::create_array(|axis| {
+          indices.get(axis * nnz + data_idx).copied().unwrap_or_default()
+        });
+        (indcs, value.clone())
+      })
+      .collect();
+    data.sort_by_key(|elem| elem.0);
+    Self::new(dims, data)
+  }
+
+  /// Creates a valid instance where the length of each dimension is the greatest index of that
+  /// dimension plus one. Elements don't need to be sorted but duplicated entries are rejected.
+  ///
+  /// # Arguments
+  ///
+  /// * `data`: Data collection
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::coo::CooVec;
+  /// let coo = CooVec::new_infer_dims(vec![([4, 0], 1), ([0, 7], 2), ([2, 3], 3)])?;
+  /// assert_eq!(coo.dims(), &[5, 8]);
+  /// assert_eq!(coo.data(), &[([0, 7], 2), ([2, 3], 3), ([4, 0], 1)]);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn new_infer_dims(mut data: Vec<([usize; D], DATA)>) -> crate::Result<Self> {
+    data.sort_by_key(|elem| elem.0);
+    Self::new(infer_dims(&data)?, data)
+  }
+}
+
+#[cfg(feature = "with-nalgebra")]
+impl<DATA, DS> Coo<DS, 2>
+where
+  DS: AsRef<[<DS as Storage>::Item]> + Storage<Item = ([usize; 2], DATA)>,
+{
+  /// Dense `nalgebra` matrix where the outermost dimension represents rows. Missing elements
+  /// are filled with `DATA::default()`.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-nalgebra", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-nalgebra"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::coo::CooArray;
+  /// let coo = CooArray::new([2, 2], [([0, 1], 1), ([1, 0], 2)])?;
+  /// assert_eq!(coo.to_dmatrix(), nalgebra::DMatrix::from_row_slice(2, 2, &[0, 1, 2, 0]));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn to_dmatrix(&self) -> nalgebra::DMatrix<DATA>
+  where
+    DATA: Default + nalgebra::Scalar,
+  {
+    let [rows, cols] = self.dims;
+    let mut rslt = nalgebra::DMatrix::from_element(rows, cols, DATA::default());
+    for ([row, col], value) in self.data() {
+      if let Some(elem) = rslt.get_mut((*row, *col)) {
+        *elem = value.clone();
+      }
+    }
+    rslt
+  }
+
+  /// `nalgebra_sparse` COO matrix where the outermost dimension represents rows.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-nalgebra", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-nalgebra"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::coo::CooArray;
+  /// let coo = CooArray::new([2, 2], [([0, 1], 1), ([1, 0], 2)])?;
+  /// let mat = coo.to_nalgebra_coo()?;
+  /// assert_eq!(mat.nnz(), 2);
+  /// assert_eq!(mat.row_indices(), &[0, 1]);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn to_nalgebra_coo(&self) -> crate::Result<nalgebra_sparse::CooMatrix<DATA>>
+  where
+    DATA: nalgebra::Scalar,
+  {
+    let [rows, cols] = self.dims;
+    let data = self.data();
+    nalgebra_sparse::CooMatrix::try_from_triplets(
+      rows,
+      cols,
+      data.iter().map(|([row, _], _)| *row).collect(),
+      data.iter().map(|([_, col], _)| *col).collect(),
+      data.iter().map(|(_, value)| value.clone()).collect(),
+    )
+    .map_err(|_err| crate::Error::UnknownError)
+  }
+}
+
+#[cfg(feature = "with-nalgebra")]
+impl<DATA> CooVec<DATA, 2> {
+  /// Creates a valid instance from a `nalgebra_sparse` COO matrix. Triplets are sorted but
+  /// duplicated entries are rejected.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-nalgebra", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-nalgebra"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::coo::CooVec;
+  /// let mat = nalgebra_sparse::CooMatrix::try_from_triplets(2, 2, vec![1, 0], vec![0, 1], vec![2, 1])
+  ///   .unwrap();
+  /// let coo = CooVec::from_nalgebra_coo(&mat)?;
+  /// assert_eq!(coo.data(), &[([0, 1], 1), ([1, 0], 2)]);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn from_nalgebra_coo(mat: &nalgebra_sparse::CooMatrix<DATA>) -> crate::Result<Self>
+  where
+    DATA: Clone,
+  {
+    let mut data: Vec<_> =
+      mat.triplet_iter().map(|(row, col, value)| ([row, col], value.clone())).collect();
+    data.sort_by_key(|elem| elem.0);
+    Self::new([mat.nrows(), mat.ncols()], data)
+  }
+}
+
 #[cfg(feature = "with-rand")]
 impl<DATA, DS, const D: usize> Coo<DS, D>
 where
@@ -186,7 +1057,7 @@ where
       return Err(CooError::NnzGreaterThanMaximumNnz.into());
     }
     let mut data: DS = Default::default();
-    if nnz > data.as_ref().len() {
+    if nnz > data.capacity_upper_bound() {
       return Err(crate::Error::InsufficientCapacity);
     }
     for _ in 0..nnz {
@@ -209,7 +1080,7 @@ where
         });
       }
     }
-    data.as_mut().sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    data.as_mut().sort_unstable_by_key(|a| a.0);
     Coo::new(dims, data)
   }
 
@@ -243,6 +1114,36 @@ where
     let nnz = if max_nnz == 0 { 0 } else { rng.gen_range(0..max_nnz) };
     Self::new_controlled_random_rand(dims, nnz, rng, |rng, _| rng.gen())
   }
+
+  /// Creates a new random and valid instance with up to `nnz` elements that is fully determined
+  /// by `seed`, which is useful for reproducible fixtures. Sampled ranges depend on the width of
+  /// `usize`, therefore, the same seed only produces the same instance on platforms with the
+  /// same pointer width.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  /// * `nnz`: Number of Non-Zero elements
+  /// * `seed`: Seed of the internal generator
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::coo::CooVec;
+  /// let a = CooVec::<u8, 3>::new_random_with_seed([1, 2, 3], 4, 42);
+  /// let b = CooVec::<u8, 3>::new_random_with_seed([1, 2, 3], 4, 42);
+  /// assert!(a.is_ok());
+  /// assert_eq!(a, b);
+  /// ```
+  #[inline]
+  pub fn new_random_with_seed(dims: [usize; D], nnz: usize, seed: u64) -> crate::Result<Self>
+  where
+    rand::distributions::Standard: rand::distributions::Distribution<DATA>,
+  {
+    use rand::Rng;
+    let mut rng = crate::utils::XorShift64::new(seed);
+    Self::new_controlled_random_rand(dims, nnz, &mut rng, |rng, _| rng.gen())
+  }
 }
 
 impl<DS, const D: usize> Default for Coo<DS, D>
@@ -254,3 +1155,16 @@ where
     Self { data: DS::default(), dims: cl_traits::default_array() }
   }
 }
+
+/// Summary with the dimensions and the number of stored elements, which avoids sending the whole
+/// structure through the wire.
+#[cfg(feature = "with-defmt")]
+impl<DATA, DS, const D: usize> defmt::Format for Coo<DS, D>
+where
+  DS: AsRef<[<DS as Storage>::Item]> + Storage<Item = ([usize; D], DATA)>,
+{
+  #[inline]
+  fn format(&self, fmt: defmt::Formatter<'_>) {
+    defmt::write!(fmt, "Coo {{ dims: {}, nnz: {} }}", self.dims, self.data.as_ref().len());
+  }
+}
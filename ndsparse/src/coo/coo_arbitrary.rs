@@ -0,0 +1,31 @@
+use crate::coo::CooVec;
+use alloc::vec::Vec;
+use arbitrary::{Arbitrary, Unstructured};
+
+// Maximum number of generated elements, which keeps sorting and deduplication cheap.
+const MAX_NNZ: usize = 64;
+
+// Dimensions are generated first and then every element receives indices that are within
+// bounds. Duplicated indices are removed, which means that all generated instances are valid.
+impl<'a, DATA, const D: usize> Arbitrary<'a> for CooVec<DATA, D>
+where
+  DATA: Arbitrary<'a>,
+{
+  #[inline]
+  fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+    let dims = crate::utils::arbitrary_dims(u)?;
+    let max_nnz = crate::utils::max_nnz(&dims).min(MAX_NNZ);
+    let nnz = u.int_in_range(0..=max_nnz)?;
+    let mut data = Vec::with_capacity(nnz);
+    for _ in 0..nnz {
+      let mut indcs = [0; D];
+      for (idx, &dim) in indcs.iter_mut().zip(&dims) {
+        *idx = u.int_in_range(0..=dim.saturating_sub(1))?;
+      }
+      data.push((indcs, DATA::arbitrary(u)?));
+    }
+    data.sort_unstable_by_key(|elem| elem.0);
+    data.dedup_by_key(|elem| elem.0);
+    Self::new(dims, data).map_err(|_err| arbitrary::Error::IncorrectFormat)
+  }
+}
@@ -1,34 +1,142 @@
 use core::fmt;
 
 /// Any error related to `Coo` operations
+#[cfg_attr(feature = "with-defmt", derive(defmt::Format))]
 #[derive(Debug, PartialEq)]
 #[non_exhaustive]
 pub enum CooError {
+  /// Axis isn't one of the dimensions of the structure
+  ///
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{coo::CooError, fixtures::coo_array_5};
+  /// let coo = coo_array_5();
+  /// let rslt = coo.unsqueeze::<6>(6);
+  /// assert_eq!(rslt, Err(ndsparse::Error::Coo(CooError::InvalidAxis { axis: 6 })));
+  /// ```
+  InvalidAxis {
+    /// The offending axis
+    axis: usize,
+  },
+
+  /// Bounds must have a lower limit that isn't greater than the upper limit and an upper limit
+  /// that is less than the dimension length
+  ///
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{coo::CooError, fixtures::coo_array_5};
+  /// let coo = coo_array_5();
+  /// let rslt = coo.crop([(0, 1), (0, 2), (3, 2), (0, 2), (0, 2)]);
+  /// assert_eq!(rslt, Err(ndsparse::Error::Coo(CooError::InvalidBounds { axis: 2 })));
+  /// ```
+  InvalidBounds {
+    /// The offending axis
+    axis: usize,
+  },
+
   /// Some index isn't in asceding order
   ///
   /// ```rust
   /// use ndsparse::coo::{CooArray, CooError};
   /// let coo = CooArray::new([2, 2], [([1, 1], 8), ([0, 0], 9)]);
-  /// assert_eq!(coo, Err(ndsparse::Error::Coo(CooError::InvalidIndcsOrder)));
+  /// assert_eq!(coo, Err(ndsparse::Error::Coo(CooError::InvalidIndcsOrder { data_idx: 1 })));
   /// ```
-  InvalidIndcsOrder,
+  InvalidIndcsOrder {
+    /// Position of the first element that is smaller than its predecessor
+    data_idx: usize,
+  },
 
   /// Some index is greater than the defined dimensions
   ///
   /// ```rust
   /// use ndsparse::coo::{CooArray, CooError};
   /// let coo = CooArray::new([2, 2], [([0, 1], 8), ([9, 9], 9)]);
-  /// assert_eq!(coo, Err(ndsparse::Error::Coo(CooError::InvalidIndcs)));
+  /// assert_eq!(coo, Err(ndsparse::Error::Coo(CooError::InvalidIndcs { data_idx: 1 })));
   /// ```
-  InvalidIndcs,
+  InvalidIndcs {
+    /// Position of the element
+    data_idx: usize,
+  },
+
+  /// An iterator can't fill an array of a different length
+  ///
+  /// ```rust
+  /// use ndsparse::coo::{CooArray, CooError};
+  /// let coo = CooArray::<i32, 2, 3>::fill_from_iter([2, 2], [([0, 0], 8), ([1, 1], 9)]);
+  /// let err = CooError::InvalidArrayLength { expected: 3, found: 2 };
+  /// assert_eq!(coo, Err(ndsparse::Error::Coo(err)));
+  /// ```
+  InvalidArrayLength {
+    /// Length of the array
+    expected: usize,
+    /// Number of elements yielded by the iterator
+    found: usize,
+  },
+
+  /// Chunk iterators must cover at least one outermost index
+  ///
+  /// ```rust
+  /// use ndsparse::{coo::CooError, fixtures::coo_array_5};
+  /// let coo = coo_array_5();
+  /// let rslt = coo.chunks_by_outer(0).map(|_| ());
+  /// assert_eq!(rslt, Err(ndsparse::Error::Coo(CooError::InvalidChunkSize)));
+  /// ```
+  InvalidChunkSize,
+
+  /// The permutation isn't a valid rearrangement of the outermost dimension
+  ///
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{coo::CooError, fixtures::coo_array_5};
+  /// let coo = coo_array_5();
+  /// let rslt = coo.permute_outermost(&[0, 1, 2]);
+  /// assert_eq!(rslt, Err(ndsparse::Error::Coo(CooError::InvalidPermutation)));
+  /// ```
+  InvalidPermutation,
+
+  /// The number of dimensions of the resulting structure isn't compatible with the operation
+  ///
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{coo::CooError, fixtures::coo_array_5};
+  /// let coo = coo_array_5();
+  /// let rslt = coo.squeeze::<5>(0);
+  /// assert_eq!(rslt, Err(ndsparse::Error::Coo(CooError::InvalidRank { expected: 4, found: 5 })));
+  /// ```
+  InvalidRank {
+    /// Required number of dimensions
+    expected: usize,
+    /// Provided number of dimensions
+    found: usize,
+  },
+
+  /// Only dimensions of length one can be removed
+  ///
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{coo::CooError, fixtures::coo_array_5};
+  /// let coo = coo_array_5();
+  /// let rslt = coo.squeeze::<4>(0);
+  /// assert_eq!(rslt, Err(ndsparse::Error::Coo(CooError::NonUnitAxis { axis: 0, dim_len: 2 })));
+  /// ```
+  NonUnitAxis {
+    /// The offending axis
+    axis: usize,
+    /// Length of the axis
+    dim_len: usize,
+  },
 
   /// There are duplicated indices
   ///
   /// ```rust
   /// use ndsparse::coo::{CooArray, CooError};
   /// let coo = CooArray::new([2, 2], [([0, 0], 8), ([0, 0], 9)]);
-  /// assert_eq!(coo, Err(ndsparse::Error::Coo(CooError::DuplicatedIndices)));
-  DuplicatedIndices,
+  /// assert_eq!(coo, Err(ndsparse::Error::Coo(CooError::DuplicatedIndices { data_idx: 1 })));
+  /// ```
+  DuplicatedIndices {
+    /// Position of the first element that repeats the indices of its predecessor
+    data_idx: usize,
+  },
 
   /// nnz is greater than the maximum permitted number of nnz
   ///
@@ -50,9 +158,26 @@ impl fmt::Display for CooError {
   #[inline]
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     let s = match *self {
-      Self::InvalidIndcsOrder => "InvalidIndcsOrder",
-      Self::InvalidIndcs => "InvalidIndcs",
-      Self::DuplicatedIndices => "DuplicatedIndices",
+      Self::InvalidAxis { axis } => return write!(f, "InvalidAxis ({})", axis),
+      Self::InvalidBounds { axis } => return write!(f, "InvalidBounds (axis {})", axis),
+      Self::InvalidIndcsOrder { data_idx } => {
+        return write!(f, "InvalidIndcsOrder (element {})", data_idx)
+      }
+      Self::InvalidIndcs { data_idx } => return write!(f, "InvalidIndcs (element {})", data_idx),
+      Self::InvalidArrayLength { expected, found } => {
+        return write!(f, "InvalidArrayLength (expected: {}, found: {})", expected, found);
+      }
+      Self::InvalidChunkSize => "InvalidChunkSize",
+      Self::InvalidPermutation => "InvalidPermutation",
+      Self::InvalidRank { expected, found } => {
+        return write!(f, "InvalidRank (expected: {}, found: {})", expected, found);
+      }
+      Self::NonUnitAxis { axis, dim_len } => {
+        return write!(f, "NonUnitAxis (axis {} of length {})", axis, dim_len);
+      }
+      Self::DuplicatedIndices { data_idx } => {
+        return write!(f, "DuplicatedIndices (element {})", data_idx)
+      }
       #[cfg(feature = "with-rand")]
       Self::NnzGreaterThanMaximumNnz => "NnzGreaterThanMaximumNnz",
     };
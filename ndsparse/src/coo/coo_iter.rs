@@ -0,0 +1,55 @@
+use core::slice;
+
+macro_rules! impl_iter {
+  (
+    $coo_iter:ident,
+    $slice_iter:ident,
+    $iter_fn:ident,
+    $into_slice:ident,
+    $split_at:ident
+    $(, $mut:tt)?
+  ) => {
+    /// Iterator over the indices and values of all stored elements of a COO structure. Indices
+    /// are always immutable to preserve the ordering invariant.
+    #[derive(Debug)]
+    pub struct $coo_iter<'a, T, const D: usize>(slice::$slice_iter<'a, ([usize; D], T)>);
+
+    impl<'a, T, const D: usize> $coo_iter<'a, T, D> {
+      pub(crate) fn new(data: &'a $($mut)? [([usize; D], T)]) -> Self {
+        Self(data.$iter_fn())
+      }
+
+      #[cfg(feature = "with-rayon")]
+      pub(crate) fn split_at(self, idx: usize) -> [Self; 2] {
+        let (head, tail) = self.0.$into_slice().$split_at(idx);
+        [Self(head.$iter_fn()), Self(tail.$iter_fn())]
+      }
+    }
+
+    impl<T, const D: usize> DoubleEndedIterator for $coo_iter<'_, T, D> {
+      #[inline]
+      fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(indcs, value)| (&*indcs, value))
+      }
+    }
+
+    impl<T, const D: usize> ExactSizeIterator for $coo_iter<'_, T, D> {}
+
+    impl<'a, T, const D: usize> Iterator for $coo_iter<'a, T, D> {
+      type Item = (&'a [usize; D], &'a $($mut)? T);
+
+      #[inline]
+      fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(indcs, value)| (&*indcs, value))
+      }
+
+      #[inline]
+      fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+      }
+    }
+  };
+}
+
+impl_iter!(CooIterMut, IterMut, iter_mut, into_slice, split_at_mut, mut);
+impl_iter!(CooIterRef, Iter, iter, as_slice, split_at);
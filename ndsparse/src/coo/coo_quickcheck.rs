@@ -0,0 +1,78 @@
+use crate::coo::CooVec;
+use alloc::{boxed::Box, vec::Vec};
+use quickcheck::{Arbitrary, Gen};
+
+impl<DATA, const D: usize> Arbitrary for CooVec<DATA, D>
+where
+  DATA: Arbitrary,
+{
+  #[inline]
+  fn arbitrary(g: &mut Gen) -> Self {
+    let dims = crate::utils::quickcheck_dims(g);
+    let max_nnz = crate::utils::max_nnz(&dims).min(g.size());
+    let nnz = usize::arbitrary(g) % max_nnz.saturating_add(1);
+    let mut data = Vec::with_capacity(nnz);
+    for _ in 0..nnz {
+      let mut indcs = [0; D];
+      for (idx, &dim) in indcs.iter_mut().zip(&dims) {
+        *idx = if dim == 0 { 0 } else { usize::arbitrary(g) % dim };
+      }
+      data.push((indcs, DATA::arbitrary(g)));
+    }
+    data.sort_unstable_by_key(|elem| elem.0);
+    data.dedup_by_key(|elem| elem.0);
+    Self::new(dims, data).unwrap_or_default()
+  }
+
+  /// Candidates are yielded from the most aggressive to the least aggressive, i.e., instances
+  /// without one of the stored elements, instances where a single dimension is just enough to
+  /// hold all stored indices and finally instances where a single value is shrunk.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::coo_vec_5;
+  /// use quickcheck::Arbitrary;
+  /// let coo = coo_vec_5();
+  /// let mut candidates = coo.shrink();
+  /// assert_eq!(candidates.next().map(|elem| elem.data().len()), Some(6));
+  /// assert!(candidates.all(|elem| elem.validate().is_ok()));
+  /// ```
+  #[inline]
+  fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+    let this = self.clone();
+    let elements = (0..self.data.len()).map(move |idx| {
+      let mut coo = this.clone();
+      let _ = coo.data.remove(idx);
+      coo
+    });
+
+    let this = self.clone();
+    let dims = (0..D).filter_map(move |axis| {
+      let dim = this.dims.get(axis).copied()?;
+      let max_idx = this.data.iter().filter_map(|(indcs, _)| indcs.get(axis).copied()).max();
+      let needed = max_idx.map_or(1, |idx| idx.saturating_add(1));
+      if dim == 0 || needed >= dim {
+        return None;
+      }
+      let mut coo = this.clone();
+      *coo.dims.get_mut(axis)? = needed;
+      Some(coo)
+    });
+
+    let this = self.clone();
+    let values = (0..self.data.len()).flat_map(move |idx| {
+      let this = this.clone();
+      let value = this.data.get(idx).map(|elem| elem.1.clone());
+      value.into_iter().flat_map(|value| value.shrink()).map(move |value| {
+        let mut coo = this.clone();
+        if let Some(r) = coo.data.get_mut(idx) {
+          r.1 = value;
+        }
+        coo
+      })
+    });
+
+    Box::new(elements.chain(dims).chain(values))
+  }
+}
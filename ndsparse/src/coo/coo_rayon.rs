@@ -0,0 +1,95 @@
+use crate::{
+  coo::{CooIterMut, CooIterRef},
+  ParallelIteratorWrapper, ParallelProducerWrapper,
+};
+use rayon::iter::{
+  plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer},
+  IndexedParallelIterator, ParallelIterator,
+};
+
+macro_rules! create_rayon_iter {
+  ($coo_rayon_iter:ident, $item:ty, $($bounds:tt)+) => {
+    impl<'a, T, const D: usize> ParallelIterator
+      for ParallelIteratorWrapper<$coo_rayon_iter<'a, T, D>>
+    where
+      T: $($bounds)+ + 'a,
+    {
+      type Item = $item;
+
+      #[inline]
+      fn drive_unindexed<C>(self, consumer: C) -> C::Result
+      where
+        C: UnindexedConsumer<Self::Item>,
+      {
+        bridge(self, consumer)
+      }
+
+      #[inline]
+      fn opt_len(&self) -> Option<usize> {
+        Some(self.0.len())
+      }
+    }
+
+    impl<'a, T, const D: usize> IndexedParallelIterator
+      for ParallelIteratorWrapper<$coo_rayon_iter<'a, T, D>>
+    where
+      T: $($bounds)+ + 'a,
+    {
+      #[inline]
+      fn drive<C>(self, consumer: C) -> C::Result
+      where
+        C: Consumer<Self::Item>,
+      {
+        bridge(self, consumer)
+      }
+
+      #[inline]
+      fn len(&self) -> usize {
+        ExactSizeIterator::len(&self.0)
+      }
+
+      #[inline]
+      fn with_producer<Cb>(self, callback: Cb) -> Cb::Output
+      where
+        Cb: ProducerCallback<Self::Item>,
+      {
+        callback.callback(ParallelProducerWrapper(self.0))
+      }
+    }
+
+    impl<'a, T, const D: usize> IntoIterator for ParallelProducerWrapper<$coo_rayon_iter<'a, T, D>>
+    where
+      T: 'a,
+    {
+      type IntoIter = $coo_rayon_iter<'a, T, D>;
+      type Item = <Self::IntoIter as Iterator>::Item;
+
+      #[inline]
+      fn into_iter(self) -> Self::IntoIter {
+        self.0
+      }
+    }
+
+    impl<'a, T, const D: usize> Producer for ParallelProducerWrapper<$coo_rayon_iter<'a, T, D>>
+    where
+      T: $($bounds)+ + 'a,
+    {
+      type IntoIter = $coo_rayon_iter<'a, T, D>;
+      type Item = <Self::IntoIter as Iterator>::Item;
+
+      #[inline]
+      fn into_iter(self) -> Self::IntoIter {
+        self.0
+      }
+
+      #[inline]
+      fn split_at(self, i: usize) -> (Self, Self) {
+        let [a, b] = self.0.split_at(i);
+        (ParallelProducerWrapper(a), ParallelProducerWrapper(b))
+      }
+    }
+  };
+}
+
+create_rayon_iter!(CooIterMut, (&'a [usize; D], &'a mut T), Send);
+create_rayon_iter!(CooIterRef, (&'a [usize; D], &'a T), Send + Sync);
@@ -1,4 +1,11 @@
-use crate::utils::windows2;
+use crate::{coo::CooError, utils::windows2};
+#[cfg(feature = "alloc")]
+use crate::{
+  coo::CooVec,
+  utils::{insert_axis, remove_axis, roll_idx},
+};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 macro_rules! create_value {
   ($get:ident $fn_name:ident $([$mut:tt])?) => {
@@ -19,10 +26,193 @@ macro_rules! create_value {
 create_value!(get value);
 create_value!(get_mut value_mut [mut]);
 
+#[cfg(feature = "alloc")]
 #[inline]
-pub(crate) fn does_not_have_duplicates_sorted<F, T>(slice: &[T], mut cb: F) -> bool
+pub(crate) fn infer_dims<DATA, const D: usize>(
+  data: &[([usize; D], DATA)],
+) -> crate::Result<[usize; D]> {
+  let mut dims = [0; D];
+  for (data_idx, (indcs, _)) in data.iter().enumerate() {
+    for (dim, &idx) in dims.iter_mut().zip(indcs.iter()) {
+      let len = idx.checked_add(1).ok_or(CooError::InvalidIndcs { data_idx })?;
+      *dim = (*dim).max(len);
+    }
+  }
+  Ok(dims)
+}
+
+#[inline]
+pub(crate) fn validate<DATA, const D: usize>(
+  dims: &[usize; D],
+  data: &[([usize; D], DATA)],
+) -> crate::Result<()> {
+  if let Some(idx) = windows2(data).position(|[a, b]| a.0 > b.0) {
+    return Err(CooError::InvalidIndcsOrder { data_idx: idx + 1 }.into());
+  }
+  let invalid_indcs = data.iter().position(|(indcs, _)| {
+    indcs.iter().zip(dims.iter()).any(|(data_idx, dim)| dim != &0 && data_idx >= dim)
+  });
+  if let Some(data_idx) = invalid_indcs {
+    return Err(CooError::InvalidIndcs { data_idx }.into());
+  }
+  if let Some(idx) = windows2(data).position(|[a, b]| a.0 == b.0) {
+    return Err(CooError::DuplicatedIndices { data_idx: idx + 1 }.into());
+  }
+  Ok(())
+}
+
+#[inline]
+pub(crate) fn bounds<DATA, const D: usize>(
+  data: &[([usize; D], DATA)],
+) -> Option<[(usize, usize); D]> {
+  let (first, _) = data.first()?;
+  let mut rslt = [(0, 0); D];
+  for (bound, &idx) in rslt.iter_mut().zip(first.iter()) {
+    *bound = (idx, idx);
+  }
+  for (indcs, _) in data {
+    for ((min, max), &idx) in rslt.iter_mut().zip(indcs.iter()) {
+      *min = (*min).min(idx);
+      *max = (*max).max(idx);
+    }
+  }
+  Some(rslt)
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn crop<DATA, const D: usize>(
+  dims: &[usize; D],
+  data: &[([usize; D], DATA)],
+  bounds: [(usize, usize); D],
+) -> crate::Result<CooVec<DATA, D>>
 where
-  F: FnMut(&T, &T) -> bool,
+  DATA: Clone,
 {
-  windows2(slice).all(|[a, b]| cb(a, b))
+  let mut new_dims = [0; D];
+  for (axis, ((new_dim, &(min, max)), &dim)) in
+    new_dims.iter_mut().zip(bounds.iter()).zip(dims.iter()).enumerate()
+  {
+    if min > max || (dim != 0 && max >= dim) {
+      return Err(CooError::InvalidBounds { axis }.into());
+    }
+    *new_dim = (max - min).checked_add(1).ok_or(CooError::InvalidBounds { axis })?;
+  }
+  let (outermost_min, outermost_max) = bounds.first().copied().unwrap_or_default();
+  let outermost = |elem: &([usize; D], DATA)| elem.0.first().copied().unwrap_or_default();
+  let start = data.partition_point(|elem| outermost(elem) < outermost_min);
+  let end = data.partition_point(|elem| outermost(elem) <= outermost_max);
+  let mut rslt = Vec::new();
+  for (indcs, value) in data.get(start..end).unwrap_or_default() {
+    let is_contained =
+      indcs.iter().zip(bounds.iter()).all(|(idx, (min, max))| idx >= min && idx <= max);
+    if !is_contained {
+      continue;
+    }
+    let mut new_indcs = *indcs;
+    for (idx, (min, _)) in new_indcs.iter_mut().zip(bounds.iter()) {
+      *idx -= min;
+    }
+    rslt.push((new_indcs, value.clone()));
+  }
+  Ok(CooVec { data: rslt, dims: new_dims })
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn permute_outermost<DATA, const D: usize>(
+  dims: [usize; D],
+  data: &[([usize; D], DATA)],
+  perm: &[usize],
+) -> crate::Result<CooVec<DATA, D>>
+where
+  DATA: Clone,
+{
+  let outermost_dim = dims.first().copied().unwrap_or_default();
+  let _ =
+    crate::utils::inverse_permutation(perm, outermost_dim).ok_or(CooError::InvalidPermutation)?;
+  if outermost_dim == 0 {
+    return Ok(CooVec { data: data.to_vec(), dims });
+  }
+  let mut rslt = Vec::with_capacity(data.len());
+  for (new_idx, old_idx) in perm.iter().copied().enumerate() {
+    let start = data.partition_point(|elem| elem.0.first().copied() < Some(old_idx));
+    let end = data.partition_point(|elem| elem.0.first().copied() <= Some(old_idx));
+    for (indcs, value) in data.get(start..end).unwrap_or_default() {
+      let mut new_indcs = *indcs;
+      if let Some(first) = new_indcs.first_mut() {
+        *first = new_idx;
+      }
+      rslt.push((new_indcs, value.clone()));
+    }
+  }
+  Ok(CooVec { data: rslt, dims })
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn roll<DATA, const D: usize>(
+  dims: &[usize; D],
+  data: &[([usize; D], DATA)],
+  axis: usize,
+  amount: isize,
+) -> crate::Result<CooVec<DATA, D>>
+where
+  DATA: Clone,
+{
+  let dim_len = *dims.get(axis).ok_or(CooError::InvalidAxis { axis })?;
+  let mut data: Vec<_> = data
+    .iter()
+    .map(|(indcs, value)| {
+      let mut new_indcs = *indcs;
+      if let Some(r) = new_indcs.get_mut(axis) {
+        *r = roll_idx(*r, dim_len, amount);
+      }
+      (new_indcs, value.clone())
+    })
+    .collect();
+  data.sort_unstable_by_key(|elem| elem.0);
+  Ok(CooVec { data, dims: *dims })
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn squeeze<DATA, const D: usize, const TD: usize>(
+  dims: &[usize; D],
+  data: &[([usize; D], DATA)],
+  axis: usize,
+) -> crate::Result<CooVec<DATA, TD>>
+where
+  DATA: Clone,
+{
+  if TD.checked_add(1) != Some(D) {
+    return Err(CooError::InvalidRank { expected: D.saturating_sub(1), found: TD }.into());
+  }
+  let dim_len = *dims.get(axis).ok_or(CooError::InvalidAxis { axis })?;
+  if dim_len != 1 {
+    return Err(CooError::NonUnitAxis { axis, dim_len }.into());
+  }
+  let data = data.iter().map(|(indcs, value)| (remove_axis(indcs, axis), value.clone())).collect();
+  Ok(CooVec { data, dims: remove_axis(dims, axis) })
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn unsqueeze<DATA, const D: usize, const TD: usize>(
+  dims: &[usize; D],
+  data: &[([usize; D], DATA)],
+  axis: usize,
+) -> crate::Result<CooVec<DATA, TD>>
+where
+  DATA: Clone,
+{
+  if D.checked_add(1) != Some(TD) {
+    return Err(CooError::InvalidRank { expected: D.saturating_add(1), found: TD }.into());
+  }
+  if axis > D {
+    return Err(CooError::InvalidAxis { axis }.into());
+  }
+  let data =
+    data.iter().map(|(indcs, value)| (insert_axis(indcs, axis, 0), value.clone())).collect();
+  Ok(CooVec { data, dims: insert_axis(dims, axis, 1) })
 }
@@ -7,24 +7,71 @@
 //! [`CSC`]: en.wikipedia.org/wiki/Sparse_matrix#Compressed_sparse_column_(CSC_or_CCS)
 //! [`CSR`]: en.wikipedia.org/wiki/Sparse_matrix#Compressed_sparse_row_(CSR,_CRS_or_Yale_format)
 
+#[cfg(all(feature = "alloc", feature = "with-arbitrary"))]
+mod csl_arbitrary;
+#[cfg(feature = "with-arrow")]
+mod csl_arrow;
+#[cfg(feature = "alloc")]
+mod csl_bool;
+#[cfg(feature = "alloc")]
+mod csl_builder;
+mod csl_canonical;
+mod csl_cursor;
+mod csl_entry;
 mod csl_error;
+#[cfg(feature = "with-hdf5")]
+mod csl_hdf5;
 mod csl_line_constructor;
 mod csl_line_iter;
+#[cfg(feature = "alloc")]
+mod csl_lookup;
+#[cfg(feature = "alloc")]
+mod csl_narrow;
+mod csl_norms;
+mod csl_order;
+#[cfg(feature = "with-quickcheck")]
+mod csl_quickcheck;
 #[cfg(feature = "with-rayon")]
 mod csl_rayon;
 #[cfg(feature = "with-rand")]
 mod csl_rnd;
+mod csl_static;
+mod csl_sym;
+#[cfg(feature = "alloc")]
+mod csl_transpose;
 mod csl_utils;
+#[cfg(feature = "with-npz")]
+pub mod io;
+#[cfg(feature = "alloc")]
+pub mod ops;
+#[cfg(feature = "alloc")]
+pub mod ordering;
+#[cfg(feature = "std")]
+pub mod persist;
+pub mod raw;
 
-use crate::utils::{are_in_ascending_order, are_in_upper_bound, has_duplicates, max_nnz, windows2};
+use crate::MemoryUsage;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
-use cl_traits::{Clear, Push, Storage, Truncate, WithCapacity};
-use core::ops::Range;
-#[cfg(feature = "with-rayon")]
-pub use csl_rayon::*;
-use csl_utils::*;
-pub use {csl_error::*, csl_line_constructor::*, csl_line_iter::*};
+use cl_traits::{Clear, Insert, Push, Storage, Truncate, WithCapacity};
+#[cfg(feature = "alloc")]
+use core::{
+  convert::TryFrom,
+  ops::{Add, Mul},
+};
+use core::{
+  mem,
+  ops::{AddAssign, Range},
+};
+#[cfg(feature = "with-rand")]
+pub use csl_rnd::{LineNnzDistribution, RandomConfig};
+pub(crate) use csl_utils::*;
+#[cfg(feature = "alloc")]
+pub use {csl_builder::*, csl_lookup::*, csl_transpose::*};
+pub use {
+  csl_canonical::*, csl_cursor::*, csl_entry::*, csl_error::*, csl_line_constructor::*,
+  csl_line_iter::*, csl_norms::*, csl_order::*, csl_static::*, csl_sym::*,
+};
 
 /// CSL backed by a static array.
 pub type CslArray<DATA, const D: usize, const N: usize, const O: usize> =
@@ -39,6 +86,13 @@ pub type CslVec<DATA, const D: usize> = Csl<Vec<DATA>, Vec<usize>, Vec<usize>, D
 
 /// Base structure for all CSL* variants.
 ///
+/// The indices of every line are always stored in strictly ascending order, which allows
+/// elements to be found with binary searches.
+///
+/// The derived `Eq` and `Ord` implementations compare the raw storages field by field, i.e.,
+/// structures that represent the same tensor through different offsets are considered different.
+/// Use [`CanonicalKey`] to compare structures by their content.
+///
 /// It is possible to define your own fancy CSL, e.g., `Csl<
 ///   staticvec::StaticVec<num_bigint::BigNum, 32>,
 ///   arrayvec::ArrayVec<[usize; 32]>,
@@ -53,6 +107,7 @@ pub type CslVec<DATA, const D: usize> = Csl<Vec<DATA>, Vec<usize>, Vec<usize>, D
 /// * `IS`: Indices Storage
 /// * `OS`: Offsets Storage
 #[cfg_attr(feature = "with-serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "with-serde", serde(from = "CslFields<DS, IS, OS, D>"))]
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Csl<DS, IS, OS, const D: usize> {
   pub(crate) data: DS,
@@ -60,6 +115,30 @@ pub struct Csl<DS, IS, OS, const D: usize> {
   pub(crate) dims: [usize; D],
   pub(crate) indcs: IS,
   pub(crate) offs: OS,
+  // Number of lines of each outermost index, which is needed by every line lookup. `None` if the
+  // product of the dimensions overflows.
+  #[cfg_attr(feature = "with-serde", serde(skip_serializing))]
+  pub(crate) outermost_stride: Option<usize>,
+}
+
+// The serialized representation of `Csl`, which doesn't include cached values.
+#[cfg(feature = "with-serde")]
+#[derive(serde::Deserialize)]
+struct CslFields<DS, IS, OS, const D: usize> {
+  data: DS,
+  #[serde(with = "serde_big_array::BigArray")]
+  dims: [usize; D],
+  indcs: IS,
+  offs: OS,
+}
+
+#[cfg(feature = "with-serde")]
+impl<DS, IS, OS, const D: usize> From<CslFields<DS, IS, OS, D>> for Csl<DS, IS, OS, D> {
+  #[inline]
+  fn from(from: CslFields<DS, IS, OS, D>) -> Self {
+    let CslFields { data, dims, indcs, offs } = from;
+    Self { data, dims, indcs, offs, outermost_stride: outermost_stride(&dims) }
+  }
 }
 
 impl<DS, IS, OS, const D: usize> Csl<DS, IS, OS, D>
@@ -89,450 +168,2508 @@ where
   /// ```
   #[inline]
   pub fn with_capacity(nnz: usize, nolp1: usize) -> Self {
+    let dims = cl_traits::default_array();
     Self {
       data: DS::with_capacity(nnz),
-      dims: cl_traits::default_array(),
+      dims,
       indcs: IS::with_capacity(nnz),
       offs: OS::with_capacity(nolp1),
+      outermost_stride: outermost_stride(&dims),
     }
   }
 }
 
-impl<DS, IS, OS, const D: usize> Csl<DS, IS, OS, D> {
-  /// The definitions of all dimensions.
+impl<DATA, const D: usize, const N: usize, const O: usize> CslArray<DATA, D, N, O> {
+  /// Creates a valid array-backed instance where each element is provided by a callback. Useful
+  /// for `no_std` environments without `alloc` because `DATA` isn't required to implement
+  /// `Default`.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  /// * `data_cb`: Returns the innermost index and the data of the nth stored element
+  /// * `offs_cb`: Returns the nth offset
   ///
   /// # Example
   ///
   /// ```rust
-  /// use ndsparse::doc_tests::csl_array_4;
-  /// assert_eq!(csl_array_4().dims(), &[2, 3, 4, 5]);
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslArray;
+  /// // 4x4 identity matrix
+  /// let csl = CslArray::<i32, 2, 4, 5>::new_with([4, 4], |idx| (idx, 1), |idx| idx)?;
+  /// assert_eq!(csl.value([2, 2]), Some(&1));
+  /// assert_eq!(csl.value([2, 3]), None);
+  /// # Ok(()) }
   /// ```
   #[inline]
-  pub fn dims(&self) -> &[usize; D] {
-    &self.dims
+  pub fn new_with<F, G>(dims: [usize; D], mut data_cb: F, offs_cb: G) -> crate::Result<Self>
+  where
+    F: FnMut(usize) -> (usize, DATA),
+    G: FnMut(usize) -> usize,
+  {
+    let mut indcs = [0; N];
+    let data = cl_traits::create_array(|idx| {
+      let (innermost_idx, elem) = data_cb(idx);
+      if let Some(r) = indcs.get_mut(idx) {
+        *r = innermost_idx;
+      }
+      elem
+    });
+    Self::new(dims, data, indcs, cl_traits::create_array(offs_cb))
   }
 }
 
-impl<DATA, DS, IS, OS, const D: usize> Csl<DS, IS, OS, D>
-where
-  DS: AsRef<[DATA]> + Storage<Item = DATA>,
-  IS: AsRef<[usize]>,
-  OS: AsRef<[usize]>,
-{
-  /// Creates a valid CSL instance.
+#[cfg(feature = "alloc")]
+impl<DATA, const D: usize> CslVec<DATA, D> {
+  /// Creates a valid instance from a stream of lines in a single pass, without intermediate
+  /// collections.
   ///
-  /// The compressed fields are a bit complex and unless you really know what you are doing, this
-  /// method shouldn't probably be used directly. Please, try to consider using [`#constructor`]
-  /// instead.
+  /// If `D` is greater than 1 and the outermost dimension of `dims_hint` is zero, its length is
+  /// inferred from the number of provided lines.
   ///
   /// # Arguments
   ///
-  /// * `dims`: Array of dimensions
-  /// * `data`: Data collection
-  /// * `indcs`: Indices of each data item
-  /// * `offs`: Offset of each innermost line
+  /// * `dims_hint`: Array of dimensions
+  /// * `lines`: Iterator of lines, each one an iterator of innermost indices and their data
   ///
   /// # Example
-  #[cfg_attr(feature = "alloc", doc = "```rust")]
-  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
-  /// use ndsparse::csl::{CslArray, CslVec};
-  /// // Sparse array ([8, _, _, _, _, 9, _, _, _, _])
-  /// let mut _sparse_array = CslArray::new([10], [8.0, 9.0], [0, 5], [0, 2]);
-  /// // A bunch of nothing for your overflow needs
-  /// let mut _over_nine: ndsparse::Result<CslVec<(), 9001>>;
-  /// _over_nine = CslVec::new([0; 9001], vec![], vec![], vec![]);
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let lines = vec![vec![(0, 1), (2, 2)], vec![], vec![(1, 3)]];
+  /// let csl = CslVec::from_lines([0, 3], lines)?;
+  /// assert_eq!(csl.dims(), &[3, 3]);
+  /// assert_eq!(csl.data(), &[1, 2, 3]);
+  /// assert_eq!(csl.indcs(), &[0, 2, 1]);
+  /// assert_eq!(csl.offs(), &[0, 2, 2, 3]);
+  /// # Ok(()) }
   /// ```
   #[inline]
-  pub fn new(dims: [usize; D], data: DS, indcs: IS, offs: OS) -> crate::Result<Self> {
-    let data_ref = data.as_ref();
-    let indcs_ref = indcs.as_ref();
-    let offs_ref = offs.as_ref();
-
-    let innermost_dim_is_zero = {
-      let mut iter = dims.iter().copied();
-      while let Some(dim) = iter.next() {
-        if dim != 0 {
-          break;
-        }
-      }
-      iter.any(|v| v == 0)
-    };
-    if innermost_dim_is_zero {
-      return Err(CslError::InnermostDimsZero.into());
-    }
-
-    if data_ref.len() != indcs_ref.len() {
-      return Err(CslError::DiffDataIndcsLength.into());
-    }
-
-    if !are_in_ascending_order(&offs_ref, |a, b| [a, b]) {
-      return Err(CslError::InvalidOffsetsOrder.into());
-    }
-
-    let data_indcs_length_greater_than_dims_length = {
-      let max_nnz = max_nnz(&dims);
-      data_ref.len() > max_nnz || indcs_ref.len() > max_nnz
-    };
-    if data_indcs_length_greater_than_dims_length {
-      return Err(CslError::DataIndcsLengthGreaterThanDimsLength.into());
-    }
-
-    if let Some(last) = dims.last() {
-      let are_in_upper_bound = are_in_upper_bound(indcs_ref, last);
-      if !are_in_upper_bound {
-        return Err(CslError::IndcsGreaterThanEqualDimLength.into());
-      }
-      if offs_ref.len() != correct_offs_len(&dims)? {
-        return Err(CslError::InvalidOffsetsLength.into());
-      }
-    }
-
-    let first_off = if let Some(r) = offs_ref.first() {
-      r
-    } else {
-      return Ok(Self { data, dims, indcs, offs });
-    };
-
-    if let Some(last_ref) = offs_ref.last() {
-      let last = last_ref - first_off;
-      if last != data_ref.len() || last != indcs_ref.len() {
-        return Err(CslError::LastOffsetDifferentNnz.into());
+  pub fn from_lines<I, L>(dims_hint: [usize; D], lines: I) -> crate::Result<Self>
+  where
+    I: IntoIterator<Item = L>,
+    L: IntoIterator<Item = (usize, DATA)>,
+  {
+    let mut data = Vec::new();
+    let mut indcs = Vec::new();
+    let mut offs = alloc::vec![0];
+    for line in lines {
+      for (idx, elem) in line {
+        data.push(elem);
+        indcs.push(idx);
       }
+      offs.push(data.len());
     }
-
-    let has_duplicated_indices = windows2(offs_ref).any(|[a, b]| {
-      if let Some(indcs) = indcs_ref.get(a - first_off..b - first_off) {
-        has_duplicates(indcs)
-      } else {
-        false
+    let mut dims = dims_hint;
+    if D > 1 && dims.first() == Some(&0) {
+      let nol = offs.len().saturating_sub(1);
+      let middle =
+        dims.iter().skip(1).rev().skip(1).try_fold(1usize, |acc, &dim| acc.checked_mul(dim));
+      match middle {
+        Some(0) if nol == 0 => {}
+        Some(middle) if middle != 0 && nol % middle == 0 => {
+          if let Some(r) = dims.first_mut() {
+            *r = nol / middle;
+          }
+        }
+        Some(0) => {
+          return Err(CslError::InvalidOffsetsLength { expected: 1, found: offs.len() }.into())
+        }
+        Some(middle) => {
+          let expected = (nol / middle + 1).saturating_mul(middle).saturating_add(1);
+          return Err(CslError::InvalidOffsetsLength { expected, found: offs.len() }.into());
+        }
+        None => return Err(CslError::OffsLengthOverflow.into()),
       }
-    });
-    if has_duplicated_indices {
-      return Err(CslError::DuplicatedIndices.into());
     }
+    Self::new(dims, data, indcs, offs)
+  }
 
-    Ok(Self { data, dims, indcs, offs })
+  /// Reads an instance from the `row`th element of a `StructArray` created by
+  /// [`to_arrow`](#method.to_arrow).
+  ///
+  /// # Arguments
+  ///
+  /// * `array`: Arrow array with the `dims`, `data`, `indcs` and `offs` fields
+  /// * `row`: Element of `array` that contains the instance
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use arrow_array::types::Int32Type;
+  /// use ndsparse::{csl::CslVec, fixtures::csl_vec_4};
+  /// let csl = csl_vec_4();
+  /// let array = csl.to_arrow::<Int32Type>()?;
+  /// assert_eq!(CslVec::from_arrow::<Int32Type>(&array, 0)?, csl);
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "with-arrow")]
+  #[inline]
+  pub fn from_arrow<T>(array: &arrow_array::StructArray, row: usize) -> crate::Result<Self>
+  where
+    T: arrow_array::ArrowPrimitiveType<Native = DATA>,
+  {
+    csl_arrow::from_arrow::<T, D>(array, row)
   }
 
-  /// The data that is being stored.
+  /// Creates a valid instance from unordered `(indices, data)` triplets where the length of each
+  /// dimension is the greatest index of that dimension plus one. Duplicated entries are
+  /// rejected.
+  ///
+  /// # Arguments
+  ///
+  /// * `triplets`: Indices of all dimensions and their data
   ///
   /// # Example
   ///
   /// ```rust
-  /// use ndsparse::doc_tests::csl_array_4;
-  /// assert_eq!(csl_array_4().data(), &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let csl = CslVec::from_triplets_infer_dims(vec![([2, 1], 3), ([0, 4], 1), ([0, 0], 2)])?;
+  /// assert_eq!(csl.dims(), &[3, 5]);
+  /// assert_eq!(csl.data(), &[2, 1, 3]);
+  /// assert_eq!(csl.indcs(), &[0, 4, 1]);
+  /// assert_eq!(csl.offs(), &[0, 2, 2, 3]);
+  /// let names = CslVec::from_triplets_infer_dims(vec![([1, 2], String::from("foo"))])?;
+  /// assert_eq!(names.value([1, 2]).map(String::as_str), Some("foo"));
+  /// # Ok(()) }
   /// ```
   #[inline]
-  pub fn data(&self) -> &[DATA] {
-    self.data.as_ref()
+  pub fn from_triplets_infer_dims<I>(triplets: I) -> crate::Result<Self>
+  where
+    I: IntoIterator<Item = ([usize; D], DATA)>,
+  {
+    let coo = crate::coo::CooVec::new_infer_dims(triplets.into_iter().collect())?;
+    from_sorted_elements(coo.dims, coo.data.len(), coo.data)
   }
+}
 
-  /// Indices (indcs) of a line, i.e., indices of the innermost dimension.
+#[cfg(feature = "alloc")]
+impl<DATA, const D: usize> CslRef<'_, DATA, D> {
+  /// Owned copy of the view. Offsets are rebased to start at zero, which allows structures
+  /// produced by [`sub_dim`](#method.sub_dim) or [`line`](#method.line) to outlive their source.
+  ///
+  /// Returns [`CslError::InvalidOffsetsOrder`] if an offset of a lazily constructed view is
+  /// smaller than the first one.
   ///
   /// # Example
   ///
   /// ```rust
-  /// use ndsparse::doc_tests::csl_array_4;
-  /// assert_eq!(csl_array_4().indcs(), &[0, 3, 1, 3, 4, 2, 2, 4, 2]);
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{
+  ///   csl::{CslError, CslRef, CslVec},
+  ///   fixtures::csl_array_4,
+  /// };
+  /// let owned = {
+  ///   let csl = csl_array_4();
+  ///   csl.sub_dim::<4>(1..2).unwrap().to_vec_csl()?
+  /// };
+  /// let offs = vec![0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1];
+  /// assert_eq!(owned, CslVec::new([1, 3, 4, 5], vec![9], vec![2], offs)?);
+  /// let lazy = CslRef::new_lazy([2, 3], &[1, 2][..], &[0, 1][..], &[1, 0, 3][..])?;
+  /// let err = ndsparse::Error::Csl(CslError::InvalidOffsetsOrder { offs_idx: 1 });
+  /// assert_eq!(lazy.to_vec_csl(), Err(err));
+  /// # Ok(()) }
   /// ```
   #[inline]
-  pub fn indcs(&self) -> &[usize] {
-    self.indcs.as_ref()
+  pub fn to_vec_csl(&self) -> crate::Result<CslVec<DATA, D>>
+  where
+    DATA: Clone,
+  {
+    Ok(CslVec {
+      data: self.data.to_vec(),
+      dims: self.dims,
+      indcs: self.indcs.to_vec(),
+      offs: rebased_offs(self.offs)?,
+      outermost_stride: self.outermost_stride,
+    })
   }
+}
 
-  /// Any immutable line reference determined by `indcs`. The innermost dimension is ignored.
+#[cfg(feature = "with-bytemuck")]
+impl<'a, DATA, const D: usize> CslRef<'a, DATA, D>
+where
+  DATA: bytemuck::Pod,
+{
+  /// Creates a view over raw bytes in native endianness without copying them, e.g., the
+  /// contents of a memory-mapped file.
   ///
-  /// # Examples
+  /// Like [`new_lazy`](#method.new_lazy), only dimensions and lengths are verified. Call
+  /// [`validate`](#method.validate) to verify the remaining invariants.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  /// * `data`: Bytes of the data collection
+  /// * `indcs`: Bytes of the indices of each data item
+  /// * `offs`: Bytes of the offset of each innermost line
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-bytemuck", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-bytemuck"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslRef;
+  /// // Bytes that could come from a `memmap2::Mmap`
+  /// let (indcs, offs) = ([0usize, 2], [0usize, 2]);
+  /// let [indcs_bytes, offs_bytes] = [bytemuck::cast_slice(&indcs), bytemuck::cast_slice(&offs)];
+  /// let csl = CslRef::<u8, 1>::from_bytes([3], &[7, 8], indcs_bytes, offs_bytes)?;
+  /// csl.validate()?;
+  /// assert_eq!(csl.value([2]), Some(&8));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn from_bytes(
+    dims: [usize; D],
+    data: &'a [u8],
+    indcs: &'a [u8],
+    offs: &'a [u8],
+  ) -> crate::Result<Self> {
+    let data = bytemuck::try_cast_slice(data).map_err(|_err| CslError::InvalidBytes)?;
+    let indcs = bytemuck::try_cast_slice(indcs).map_err(|_err| CslError::InvalidBytes)?;
+    let offs = bytemuck::try_cast_slice(offs).map_err(|_err| CslError::InvalidBytes)?;
+    Self::new_lazy(dims, data, indcs, offs)
+  }
+}
+
+impl<'a, DATA> CslRef<'a, DATA, 1> {
+  /// Indices and data of all stored elements of a line.
+  ///
+  /// # Example
   ///
   /// ```rust
-  /// use ndsparse::{csl::CslRef, doc_tests::csl_array_4};
+  /// use ndsparse::fixtures::csl_array_4;
   /// let csl = csl_array_4();
-  /// assert_eq!(csl.line([0, 0, 2, 0]), CslRef::new([5], &[][..], &[][..], &[3, 3][..]).ok());
-  /// assert_eq!(csl.line([0, 1, 0, 0]), CslRef::new([5], &[6][..], &[2][..], &[5, 6][..]).ok());
+  /// let line = csl.line([0, 0, 0, 0]).unwrap();
+  /// assert_eq!(line.as_slices(), (&[0, 3][..], &[1, 2][..]));
   /// ```
   #[inline]
-  pub fn line(&self, indcs: [usize; D]) -> Option<CslRef<'_, DATA, 1>> {
-    line(self, indcs)
+  pub fn as_slices(&self) -> (&'a [usize], &'a [DATA]) {
+    (self.indcs, self.data)
   }
 
-  /// Number of NonZero elements.
+  /// Iterator of all stored elements of a line and their respective indices in ascending order.
   ///
   /// # Example
   ///
   /// ```rust
-  /// use ndsparse::doc_tests::csl_array_4;
-  /// assert_eq!(csl_array_4().nnz(), 9);
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let line = csl.line([0, 0, 0, 0]).unwrap();
+  /// assert_eq!(line.iter().collect::<Vec<_>>(), [(0, &1), (3, &2)]);
   /// ```
   #[inline]
-  pub fn nnz(&self) -> usize {
-    self.data.as_ref().len()
+  pub fn iter(&self) -> impl Iterator<Item = (usize, &'a DATA)> + 'a {
+    self.indcs.iter().copied().zip(self.data.iter())
   }
 
-  /// The joining of two consecutives offsets (offs) represent the starting and ending points of a
-  /// line in the `data` and `indcs` slices.
+  /// Binary searches the element stored at innermost index `idx`.
   ///
   /// # Example
   ///
   /// ```rust
-  /// use ndsparse::doc_tests::csl_array_4;
-  /// assert_eq!(
-  ///   csl_array_4().offs(),
-  ///   &[0, 2, 3, 3, 5, 6, 6, 6, 6, 7, 8, 8, 8, 8, 8, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9]
-  /// );
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let line = csl.line([0, 0, 0, 0]).unwrap();
+  /// assert_eq!(line.value_by_idx(3), Some(&2));
+  /// assert_eq!(line.value_by_idx(1), None);
   /// ```
   #[inline]
-  pub fn offs(&self) -> &[usize] {
-    self.offs.as_ref()
+  pub fn value_by_idx(&self, idx: usize) -> Option<&'a DATA> {
+    let data_idx = self.indcs.binary_search(&idx).ok()?;
+    self.data.get(data_idx)
   }
 
-  /// Iterator that returns immutable line references of the outermost dimension
+  /// Owned pairs of indices and data of all stored elements of a line.
   ///
-  /// # Examples
+  /// # Example
   ///
   /// ```rust
-  /// # fn main() -> ndsparse::Result<()> {
-  /// use ndsparse::{csl::CslRef, doc_tests::csl_array_4};
+  /// use ndsparse::fixtures::csl_array_4;
   /// let csl = csl_array_4();
-  /// let sub_csl = csl.sub_dim(0..3).unwrap();
-  /// let mut iter = sub_csl.outermost_line_iter()?;
-  /// assert_eq!(
-  ///   iter.next(),
-  ///   CslRef::new([1, 4, 5], &[1, 2, 3, 4, 5][..], &[0, 3, 1, 3, 4][..], &[0, 2, 3, 3, 5][..]).ok()
-  /// );
-  /// assert_eq!(iter.next(), CslRef::new([1, 4, 5], &[6][..], &[2][..], &[5, 6, 6, 6, 6][..]).ok());
-  /// assert_eq!(
-  ///   iter.next(),
-  ///   CslRef::new([1, 4, 5], &[7, 8][..], &[2, 4][..], &[6, 7, 8, 8, 8][..]).ok()
-  /// );
-  /// assert_eq!(iter.next(), None);
-  /// # Ok(()) }
+  /// let line = csl.line([0, 0, 0, 0]).unwrap();
+  /// assert_eq!(line.to_vec_pairs(), [(0, 1), (3, 2)]);
+  /// ```
+  #[cfg(feature = "alloc")]
   #[inline]
-  pub fn outermost_line_iter(&self) -> crate::Result<CslLineIterRef<'_, DATA, D>> {
-    CslLineIterRef::new(self.dims, self.data.as_ref(), self.indcs.as_ref(), self.offs.as_ref())
+  pub fn to_vec_pairs(&self) -> Vec<(usize, DATA)>
+  where
+    DATA: Clone,
+  {
+    self.iter().map(|(idx, value)| (idx, value.clone())).collect()
   }
+}
 
-  /// Parallel iterator that returns all immutable line references of the current dimension
-  /// using `rayon`.
+impl<'a, DATA> CslMut<'a, DATA, 1> {
+  /// Mutable version of [`CslRef::as_slices`](#method.as_slices).
   ///
-  /// # Examples
-  #[cfg_attr(all(feature = "alloc", feature = "with-rayon"), doc = "```rust")]
-  #[cfg_attr(not(all(feature = "alloc", feature = "with-rayon")), doc = "```ignore")]
-  /// # fn main() -> ndsparse::Result<()> {
-  /// use ndsparse::doc_tests::csl_array_4;
-  /// use rayon::prelude::*;
-  /// let csl = csl_array_4();
-  /// let outermost_rayon_iter = csl.outermost_line_rayon_iter()?;
-  /// outermost_rayon_iter.enumerate().for_each(|(idx, csl_ref)| {
-  ///   assert_eq!(csl_ref, csl.outermost_line_iter().unwrap().nth(idx).unwrap());
-  /// });
-  /// # Ok(()) }
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let mut csl = csl_array_4();
+  /// let mut line = csl.line_mut([0, 0, 0, 0]).unwrap();
+  /// let (indcs, data) = line.as_mut_slices();
+  /// data[1] = indcs[1] as i32;
+  /// assert_eq!(csl.value([0, 0, 0, 3]), Some(&3));
   /// ```
-  #[cfg(feature = "with-rayon")]
   #[inline]
-  pub fn outermost_line_rayon_iter(
-    &self,
-  ) -> crate::Result<crate::ParallelIteratorWrapper<CslLineIterRef<'_, DATA, D>>> {
-    Ok(crate::ParallelIteratorWrapper(self.outermost_line_iter()?))
+  pub fn as_mut_slices(&mut self) -> (&[usize], &mut [DATA]) {
+    (self.indcs, &mut *self.data)
   }
 
-  /// Retrieves an immutable reference of any sub dimension.
+  /// See [`CslRef::as_slices`](#method.as_slices).
+  #[inline]
+  pub fn as_slices(&self) -> (&[usize], &[DATA]) {
+    (self.indcs, &*self.data)
+  }
+
+  /// Mutable version of [`CslRef::iter`](#method.iter).
   ///
-  /// # Arguments
+  /// # Example
   ///
-  /// * `range`: Starting and ending of the desired dimension
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let mut csl = csl_array_4();
+  /// let mut line = csl.line_mut([0, 0, 3, 0]).unwrap();
+  /// line.iter_mut().for_each(|(_, value)| *value *= 10);
+  /// assert_eq!(csl.line([0, 0, 3, 0]).unwrap().as_slices(), (&[3, 4][..], &[40, 50][..]));
+  /// ```
+  #[inline]
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = (usize, &mut DATA)> {
+    self.indcs.iter().copied().zip(self.data.iter_mut())
+  }
+
+  /// Replaces the element stored at innermost index `idx` and returns a mutable reference to it.
+  /// Returns `None` without doing anything if there is no element at `idx` because views can't
+  /// grow.
   ///
   /// # Example
   ///
   /// ```rust
-  /// use ndsparse::{csl::CslRef, doc_tests::csl_array_4};
-  /// let csl = csl_array_4();
-  /// // The last cuboid
-  /// assert_eq!(
-  ///   csl.sub_dim(1..2),
-  ///   CslRef::new([1, 3, 4, 5], &[9][..], &[2][..], &[8, 8, 8, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9][..])
-  ///     .ok()
-  /// );
-  /// // The last 2 matrices of the first cuboid;
-  /// assert_eq!(
-  ///   csl.sub_dim(1..3),
-  ///   CslRef::new([2, 4, 5], &[6, 7, 8][..], &[2, 2, 4][..], &[5, 6, 6, 6, 6, 7, 8, 8, 8][..]).ok()
-  /// );
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let mut csl = csl_array_4();
+  /// let mut line = csl.line_mut([0, 0, 0, 0]).unwrap();
+  /// assert_eq!(line.set(3, 20), Some(&mut 20));
+  /// assert_eq!(line.set(1, 10), None);
+  /// assert_eq!(csl.value([0, 0, 0, 3]), Some(&20));
   /// ```
   #[inline]
-  pub fn sub_dim<const TD: usize>(&self, range: Range<usize>) -> Option<CslRef<'_, DATA, TD>> {
-    sub_dim(self, range)
+  pub fn set(&mut self, idx: usize, value: DATA) -> Option<&mut DATA> {
+    let data_idx = self.indcs.binary_search(&idx).ok()?;
+    let elem = self.data.get_mut(data_idx)?;
+    *elem = value;
+    Some(elem)
   }
+}
 
-  /// Retrieves an immutable reference of a single data value.
+impl<DS, IS, OS, const D: usize> Csl<DS, IS, OS, D> {
+  /// The maximum number of elements that the dimensions can hold, computed without saturation.
+  /// Returns `None` if the product doesn't fit in an `u128`.
   ///
-  /// # Arguments
+  /// # Example
   ///
-  /// * `indcs`: Indices of all dimensions
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// assert_eq!(csl_array_4().checked_max_nnz(), Some(120));
+  /// ```
+  #[inline]
+  pub fn checked_max_nnz(&self) -> Option<u128> {
+    crate::utils::checked_max_nnz(&self.dims)
+  }
+
+  /// The definitions of all dimensions.
   ///
   /// # Example
   ///
   /// ```rust
-  /// use ndsparse::doc_tests::csl_array_4;
-  /// let csl = csl_array_4();
-  /// assert_eq!(csl.value([1, 0, 2, 2]), Some(&9));
-  /// let line = csl.line([0, 0, 3, 0]).unwrap();
-  /// assert_eq!(line.value([3]), Some(&4));
+  /// use ndsparse::fixtures::csl_array_4;
+  /// assert_eq!(csl_array_4().dims(), &[2, 3, 4, 5]);
   /// ```
   #[inline]
-  pub fn value(&self, indcs: [usize; D]) -> Option<&DATA> {
-    let idx = data_idx(self, indcs)?;
-    self.data.as_ref().get(idx)
+  pub fn dims(&self) -> &[usize; D] {
+    &self.dims
   }
 }
 
 impl<DATA, DS, IS, OS, const D: usize> Csl<DS, IS, OS, D>
 where
-  DS: AsMut<[DATA]> + AsRef<[DATA]> + Storage<Item = DATA>,
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
   IS: AsRef<[usize]>,
   OS: AsRef<[usize]>,
 {
+  /// Creates a valid CSL instance.
+  ///
+  /// The compressed fields are a bit complex and unless you really know what you are doing, this
+  /// method shouldn't probably be used directly. Please, try to consider using [`#constructor`]
+  /// instead.
+  ///
+  /// The indices of each line must be in strictly ascending order.
+  ///
+  /// With the `with-rayon` feature, the offsets, indices and lines of large structures are
+  /// validated in parallel. Reported errors are the same as the ones of a sequential validation.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  /// * `data`: Data collection
+  /// * `indcs`: Indices of each data item
+  /// * `offs`: Offset of each innermost line
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::csl::{CslArray, CslError, CslVec};
+  /// // Sparse array ([8, _, _, _, _, 9, _, _, _, _])
+  /// let mut _sparse_array = CslArray::new([10], [8.0, 9.0], [0, 5], [0, 2]);
+  /// // A bunch of nothing for your overflow needs
+  /// let mut _over_nine: ndsparse::Result<CslVec<(), 9001>>;
+  /// _over_nine = CslVec::new([0; 9001], vec![], vec![], vec![]);
+  /// // Unordered offsets are reported before wrong lengths
+  /// let err = CslError::InvalidOffsetsOrder { offs_idx: 1 };
+  /// assert_eq!(CslVec::new([3], vec![1], vec![0], vec![1, 0, 2]), Err(err.into()));
+  /// ```
+  ///
+  #[cfg_attr(feature = "with-rayon", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-rayon"), doc = "```ignore")]
+  /// use ndsparse::csl::{CslError, CslVec};
+  /// // Enough elements to be validated in parallel
+  /// let lines = 1 << 17;
+  /// let offs: Vec<usize> = (0..=lines).map(|line| 2 * line).collect();
+  /// let mut indcs: Vec<usize> = (0..lines).flat_map(|_| [0, 1]).collect();
+  /// indcs.swap(140_000, 140_001);
+  /// indcs.swap(200_000, 200_001);
+  /// let csl = CslVec::new([lines, 2], vec![0; 2 * lines], indcs.clone(), offs.clone());
+  /// assert_eq!(csl, Err(CslError::UnsortedIndices { line_idx: 70_000 }.into()));
+  /// indcs[150_001] = 2;
+  /// let csl = CslVec::new([lines, 2], vec![0; 2 * lines], indcs, offs);
+  /// assert_eq!(csl, Err(CslError::IndcsGreaterThanEqualDimLength { dim_len: 2, idx: 2 }.into()));
+  /// ```
+  #[inline]
+  pub fn new(dims: [usize; D], data: DS, indcs: IS, offs: OS) -> crate::Result<Self> {
+    let data_ref = data.as_ref();
+    let indcs_ref = indcs.as_ref();
+    let offs_ref = offs.as_ref();
+
+    validate_all(&dims, data_ref.len(), indcs_ref, offs_ref)?;
+    Ok(Self { data, dims, indcs, offs, outermost_stride: outermost_stride(&dims) })
+  }
+
+  /// Creates an instance that is only checked against the invariants whose cost doesn't depend
+  /// on the number of lines or stored elements, i.e., dimensions and lengths.
+  ///
+  /// Useful for huge storages, e.g., memory-mapped files, where visiting every element upfront
+  /// would defeat the purpose of not copying them. The remaining invariants can be verified
+  /// later with [`validate`](#method.validate). Methods of structures with invalid offsets or
+  /// indices won't trigger undefined behavior but can return wrong results or panic.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  /// * `data`: Data collection
+  /// * `indcs`: Indices of each data item
+  /// * `offs`: Offset of each innermost line
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::csl::{CslError, CslRef};
+  /// let csl = CslRef::new_lazy([2, 3], &[1, 2][..], &[2, 0][..], &[0, 2, 2][..]).unwrap();
+  /// let err = ndsparse::Error::Csl(CslError::UnsortedIndices { line_idx: 0 });
+  /// assert_eq!(csl.validate(), Err(err));
+  /// // Lengths are always verified
+  /// assert!(CslRef::new_lazy([2, 3], &[1, 2][..], &[0, 2][..], &[0, 2][..]).is_err());
+  /// ```
+  #[inline]
+  pub fn new_lazy(dims: [usize; D], data: DS, indcs: IS, offs: OS) -> crate::Result<Self> {
+    validate_lengths(&dims, data.as_ref().len(), indcs.as_ref().len(), offs.as_ref())?;
+    Ok(Self { data, dims, indcs, offs, outermost_stride: outermost_stride(&dims) })
+  }
+
+  /// Combines the stored values of `self` and `other` where both structures have an element.
+  ///
+  /// # Arguments
+  ///
+  /// * `other`: Structure with the same dimensions
+  /// * `cb`: Callback that receives both values and returns the new value
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let a = CslVec::new([2, 3], vec![1, 2], vec![0, 2], vec![0, 1, 2])?;
+  /// let b = CslVec::new([2, 3], vec![3, 4], vec![1, 2], vec![0, 1, 2])?;
+  /// assert_eq!(a.apply_binary(&b, |a, b| a * b)?, CslVec::new([2, 3], vec![8], vec![2], vec![0, 0, 1])?);
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn apply_binary<DATA2, DATA3, DS2, F, IS2, OS2>(
+    &self,
+    other: &Csl<DS2, IS2, OS2, D>,
+    mut cb: F,
+  ) -> crate::Result<CslVec<DATA3, D>>
+  where
+    DS2: AsRef<[DATA2]> + Storage<Item = DATA2>,
+    F: FnMut(&DATA, &DATA2) -> DATA3,
+    IS2: AsRef<[usize]>,
+    OS2: AsRef<[usize]>,
+  {
+    merge_lines(self, other, |a, b| Some(cb(a?, b?)))
+  }
+
+  /// Builds an auxiliary structure that retrieves values in constant time. See
+  /// [`CslWithLookup`] for more information.
+  ///
+  /// Returns [`CslError::DimsProductOverflow`] if the bitsets of all lines don't fit in memory.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let lookup = csl.build_lookup().unwrap();
+  /// assert_eq!(lookup.value([0, 1, 0, 2]), csl.value([0, 1, 0, 2]));
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn build_lookup(&self) -> crate::Result<CslWithLookup<'_, DATA, D>> {
+    CslWithLookup::new(self.into())
+  }
+
+  /// Builds an auxiliary structure that groups the stored elements by innermost index. See
+  /// [`CslWithTranspose`] for more information.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::csl::CslArray;
+  /// // [[1, 0, 2], [0, 0, 3]]
+  /// let csl = CslArray::new([2, 3], [1, 2, 3], [0, 2, 2], [0, 2, 3]).unwrap();
+  /// let transpose = csl.build_transpose();
+  /// assert_eq!(transpose.innermost_iter(2).collect::<Vec<_>>(), [([0, 2], &2), ([1, 2], &3)]);
+  /// assert_eq!(transpose.innermost_iter(1).next(), None);
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn build_transpose(&self) -> CslWithTranspose<'_, DATA, D> {
+    CslWithTranspose::new(self.into())
+  }
+
+  /// Number of stored elements whose indices satisfy `cb`, computed by walking the offsets
+  /// without creating intermediate structures.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// assert_eq!(csl.count_where(|indcs| indcs[3] >= 3), 4);
+  /// assert_eq!(csl.count_where(|indcs| indcs[0] == indcs[2]), 4);
+  /// ```
+  #[inline]
+  pub fn count_where<F>(&self, mut cb: F) -> usize
+  where
+    F: FnMut(&[usize; D]) -> bool,
+  {
+    let indcs = self.indcs.as_ref();
+    let mut rslt = 0;
+    for (line, range) in line_ranges(self.offs.as_ref()).enumerate() {
+      let mut elem_indcs = outermost_indcs(&self.dims, line);
+      for &innermost_idx in indcs.get(range).unwrap_or_default() {
+        if let Some(r) = elem_indcs.last_mut() {
+          *r = innermost_idx;
+        }
+        rslt += usize::from(cb(&elem_indcs));
+      }
+    }
+    rslt
+  }
+
+  /// Forward cursor over all stored elements. See [`CslCursor`].
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let mut cursor = csl.cursor();
+  /// let mut nnz = 0;
+  /// while cursor.next_entry().is_some() {
+  ///   nnz += 1;
+  /// }
+  /// assert_eq!(nnz, csl.nnz());
+  /// ```
+  #[inline]
+  pub fn cursor(&self) -> CslCursor<'_, DATA, D> {
+    CslCursor::new(self.into())
+  }
+
+  /// The data that is being stored.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// assert_eq!(csl_array_4().data(), &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+  /// ```
+  #[inline]
+  pub fn data(&self) -> &[DATA] {
+    self.data.as_ref()
+  }
+
+  /// Smallest dimensions that can hold all stored elements, i.e., the greatest index of each
+  /// axis plus one. Structures without elements return zeros.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::{csl::CslArray, fixtures::csl_array_4};
+  /// assert_eq!(csl_array_4().effective_dims(), [2, 3, 4, 5]);
+  /// let csl = CslArray::new([3, 4], [1, 2], [0, 2], [0, 1, 2, 2]).unwrap();
+  /// assert_eq!(csl.effective_dims(), [2, 3]);
+  /// ```
+  #[inline]
+  pub fn effective_dims(&self) -> [usize; D] {
+    effective_dims(&self.dims, self.indcs.as_ref(), self.offs.as_ref())
+  }
+
+  /// Version of [`remap_indices`](#method.remap_indices) where the elements whose index along
+  /// `axis` is mapped to `None` are dropped, which is useful to compact label spaces after
+  /// filtering some of them out.
+  ///
+  /// # Arguments
+  ///
+  /// * `axis`: Dimension whose indices will be relabeled
+  /// * `mapping`: New index of each index of `axis`, if any
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// // 3 users x 4 items
+  /// let csl = CslVec::new([3, 4], vec![1, 2, 3, 4], vec![0, 2, 1, 3], vec![0, 2, 3, 4])?;
+  /// // Removes the second user and the third item
+  /// let users = csl.filter_remap_indices(0, &[Some(0), None, Some(1)])?;
+  /// let items = users.filter_remap_indices(1, &[Some(0), Some(1), None, Some(2)])?;
+  /// assert_eq!(items, CslVec::new([2, 3], vec![1, 4], vec![0, 2], vec![0, 1, 2])?);
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn filter_remap_indices(
+    &self,
+    axis: usize,
+    mapping: &[Option<usize>],
+  ) -> crate::Result<CslVec<DATA, D>>
+  where
+    DATA: Clone,
+  {
+    remap_indices(self, axis, mapping.len(), |idx| mapping.get(idx).copied().flatten())
+  }
+
+  /// Indices (indcs) of a line, i.e., indices of the innermost dimension.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// assert_eq!(csl_array_4().indcs(), &[0, 3, 1, 3, 4, 2, 2, 4, 2]);
+  /// ```
+  #[inline]
+  pub fn indcs(&self) -> &[usize] {
+    self.indcs.as_ref()
+  }
+
+  /// Unit-valued structure containing only the indices stored by both `self` and `other`.
+  ///
+  /// # Arguments
+  ///
+  /// * `other`: Structure with the same dimensions
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let a = CslVec::new([2, 3], vec![1, 2], vec![0, 2], vec![0, 1, 2])?;
+  /// let b = CslVec::new([2, 3], vec![3, 4], vec![1, 2], vec![0, 1, 2])?;
+  /// assert_eq!(a.intersect_pattern(&b)?, CslVec::new([2, 3], vec![()], vec![2], vec![0, 0, 1])?);
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn intersect_pattern<DATA2, DS2, IS2, OS2>(
+    &self,
+    other: &Csl<DS2, IS2, OS2, D>,
+  ) -> crate::Result<CslVec<(), D>>
+  where
+    DS2: AsRef<[DATA2]> + Storage<Item = DATA2>,
+    IS2: AsRef<[usize]>,
+    OS2: AsRef<[usize]>,
+  {
+    merge_lines(self, other, |a, b| {
+      let _ = a?;
+      let _ = b?;
+      Some(())
+    })
+  }
+
+  /// Iterator of all stored elements whose innermost index is equal to `idx`, e.g., a column of a
+  /// CSR matrix, in ascending order of lines. Every line is binary searched, so
+  /// [`build_transpose`](#method.build_transpose) should be preferred for repeated accesses.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let column = csl.innermost_iter(3).collect::<Vec<_>>();
+  /// assert_eq!(column, [([0, 0, 0, 3], &2), ([0, 0, 3, 3], &4)]);
+  /// ```
+  #[inline]
+  pub fn innermost_iter<'a>(
+    &'a self,
+    idx: usize,
+  ) -> impl Iterator<Item = ([usize; D], &'a DATA)> + 'a
+  where
+    DATA: 'a,
+  {
+    let data = self.data.as_ref();
+    let dims = self.dims;
+    let indcs = self.indcs.as_ref();
+    line_ranges(self.offs.as_ref()).enumerate().filter_map(move |(line, range)| {
+      let line_idx = indcs.get(range.clone())?.binary_search(&idx).ok()?;
+      let value = data.get(range.start.checked_add(line_idx)?)?;
+      let mut elem_indcs = outermost_indcs(&dims, line);
+      *elem_indcs.last_mut()? = idx;
+      Some((elem_indcs, value))
+    })
+  }
+
+  /// Any immutable line reference determined by `indcs`. The innermost dimension is ignored.
+  ///
+  /// See [`try_line`](#method.try_line) to know why a line couldn't be retrieved.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use ndsparse::{csl::CslRef, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// assert_eq!(csl.line([0, 0, 2, 0]), CslRef::new([5], &[][..], &[][..], &[3, 3][..]).ok());
+  /// assert_eq!(csl.line([0, 1, 0, 0]), CslRef::new([5], &[6][..], &[2][..], &[5, 6][..]).ok());
+  /// // Out of bounds indices never map to other lines
+  /// assert_eq!(csl.line([0, 0, 4, 0]), None);
+  /// ```
+  #[inline]
+  pub fn line(&self, indcs: [usize; D]) -> Option<CslRef<'_, DATA, 1>> {
+    line(self, indcs)
+  }
+
+  /// Number of lines for each number of stored elements, i.e., the element at index `nnz` is the
+  /// number of lines that have exactly `nnz` elements.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::fixtures::csl_array_4;
+  /// assert_eq!(csl_array_4().line_nnz_histogram(), vec![17, 5, 2]);
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn line_nnz_histogram(&self) -> Vec<usize> {
+    line_nnz_histogram(self.offs.as_ref())
+  }
+
+  /// Range of the line determined by `indcs` in the [`data`](#method.data) and
+  /// [`indcs`](#method.indcs) slices. The innermost dimension is ignored.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// assert_eq!(csl.line_span([0, 0, 3, 0]), Some(3..5));
+  /// assert_eq!(csl.line_span([0, 0, 2, 0]), Some(3..3));
+  /// assert_eq!(csl.line_span([0, 0, 4, 0]), None);
+  /// ```
+  #[inline]
+  pub fn line_span(&self, indcs: [usize; D]) -> Option<Range<usize>> {
+    let [_, range] = line_offs(&self.dims, self.outermost_stride, &indcs, self.offs.as_ref())?;
+    Some(range)
+  }
+
+  /// Ranges of all lines in the [`data`](#method.data) and [`indcs`](#method.indcs) slices,
+  /// which is useful for custom kernels that don't need [`CslRef`] views.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let mut spans = csl.line_spans();
+  /// assert_eq!(spans.next(), Some(0..2));
+  /// assert_eq!(spans.next(), Some(2..3));
+  /// assert_eq!(spans.next(), Some(3..3));
+  /// assert_eq!(spans.count(), 21);
+  /// ```
+  #[inline]
+  pub fn line_spans(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+    line_ranges(self.offs.as_ref())
+  }
+
+  /// Copy of `self` that only keeps the entries whose indices are also stored by `mask`. The
+  /// values of `mask` are ignored.
+  ///
+  /// # Arguments
+  ///
+  /// * `mask`: Structure with the same dimensions
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let a = CslVec::new([2, 3], vec![1, 2, 3], vec![0, 2, 1], vec![0, 2, 3])?;
+  /// let mask = CslVec::new([2, 3], vec![(), ()], vec![2, 1], vec![0, 1, 2])?;
+  /// assert_eq!(a.mask(&mask)?, CslVec::new([2, 3], vec![2, 3], vec![2, 1], vec![0, 1, 2])?);
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn mask<DATA2, DS2, IS2, OS2>(
+    &self,
+    mask: &Csl<DS2, IS2, OS2, D>,
+  ) -> crate::Result<CslVec<DATA, D>>
+  where
+    DATA: Clone,
+    DS2: AsRef<[DATA2]> + Storage<Item = DATA2>,
+    IS2: AsRef<[usize]>,
+    OS2: AsRef<[usize]>,
+  {
+    merge_lines(self, mask, |a, b| {
+      let _ = b?;
+      a.cloned()
+    })
+  }
+
+  /// Number of bytes occupied by the stored data, indices and offsets.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use core::mem::{size_of, size_of_val};
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let usage = csl.memory_usage();
+  /// assert_eq!(usage.data_bytes, 9 * size_of::<i32>());
+  /// assert_eq!(usage.indcs_bytes, 9 * size_of::<usize>());
+  /// assert_eq!(usage.offs_bytes, 25 * size_of::<usize>());
+  /// assert_eq!(usage.overhead, size_of_val(&csl));
+  /// ```
+  #[inline]
+  pub fn memory_usage(&self) -> MemoryUsage {
+    let usize_size = mem::size_of::<usize>();
+    MemoryUsage {
+      data_bytes: self.data.as_ref().len().saturating_mul(mem::size_of::<DATA>()),
+      indcs_bytes: self.indcs.as_ref().len().saturating_mul(usize_size),
+      offs_bytes: self.offs.as_ref().len().saturating_mul(usize_size),
+      overhead: mem::size_of::<Self>(),
+    }
+  }
+
+  /// Tensor-times-matrix product along `axis`, i.e., every fiber of `axis` is multiplied by
+  /// `dense_matrix`. The dimension of `axis` is replaced by `rows` in the returned structure.
+  ///
+  /// Every fiber that has at least one stored element produces `rows` stored elements, even if
+  /// some of them are equal to zero.
+  ///
+  /// # Arguments
+  ///
+  /// * `dense_matrix`: Row-major matrix with `rows` rows and `self.dims()[axis]` columns
+  /// * `rows`: Number of rows of `dense_matrix`
+  /// * `axis`: Dimension that will be contracted
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// use num_bigint::BigUint;
+  /// // [[1, 0, 2], [0, 3, 0]]
+  /// let csl = CslVec::new([2, 3], vec![1, 2, 3], vec![0, 2, 1], vec![0, 2, 3])?;
+  /// // Column sums
+  /// let sums = csl.mode_n_product(&[1, 1], 1, 0)?;
+  /// assert_eq!(sums, CslVec::new([1, 3], vec![1, 3, 2], vec![0, 1, 2], vec![0, 3])?);
+  /// // [[1, 1, 1], [0, 1, 0]] applied to each row
+  /// let rows = csl.mode_n_product(&[1, 1, 1, 0, 1, 0], 2, 1)?;
+  /// assert_eq!(rows, CslVec::new([2, 2], vec![3, 0, 3, 3], vec![0, 1, 0, 1], vec![0, 2, 4])?);
+  /// // Payloads only need to be `Clone`
+  /// let big = CslVec::new([1, 2], vec![BigUint::from(u64::MAX)], vec![1], vec![0, 1])?;
+  /// let doubled = big.mode_n_product(&[BigUint::from(0u8), BigUint::from(2u8)], 1, 1)?;
+  /// assert_eq!(doubled.value([0, 0]), Some(&(BigUint::from(u64::MAX) * 2u8)));
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn mode_n_product(
+    &self,
+    dense_matrix: &[DATA],
+    rows: usize,
+    axis: usize,
+  ) -> crate::Result<CslVec<DATA, D>>
+  where
+    DATA: Add<Output = DATA> + Clone + Default + Mul<Output = DATA>,
+  {
+    mode_n_product(self, dense_matrix, rows, axis)
+  }
+
+  /// Number of NonZero elements. For line views, only the elements of the line are considered.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// assert_eq!(csl.nnz(), 9);
+  /// assert_eq!(csl.line([0, 0, 0, 0]).map(|line| line.nnz()), Some(2));
+  /// ```
+  #[inline]
+  pub fn nnz(&self) -> usize {
+    self.data.as_ref().len()
+  }
+
+  /// Number of stored elements whose indices are contained by the respective `ranges`. Lines
+  /// outside the outer ranges are skipped and the innermost range is located with binary
+  /// searches.
+  ///
+  /// # Arguments
+  ///
+  /// * `ranges`: Range of each dimension
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// assert_eq!(csl.nnz_in_slice([0..1, 0..3, 0..4, 2..5]), 6);
+  /// assert_eq!(csl.nnz_in_slice([0..2, 0..3, 0..4, 0..5]), 9);
+  /// assert_eq!(csl.nnz_in_slice([1..2, 1..3, 0..4, 0..5]), 0);
+  /// ```
+  #[inline]
+  pub fn nnz_in_slice(&self, ranges: [Range<usize>; D]) -> usize {
+    let indcs = self.indcs.as_ref();
+    let innermost = ranges.last().cloned().unwrap_or(0..0);
+    let outer_len = D.saturating_sub(1);
+    line_ranges(self.offs.as_ref())
+      .enumerate()
+      .filter(|(line, _)| {
+        let elem_indcs = outermost_indcs(&self.dims, *line);
+        elem_indcs.iter().zip(ranges.iter()).take(outer_len).all(|(idx, range)| range.contains(idx))
+      })
+      .map(|(_, range)| {
+        let line_indcs = indcs.get(range).unwrap_or_default();
+        let start = line_indcs.partition_point(|&idx| idx < innermost.start);
+        let end = line_indcs.partition_point(|&idx| idx < innermost.end);
+        end.saturating_sub(start)
+      })
+      .sum()
+  }
+
+  /// The joining of two consecutives offsets (offs) represent the starting and ending points of a
+  /// line in the `data` and `indcs` slices.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// assert_eq!(
+  ///   csl_array_4().offs(),
+  ///   &[0, 2, 3, 3, 5, 6, 6, 6, 6, 7, 8, 8, 8, 8, 8, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9]
+  /// );
+  /// ```
+  #[inline]
+  pub fn offs(&self) -> &[usize] {
+    self.offs.as_ref()
+  }
+
+  /// Iterator that returns immutable references where each one covers `chunk_size` outermost
+  /// indices. The last reference can cover fewer indices if the outermost dimension length is not
+  /// a multiple of `chunk_size`.
+  ///
+  /// # Arguments
+  ///
+  /// * `chunk_size`: Number of outermost indices of each chunk. Must be greater than zero.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{csl::CslRef, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// let sub_csl = csl.sub_dim(0..3).unwrap();
+  /// let mut iter = sub_csl.outermost_chunks(2)?;
+  /// assert_eq!(iter.len(), 2);
+  /// assert_eq!(
+  ///   iter.next(),
+  ///   CslRef::new(
+  ///     [2, 4, 5],
+  ///     &[1, 2, 3, 4, 5, 6][..],
+  ///     &[0, 3, 1, 3, 4, 2][..],
+  ///     &[0, 2, 3, 3, 5, 6, 6, 6, 6][..]
+  ///   )
+  ///   .ok()
+  /// );
+  /// assert_eq!(
+  ///   iter.next(),
+  ///   CslRef::new([1, 4, 5], &[7, 8][..], &[2, 4][..], &[6, 7, 8, 8, 8][..]).ok()
+  /// );
+  /// assert_eq!(iter.next(), None);
+  /// assert_eq!(sub_csl.outermost_chunks(2)?.next_back().map(|chunk| *chunk.dims()), Some([1, 4, 5]));
+  /// assert!(sub_csl.outermost_chunks(0).is_err());
+  /// # Ok(()) }
+  #[inline]
+  pub fn outermost_chunks(&self, chunk_size: usize) -> crate::Result<CslLineIterRef<'_, DATA, D>> {
+    CslLineIterRef::new(
+      chunk_size,
+      self.dims,
+      self.outermost_stride,
+      self.data.as_ref(),
+      self.indcs.as_ref(),
+      self.offs.as_ref(),
+    )
+  }
+
+  /// Parallel version of [`outermost_chunks`](#method.outermost_chunks) using `rayon`.
+  ///
+  /// # Examples
+  #[cfg_attr(all(feature = "alloc", feature = "with-rayon"), doc = "```rust")]
+  #[cfg_attr(not(all(feature = "alloc", feature = "with-rayon")), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::fixtures::csl_array_4;
+  /// use rayon::prelude::*;
+  /// let csl = csl_array_4();
+  /// let nnzs: Vec<usize> = csl.outermost_chunks_rayon(3)?.map(|chunk| chunk.nnz()).collect();
+  /// assert_eq!(nnzs, csl.outermost_chunks(3)?.map(|chunk| chunk.nnz()).collect::<Vec<_>>());
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "with-rayon")]
+  #[inline]
+  pub fn outermost_chunks_rayon(
+    &self,
+    chunk_size: usize,
+  ) -> crate::Result<crate::ParallelIteratorWrapper<CslLineIterRef<'_, DATA, D>>> {
+    Ok(crate::ParallelIteratorWrapper(self.outermost_chunks(chunk_size)?))
+  }
+
+  /// Iterator that returns immutable line references of the outermost dimension
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{csl::CslRef, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// let sub_csl = csl.sub_dim(0..3).unwrap();
+  /// let mut iter = sub_csl.outermost_line_iter()?;
+  /// assert_eq!(
+  ///   iter.next(),
+  ///   CslRef::new([1, 4, 5], &[1, 2, 3, 4, 5][..], &[0, 3, 1, 3, 4][..], &[0, 2, 3, 3, 5][..]).ok()
+  /// );
+  /// assert_eq!(iter.next(), CslRef::new([1, 4, 5], &[6][..], &[2][..], &[5, 6, 6, 6, 6][..]).ok());
+  /// assert_eq!(
+  ///   iter.next(),
+  ///   CslRef::new([1, 4, 5], &[7, 8][..], &[2, 4][..], &[6, 7, 8, 8, 8][..]).ok()
+  /// );
+  /// assert_eq!(iter.next(), None);
+  /// # Ok(()) }
+  #[inline]
+  pub fn outermost_line_iter(&self) -> crate::Result<CslLineIterRef<'_, DATA, D>> {
+    let (data, indcs, offs) = (self.data.as_ref(), self.indcs.as_ref(), self.offs.as_ref());
+    CslLineIterRef::new(1, self.dims, self.outermost_stride, data, indcs, offs)
+  }
+
+  /// Parallel iterator that returns all immutable line references of the current dimension
+  /// using `rayon`.
+  ///
+  /// # Examples
+  #[cfg_attr(all(feature = "alloc", feature = "with-rayon"), doc = "```rust")]
+  #[cfg_attr(not(all(feature = "alloc", feature = "with-rayon")), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::fixtures::csl_array_4;
+  /// use rayon::prelude::*;
+  /// let csl = csl_array_4();
+  /// let outermost_rayon_iter = csl.outermost_line_rayon_iter()?;
+  /// outermost_rayon_iter.enumerate().for_each(|(idx, csl_ref)| {
+  ///   assert_eq!(csl_ref, csl.outermost_line_iter().unwrap().nth(idx).unwrap());
+  /// });
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "with-rayon")]
+  #[inline]
+  pub fn outermost_line_rayon_iter(
+    &self,
+  ) -> crate::Result<crate::ParallelIteratorWrapper<CslLineIterRef<'_, DATA, D>>> {
+    Ok(crate::ParallelIteratorWrapper(self.outermost_line_iter()?))
+  }
+
+  /// Grows the dimension `axis` by `before` positions at the beginning and by `after` positions
+  /// at the end, i.e., stored indices of the innermost dimension or lines of outer dimensions are
+  /// shifted by `before`.
+  ///
+  /// A zero-length dimension has no positions, therefore, padding it yields a structure without
+  /// elements where every dimension up to the last zero-length one is unused.
+  ///
+  /// # Arguments
+  ///
+  /// * `axis`: Dimension that will be padded
+  /// * `before`: Number of empty positions inserted before the first position
+  /// * `after`: Number of empty positions inserted after the last position
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{csl::CslVec, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// let padded = csl.pad(3, 1, 2)?;
+  /// assert_eq!(padded.dims(), &[2, 3, 4, 8]);
+  /// assert_eq!(padded.value([0, 0, 0, 1]), Some(&1));
+  /// let padded = csl.pad(0, 1, 0)?;
+  /// assert_eq!(padded.dims(), &[3, 3, 4, 5]);
+  /// assert_eq!(padded.value([0, 0, 0, 0]), None);
+  /// assert_eq!(padded.value([2, 0, 2, 2]), Some(&9));
+  /// let empty = CslVec::<i32, 3>::new([0, 0, 3], vec![], vec![], vec![0, 0])?;
+  /// let padded = empty.pad(1, 1, 1)?;
+  /// assert_eq!(padded.dims(), &[0, 2, 3]);
+  /// assert_eq!(padded.offs(), &[0, 0, 0]);
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn pad(&self, axis: usize, before: usize, after: usize) -> crate::Result<CslVec<DATA, D>>
+  where
+    DATA: Clone,
+  {
+    pad(self, axis, before, after)
+  }
+
+  /// Parallel version of [`try_fold_lines`](#method.try_fold_lines) using `rayon`. Lines are
+  /// split into groups that are folded independently, starting from `identity()`, and the
+  /// partial results are combined by `reduce`, therefore, `reduce` must be associative.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-rayon", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-rayon"), doc = "```ignore")]
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let empty_lines =
+  ///   csl.par_fold_lines(|| 0, |acc, _, line| acc + usize::from(line.nnz() == 0), |a, b| a + b);
+  /// assert_eq!(empty_lines, 17);
+  /// ```
+  #[cfg(feature = "with-rayon")]
+  #[inline]
+  pub fn par_fold_lines<F, ID, R, T>(&self, identity: ID, fold: F, reduce: R) -> T
+  where
+    DATA: Sync,
+    F: Fn(T, usize, CslRef<'_, DATA, 1>) -> T + Send + Sync,
+    ID: Fn() -> T + Send + Sync,
+    R: Fn(T, T) -> T + Send + Sync,
+    T: Send,
+  {
+    par_fold_lines(self, identity, fold, reduce)
+  }
+
+  /// Sums all stored elements in parallel using `rayon`.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-rayon", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-rayon"), doc = "```ignore")]
+  /// use ndsparse::fixtures::csl_array_4;
+  /// assert_eq!(csl_array_4().par_sum(), 45);
+  /// ```
+  #[cfg(feature = "with-rayon")]
+  #[inline]
+  pub fn par_sum(&self) -> DATA
+  where
+    DATA: Clone + Send + Sync + core::iter::Sum,
+  {
+    use rayon::prelude::*;
+    self.data.as_ref().par_iter().cloned().sum()
+  }
+
+  /// Splits the outermost dimension into at most `num_parts` contiguous and non-empty ranges
+  /// that have roughly the same number of stored elements, which is useful to balance the work of
+  /// parallel consumers of [`sub_dim`](#method.sub_dim).
+  ///
+  /// One-dimensional structures only have a single line and always return the whole range.
+  ///
+  /// # Arguments
+  ///
+  /// * `num_parts`: Maximum number of returned ranges
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let csl = CslVec::new([4, 4], vec![1; 8], vec![0, 1, 2, 3, 0, 1, 2, 3], vec![0, 4, 5, 6, 8])?;
+  /// assert_eq!(csl.partition_lines(2), vec![0..1, 1..4]);
+  /// assert_eq!(csl.partition_lines(9), vec![0..1, 1..2, 2..3, 3..4]);
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn partition_lines(&self, num_parts: usize) -> Vec<Range<usize>> {
+    partition_lines(&self.dims, self.offs.as_ref(), num_parts)
+  }
+
+  /// Reorders the outermost dimension according to `perm`, i.e., the new outermost index `idx`
+  /// will contain the lines of the old outermost index `perm[idx]`.
+  ///
+  /// # Arguments
+  ///
+  /// * `perm`: Permutation of all outermost indices
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let permuted = csl.permute_outermost(&[1, 0])?;
+  /// assert_eq!(permuted.data(), &[9, 1, 2, 3, 4, 5, 6, 7, 8]);
+  /// assert_eq!(permuted.value([0, 0, 2, 2]), Some(&9));
+  /// assert_eq!(permuted.value([1, 0, 0, 0]), Some(&1));
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn permute_outermost(&self, perm: &[usize]) -> crate::Result<CslVec<DATA, D>>
+  where
+    DATA: Clone,
+  {
+    permute_outermost(self, perm)
+  }
+
+  /// Relabels the indices along `axis` through the lookup table `mapping`, i.e., the element of
+  /// index `idx` is moved to index `mapping[idx]`. The length of `axis` becomes the greatest
+  /// mapped index plus one.
+  ///
+  /// # Arguments
+  ///
+  /// * `axis`: Dimension whose indices will be relabeled
+  /// * `mapping`: New index of each index of `axis`, without repetitions
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::fixtures::csl_vec_4;
+  /// let csl = csl_vec_4().remap_indices(3, &[4, 3, 2, 1, 0])?;
+  /// assert_eq!(csl.value([0, 0, 0, 4]), Some(&1));
+  /// assert_eq!(csl.value([1, 0, 2, 2]), Some(&9));
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn remap_indices(&self, axis: usize, mapping: &[usize]) -> crate::Result<CslVec<DATA, D>>
+  where
+    DATA: Clone,
+  {
+    remap_indices(self, axis, mapping.len(), |idx| mapping.get(idx).copied())
+  }
+
+  /// Cyclically shifts the stored elements by `amount` positions along `axis`, like
+  /// `numpy.roll`. Elements that go beyond the last position are reintroduced at the first
+  /// position and negative amounts shift towards the first position.
+  ///
+  /// # Arguments
+  ///
+  /// * `axis`: Dimension whose indices will be shifted
+  /// * `amount`: Number of positions
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let rolled = csl.roll(3, 2)?;
+  /// assert_eq!(rolled.value([0, 0, 0, 2]), Some(&1));
+  /// assert_eq!(rolled.value([0, 0, 3, 1]), Some(&5));
+  /// assert_eq!(rolled.value([0, 0, 3, 3]), None);
+  /// let restored = rolled.roll(3, -2)?;
+  /// assert_eq!((restored.data(), restored.indcs()), (csl.data(), csl.indcs()));
+  /// let rolled = csl.roll(0, -1)?;
+  /// assert_eq!(rolled.value([0, 0, 2, 2]), Some(&9));
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn roll(&self, axis: usize, amount: isize) -> crate::Result<CslVec<DATA, D>>
+  where
+    DATA: Clone,
+  {
+    roll(self, axis, amount)
+  }
+
+  /// Gathers the lines of the outermost indices of `indices`, in the given order, into a new
+  /// structure. Unlike [`sub_dim`](#method.sub_dim), indices don't need to be contiguous and can
+  /// be repeated.
+  ///
+  /// # Arguments
+  ///
+  /// * `indices`: Outermost indices that compose the new outermost dimension
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let selected = csl.select_outermost(&[1, 0, 1])?;
+  /// assert_eq!(selected.dims(), &[3, 3, 4, 5]);
+  /// assert_eq!(selected.data(), &[9, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+  /// assert_eq!(selected.value([2, 0, 2, 2]), Some(&9));
+  /// assert!(csl.select_outermost(&[2]).is_err());
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn select_outermost(&self, indices: &[usize]) -> crate::Result<CslVec<DATA, D>>
+  where
+    DATA: Clone,
+  {
+    select_outermost(self, indices)
+  }
+
+  /// Removes the dimension `axis`, whose length must be one, and returns a structure with `TD`
+  /// dimensions, i.e., `D - 1`. Stored elements are the same but the index of `axis` is dropped.
+  ///
+  /// # Arguments
+  ///
+  /// * `axis`: Dimension that will be removed
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let last_cuboid = csl.sub_dim::<4>(1..2).unwrap().squeeze::<3>(0)?;
+  /// assert_eq!(last_cuboid.dims(), &[3, 4, 5]);
+  /// assert_eq!(last_cuboid.value([0, 2, 2]), Some(&9));
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn squeeze<const TD: usize>(&self, axis: usize) -> crate::Result<CslVec<DATA, TD>>
+  where
+    DATA: Clone,
+  {
+    squeeze(self, axis)
+  }
+
+  /// Retrieves an immutable reference of any sub dimension.
+  ///
+  /// See [`try_sub_dim`](#method.try_sub_dim) to know why a sub dimension couldn't be retrieved.
+  ///
+  /// # Arguments
+  ///
+  /// * `range`: Starting and ending of the desired dimension
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::{csl::CslRef, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// // The last cuboid
+  /// assert_eq!(
+  ///   csl.sub_dim(1..2),
+  ///   CslRef::new([1, 3, 4, 5], &[9][..], &[2][..], &[8, 8, 8, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9][..])
+  ///     .ok()
+  /// );
+  /// // The last 2 matrices of the first cuboid;
+  /// assert_eq!(
+  ///   csl.sub_dim(1..3),
+  ///   CslRef::new([2, 4, 5], &[6, 7, 8][..], &[2, 2, 4][..], &[5, 6, 6, 6, 6, 7, 8, 8, 8][..]).ok()
+  /// );
+  /// // Ranges that go beyond the stored lines
+  /// assert_eq!(csl.sub_dim::<4>(0..3), None);
+  /// ```
+  #[inline]
+  pub fn sub_dim<const TD: usize>(&self, range: Range<usize>) -> Option<CslRef<'_, DATA, TD>> {
+    sub_dim(self, range)
+  }
+
+  /// Replicates the stored elements `reps[axis]` times along each axis, like `numpy.tile`, i.e.,
+  /// the length of each dimension is multiplied by its number of repetitions.
+  ///
+  /// Unused dimensions, i.e., leading zeros, aren't replicated. A zero repetition yields a
+  /// structure without elements where every dimension up to the zero-length one is unused.
+  ///
+  /// # Arguments
+  ///
+  /// * `reps`: Number of repetitions of each dimension
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// // [1, _]
+  /// // [_, 2]
+  /// let csl = CslVec::new([2, 2], vec![1, 2], vec![0, 1], vec![0, 1, 2])?;
+  /// // [1, _, 1, _]
+  /// // [_, 2, _, 2]
+  /// // [1, _, 1, _]
+  /// // [_, 2, _, 2]
+  /// let tiled = csl.tile([2, 2])?;
+  /// assert_eq!(tiled.dims(), &[4, 4]);
+  /// assert_eq!(tiled.data(), &[1, 1, 2, 2, 1, 1, 2, 2]);
+  /// assert_eq!(tiled.indcs(), &[0, 2, 1, 3, 0, 2, 1, 3]);
+  /// assert_eq!(tiled.offs(), &[0, 2, 4, 6, 8]);
+  /// let empty = csl.tile([2, 0])?;
+  /// assert_eq!(empty.dims(), &[0, 0]);
+  /// assert_eq!(empty.nnz(), 0);
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn tile(&self, reps: [usize; D]) -> crate::Result<CslVec<DATA, D>>
+  where
+    DATA: Clone,
+  {
+    tile(self, reps)
+  }
+
+  /// Exports this instance as a single-element Arrow `StructArray` where `dims`, `data`,
+  /// `indcs` and `offs` are list fields. Offsets are rebased to start at zero and all indices are
+  /// stored as `u64`.
+  ///
+  /// The resulting array can be written to Parquet or IPC files and converted back with
+  /// [`from_arrow`](#method.from_arrow).
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-arrow", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-arrow"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use arrow_array::{types::Int32Type, Array};
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let array = csl_array_4().to_arrow::<Int32Type>()?;
+  /// assert_eq!(array.len(), 1);
+  /// assert_eq!(array.num_columns(), 4);
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "with-arrow")]
+  #[inline]
+  pub fn to_arrow<T>(&self) -> crate::Result<arrow_array::StructArray>
+  where
+    T: arrow_array::ArrowPrimitiveType<Native = DATA>,
+  {
+    csl_arrow::to_arrow::<T>(&self.dims, self.data(), self.indcs(), self.offs())
+  }
+
+  /// Writes this instance into `group` using the layout of `scipy.sparse` and `h5py`: the
+  /// `data`, `indices` and `indptr` datasets plus a `shape` attribute with all dimensions.
+  /// Offsets are rebased to start at zero and all indices are stored as `i64`.
+  ///
+  /// For two dimensions, the group can be read in Python with
+  /// `scipy.sparse.csr_matrix((g["data"], g["indices"], g["indptr"]), shape=g.attrs["shape"])`.
+  /// For more dimensions, `indptr` contains the offsets of every line of the flattened
+  /// outermost dimensions.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-hdf5", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-hdf5"), doc = "```ignore")]
+  /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let path = std::env::temp_dir().join("ndsparse_to_hdf5.h5");
+  /// let file = hdf5::File::create(&path)?;
+  /// let group = file.create_group("csl")?;
+  /// csl_array_4().to_hdf5(&group)?;
+  /// assert_eq!(group.dataset("data")?.read_raw::<i32>()?, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+  /// assert_eq!(group.dataset("indices")?.read_raw::<i64>()?, [0, 3, 1, 3, 4, 2, 2, 4, 2]);
+  /// assert_eq!(group.attr("shape")?.read_raw::<i64>()?, [2, 3, 4, 5]);
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "with-hdf5")]
+  #[inline]
+  pub fn to_hdf5(&self, group: &hdf5::Group) -> crate::Result<()>
+  where
+    DATA: hdf5::H5Type,
+  {
+    csl_hdf5::to_hdf5(group, &self.dims, self.data(), self.indcs(), self.offs())
+  }
+
+  /// Folds every line in order, passing the accumulator, the line index and a reference to the
+  /// line to `cb`. The first error returned by `cb` stops the iteration and is returned as is.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let sum = csl.try_fold_lines(0, |acc, _, line| Ok::<_, ()>(acc + line.data().iter().sum::<i32>()));
+  /// assert_eq!(sum, Ok(45));
+  /// let first_empty =
+  ///   csl.try_fold_lines((), |_, idx, line| if line.nnz() == 0 { Err(idx) } else { Ok(()) });
+  /// assert_eq!(first_empty, Err(2));
+  /// ```
+  #[inline]
+  pub fn try_fold_lines<B, E, F>(&self, init: B, mut cb: F) -> Result<B, E>
+  where
+    F: FnMut(B, usize, CslRef<'_, DATA, 1>) -> Result<B, E>,
+  {
+    let last_dim = if let Some(r) = self.dims.last() { *r } else { return Ok(init) };
+    let data = self.data.as_ref();
+    let indcs = self.indcs.as_ref();
+    let offs = self.offs.as_ref();
+    let mut acc = init;
+    for (idx, (range, line_offs)) in line_ranges(offs).zip(offs.windows(2)).enumerate() {
+      let line = CslRef {
+        data: data.get(range.clone()).unwrap_or_default(),
+        dims: [last_dim],
+        indcs: indcs.get(range).unwrap_or_default(),
+        offs: line_offs,
+        outermost_stride: Some(1),
+      };
+      acc = cb(acc, idx, line)?;
+    }
+    Ok(acc)
+  }
+
+  /// Fallible version of [`line`](#method.line) that reports why the line couldn't be retrieved.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::{csl::{CslError, CslRef}, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// assert_eq!(csl.try_line([0, 1, 0, 0]), CslRef::new([5], &[6][..], &[2][..], &[5, 6][..]));
+  /// let err = CslError::IndexOutOfBounds { axis: 1, dim_len: 3, idx: 3 };
+  /// assert_eq!(csl.try_line([0, 3, 0, 0]), Err(ndsparse::Error::Csl(err)));
+  /// ```
+  #[inline]
+  pub fn try_line(&self, indcs: [usize; D]) -> crate::Result<CslRef<'_, DATA, 1>> {
+    try_line(self, indcs)
+  }
+
+  /// Fallible version of [`sub_dim`](#method.sub_dim) that reports why the sub dimension
+  /// couldn't be retrieved.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::{csl::CslError, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// assert_eq!(csl.try_sub_dim::<4>(0..2).map(|x| x.nnz()), Ok(9));
+  /// let err = CslError::InvalidRank { expected: 4, found: 5 };
+  /// assert_eq!(csl.try_sub_dim::<5>(0..1), Err(ndsparse::Error::Csl(err)));
+  /// let err = CslError::InvalidRange { start: 2, end: 1 };
+  /// assert_eq!(csl.try_sub_dim::<4>(2..1), Err(ndsparse::Error::Csl(err)));
+  /// ```
+  #[inline]
+  pub fn try_sub_dim<const TD: usize>(
+    &self,
+    range: Range<usize>,
+  ) -> crate::Result<CslRef<'_, DATA, TD>> {
+    try_sub_dim(self, range)
+  }
+
+  /// Unit-valued structure containing all indices stored by `self` or `other`.
+  ///
+  /// # Arguments
+  ///
+  /// * `other`: Structure with the same dimensions
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let a = CslVec::new([2, 3], vec![1, 2], vec![0, 2], vec![0, 1, 2])?;
+  /// let b = CslVec::new([2, 3], vec![3, 4], vec![1, 2], vec![0, 1, 2])?;
+  /// assert_eq!(
+  ///   a.union_pattern(&b)?,
+  ///   CslVec::new([2, 3], vec![(), (), ()], vec![0, 1, 2], vec![0, 2, 3])?
+  /// );
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn union_pattern<DATA2, DS2, IS2, OS2>(
+    &self,
+    other: &Csl<DS2, IS2, OS2, D>,
+  ) -> crate::Result<CslVec<(), D>>
+  where
+    DS2: AsRef<[DATA2]> + Storage<Item = DATA2>,
+    IS2: AsRef<[usize]>,
+    OS2: AsRef<[usize]>,
+  {
+    merge_lines(self, other, |_, _| Some(()))
+  }
+
+  /// Inserts a new dimension of length one at `axis` and returns a structure with `TD`
+  /// dimensions, i.e., `D + 1`. Stored elements are the same but have index zero in `axis`.
+  ///
+  /// # Arguments
+  ///
+  /// * `axis`: Position of the new dimension, from `0` to `D`
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::fixtures::{csl_array_4, csl_vec_4};
+  /// let csl = csl_array_4();
+  /// let unsqueezed = csl.unsqueeze::<5>(1)?;
+  /// assert_eq!(unsqueezed.dims(), &[2, 1, 3, 4, 5]);
+  /// assert_eq!(unsqueezed.value([1, 0, 0, 2, 2]), Some(&9));
+  /// assert_eq!(unsqueezed.squeeze::<4>(1)?, csl_vec_4());
+  /// let innermost = csl.unsqueeze::<5>(4)?;
+  /// assert_eq!(innermost.dims(), &[2, 3, 4, 5, 1]);
+  /// assert_eq!(innermost.value([1, 0, 2, 2, 0]), Some(&9));
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn unsqueeze<const TD: usize>(&self, axis: usize) -> crate::Result<CslVec<DATA, TD>>
+  where
+    DATA: Clone,
+  {
+    unsqueeze(self, axis)
+  }
+
+  /// Retrieves an immutable reference of a single data value.
+  ///
+  /// # Arguments
+  ///
+  /// * `indcs`: Indices of all dimensions
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// assert_eq!(csl.value([1, 0, 2, 2]), Some(&9));
+  /// let line = csl.line([0, 0, 3, 0]).unwrap();
+  /// assert_eq!(line.value([3]), Some(&4));
+  /// ```
+  #[inline]
+  pub fn value(&self, indcs: [usize; D]) -> Option<&DATA> {
+    let idx = data_idx(self, indcs)?;
+    self.data.as_ref().get(idx)
+  }
+
+  /// Verifies all the invariants that are also verified by [`new`](#method.new), which is useful
+  /// for structures created by [`new_lazy`](#method.new_lazy), deserialized or modified in place,
+  /// e.g., through [`truncate`](#method.truncate).
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// assert_eq!(csl_array_4().validate(), Ok(()));
+  /// ```
+  #[inline]
+  pub fn validate(&self) -> crate::Result<()> {
+    validate_all(&self.dims, self.data.as_ref().len(), self.indcs.as_ref(), self.offs.as_ref())
+  }
+
+  /// Unit-valued structure containing only the indices stored by exactly one of `self` and
+  /// `other`.
+  ///
+  /// # Arguments
+  ///
+  /// * `other`: Structure with the same dimensions
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let a = CslVec::new([2, 3], vec![1, 2], vec![0, 2], vec![0, 1, 2])?;
+  /// let b = CslVec::new([2, 3], vec![3, 4], vec![1, 2], vec![0, 1, 2])?;
+  /// assert_eq!(a.xor_pattern(&b)?, CslVec::new([2, 3], vec![(), ()], vec![0, 1], vec![0, 2, 2])?);
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn xor_pattern<DATA2, DS2, IS2, OS2>(
+    &self,
+    other: &Csl<DS2, IS2, OS2, D>,
+  ) -> crate::Result<CslVec<(), D>>
+  where
+    DS2: AsRef<[DATA2]> + Storage<Item = DATA2>,
+    IS2: AsRef<[usize]>,
+    OS2: AsRef<[usize]>,
+  {
+    merge_lines(self, other, |a, b| if a.is_some() == b.is_some() { None } else { Some(()) })
+  }
+}
+
+impl<DATA, DS, IS, OS, const D: usize> Csl<DS, IS, OS, D>
+where
+  DS: AsMut<[DATA]> + AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  /// Adds `delta` to the element located at `indcs` or inserts `delta` if there is no such
+  /// element, which requires storages that can grow.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let mut csl = CslVec::new([2, 2], vec![1], vec![0], vec![0, 1, 1])?;
+  /// csl.add_assign_at([0, 0], 2)?;
+  /// csl.add_assign_at([1, 1], 3)?;
+  /// assert_eq!(csl, CslVec::new([2, 2], vec![3, 3], vec![0, 1], vec![0, 1, 2])?);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn add_assign_at(&mut self, indcs: [usize; D], delta: DATA) -> crate::Result<()>
+  where
+    DATA: AddAssign,
+    DS: Insert<Input = (usize, DATA)>,
+    IS: Insert<Input = (usize, usize)>,
+    OS: AsMut<[usize]>,
+  {
+    let entry = self.entry(indcs)?;
+    if entry.is_occupied() {
+      let _ = entry.and_modify(|value| *value += delta);
+    } else {
+      let _ = entry.or_insert(delta)?;
+    }
+    Ok(())
+  }
+
   /// Clears all values and dimensions.
   ///
   /// # Example
   #[cfg_attr(feature = "alloc", doc = "```rust")]
   #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
-  /// use ndsparse::{csl::CslVec, doc_tests::csl_vec_4};
+  /// use ndsparse::{csl::CslVec, fixtures::csl_vec_4};
+  /// let mut csl = csl_vec_4();
+  /// csl.clear();
+  /// assert_eq!(csl, CslVec::default());
+  /// ```
+  #[inline]
+  pub fn clear(&mut self)
+  where
+    DS: Clear,
+    IS: Clear,
+    OS: Clear,
+  {
+    self.dims = cl_traits::default_array();
+    self.outermost_stride = outermost_stride(&self.dims);
+    self.data.clear();
+    self.indcs.clear();
+    self.offs.clear();
+  }
+
+  /// Removes all stored elements equal to `DATA::default()`, e.g., explicit zeros.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let mut csl = CslVec::new([2, 3], vec![0, 2, 0], vec![0, 2, 1], vec![0, 2, 3])?;
+  /// csl.compress();
+  /// assert_eq!(csl, CslVec::new([2, 3], vec![2], vec![2], vec![0, 1, 1])?);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn compress(&mut self)
+  where
+    DATA: Default + PartialEq,
+    DS: Truncate<Input = usize>,
+    IS: AsMut<[usize]> + Truncate<Input = usize>,
+    OS: AsMut<[usize]>,
+  {
+    let zero = DATA::default();
+    self.retain(|_, value| value != &zero);
+  }
+
+  /// See [`CslLineConstructor`](CslLineConstructor) for more information.
+  #[inline]
+  pub fn constructor(&mut self) -> crate::Result<CslLineConstructor<'_, DS, IS, OS, D>>
+  where
+    DS: Push<Input = DATA>,
+    IS: Push<Input = usize>,
+    OS: Push<Input = usize>,
+  {
+    CslLineConstructor::new(self)
+  }
+
+  /// Mutable version of [`data`](#method.data).
+  #[inline]
+  pub fn data_mut(&mut self) -> &mut [DATA] {
+    self.data.as_mut()
+  }
+
+  /// Gets the entry of the position determined by `indcs` for in-place manipulation. See
+  /// [`CslEntry`] for more information.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{csl::CslError, fixtures::csl_vec_4};
+  /// let mut csl = csl_vec_4();
+  /// assert_eq!(csl.entry([1, 0, 2, 2])?.or_insert(0)?, &mut 9);
+  /// assert_eq!(csl.entry([1, 0, 2, 3])?.or_insert(10)?, &mut 10);
+  /// assert_eq!(csl.value([1, 0, 2, 3]), Some(&10));
+  /// let err = CslError::IndcsGreaterThanEqualDimLength { dim_len: 5, idx: 5 };
+  /// assert_eq!(csl.entry([1, 0, 2, 5]).err(), Some(ndsparse::Error::Csl(err)));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn entry(&mut self, indcs: [usize; D]) -> crate::Result<CslEntry<'_, DS, IS, OS, D>> {
+    CslEntry::new(self, indcs)
+  }
+
+  /// Mutable version of [`line`](#method.line).
+  #[inline]
+  pub fn line_mut(&mut self, indcs: [usize; D]) -> Option<CslMut<'_, DATA, 1>> {
+    line_mut(self, indcs)
+  }
+
+  /// Calls `cb` with the mutable data and the innermost indices of every line, in order, which
+  /// allows per-line transformations like normalizations or a sparse softmax.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let mut csl = CslVec::new([2, 3], vec![1.0, 1.0, 5.0], vec![0, 2, 1], vec![0, 2, 3])?;
+  /// csl.map_lines_in_place(|data, _| {
+  ///   let max = data.iter().copied().fold(f64::MIN, f64::max);
+  ///   let sum: f64 = data.iter().map(|value| (value - max).exp()).sum();
+  ///   data.iter_mut().for_each(|value| *value = (*value - max).exp() / sum);
+  /// });
+  /// assert_eq!(csl.data(), &[0.5, 0.5, 1.0]);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn map_lines_in_place<F>(&mut self, mut cb: F)
+  where
+    F: FnMut(&mut [DATA], &[usize]),
+  {
+    let data = self.data.as_mut();
+    let indcs = self.indcs.as_ref();
+    for range in line_ranges(self.offs.as_ref()) {
+      if let (Some(line_data), Some(line_indcs)) = (data.get_mut(range.clone()), indcs.get(range)) {
+        cb(line_data, line_indcs);
+      }
+    }
+  }
+
+  /// Mutable version of [`outermost_chunks`](#method.outermost_chunks).
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let mut csl = csl_array_4();
+  /// for mut chunk in csl.outermost_chunks_mut(1)? {
+  ///   chunk.data_mut().reverse();
+  /// }
+  /// assert_eq!(csl.data(), &[8, 7, 6, 5, 4, 3, 2, 1, 9]);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn outermost_chunks_mut(
+    &mut self,
+    chunk_size: usize,
+  ) -> crate::Result<CslLineIterMut<'_, DATA, D>> {
+    CslLineIterMut::new(
+      chunk_size,
+      self.dims,
+      self.outermost_stride,
+      self.data.as_mut(),
+      self.indcs.as_ref(),
+      self.offs.as_ref(),
+    )
+  }
+
+  /// Mutable version of [`outermost_chunks_rayon`](#method.outermost_chunks_rayon).
+  #[cfg(feature = "with-rayon")]
+  #[inline]
+  pub fn outermost_chunks_rayon_mut(
+    &mut self,
+    chunk_size: usize,
+  ) -> crate::Result<crate::ParallelIteratorWrapper<CslLineIterMut<'_, DATA, D>>> {
+    Ok(crate::ParallelIteratorWrapper(self.outermost_chunks_mut(chunk_size)?))
+  }
+
+  /// Mutable version of [`outermost_line_iter`](#method.outermost_line_iter).
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let mut csl = csl_array_4();
+  /// let mut iter = csl.outermost_line_iter_mut()?;
+  /// // Lines taken from both ends are disjoint and can be modified at the same time
+  /// let (mut front, mut back) = (iter.next().unwrap(), iter.next_back().unwrap());
+  /// assert!(iter.next().is_none());
+  /// front.data_mut().iter_mut().for_each(|value| *value *= 10);
+  /// back.data_mut().iter_mut().for_each(|value| *value = 0);
+  /// assert_eq!(csl.data(), &[10, 20, 30, 40, 50, 60, 70, 80, 0]);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn outermost_line_iter_mut(&mut self) -> crate::Result<CslLineIterMut<'_, DATA, D>> {
+    let (data, indcs, offs) = (self.data.as_mut(), self.indcs.as_ref(), self.offs.as_ref());
+    CslLineIterMut::new(1, self.dims, self.outermost_stride, data, indcs, offs)
+  }
+
+  /// Mutable version of [`outermost_line_rayon_iter`](#method.outermost_line_rayon_iter).
+  #[cfg(feature = "with-rayon")]
+  #[inline]
+  pub fn outermost_line_rayon_iter_mut(
+    &mut self,
+  ) -> crate::Result<crate::ParallelIteratorWrapper<CslLineIterMut<'_, DATA, D>>> {
+    Ok(crate::ParallelIteratorWrapper(self.outermost_line_iter_mut()?))
+  }
+
+  /// Appends `block`, a structure with one dimension less, as a new outermost index. If `self`
+  /// is empty, its inner dimensions are taken from `block`.
+  ///
+  /// If any error occurs, e.g., the storages of a fixed-capacity structure are full, all pushed
+  /// items are removed and the structure is left untouched.
+  ///
+  /// # Arguments
+  ///
+  /// * `block`: Structure with the same dimensions of `self`, excluding the outermost one
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::{CslRef, CslVec};
+  /// let mut csl = CslVec::<i32, 3>::default();
+  /// csl.push_outermost_block(&CslRef::new([2, 3], &[1, 2][..], &[0, 2][..], &[0, 1, 2][..])?)?;
+  /// csl.push_outermost_block(&CslRef::new([2, 3], &[3][..], &[1][..], &[0, 0, 1][..])?)?;
+  /// assert_eq!(csl, CslVec::new([2, 2, 3], vec![1, 2, 3], vec![0, 2, 1], vec![0, 1, 2, 2, 3])?);
+  /// let wrong_dims = CslRef::new([2, 4], &[][..], &[][..], &[0, 0, 0][..])?;
+  /// assert!(csl.push_outermost_block(&wrong_dims).is_err());
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn push_outermost_block<const TD: usize>(
+    &mut self,
+    block: &CslRef<'_, DATA, TD>,
+  ) -> crate::Result<()>
+  where
+    DATA: Clone,
+    DS: Push<Input = DATA> + Truncate<Input = usize>,
+    IS: Push<Input = usize> + Truncate<Input = usize>,
+    OS: Push<Input = usize> + Truncate<Input = usize>,
+  {
+    push_outermost_block(self, block)
+  }
+
+  /// Keeps only the elements for which `cb` returns `true`, in place. Offsets are adjusted
+  /// accordingly.
+  ///
+  /// # Arguments
+  ///
+  /// * `cb`: Callback that receives the indices of all dimensions and the value of an element
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::fixtures::csl_vec_4;
   /// let mut csl = csl_vec_4();
-  /// csl.clear();
-  /// assert_eq!(csl, CslVec::default());
+  /// csl.retain(|indcs, value| indcs[0] == 0 && value % 2 == 1);
+  /// assert_eq!(csl.data(), &[1, 3, 5, 7]);
+  /// assert_eq!(csl.value([0, 2, 0, 2]), Some(&7));
+  /// assert_eq!(csl.value([1, 0, 2, 2]), None);
   /// ```
   #[inline]
-  pub fn clear(&mut self)
+  pub fn retain<F>(&mut self, cb: F)
   where
-    DS: Clear,
-    IS: Clear,
-    OS: Clear,
+    DS: Truncate<Input = usize>,
+    F: FnMut([usize; D], &DATA) -> bool,
+    IS: AsMut<[usize]> + Truncate<Input = usize>,
+    OS: AsMut<[usize]>,
   {
-    self.dims = cl_traits::default_array();
-    let _ = self.data.clear();
-    let _ = self.indcs.clear();
-    let _ = self.offs.clear();
+    retain(self, cb)
   }
 
-  /// See [`CslLineConstructor`](CslLineConstructor) for more information.
+  /// Reverses the order of the outermost dimension in place, i.e., the lines of the outermost
+  /// index `idx` are moved to the outermost index `dims[0] - 1 - idx`.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::csl::CslArray;
+  /// let mut csl = CslArray::new([3, 3], [1, 2, 3], [0, 1, 2], [0, 1, 1, 3]).unwrap();
+  /// csl.reverse_outermost();
+  /// assert_eq!(csl, CslArray::new([3, 3], [2, 3, 1], [1, 2, 0], [0, 2, 2, 3]).unwrap());
+  /// ```
   #[inline]
-  pub fn constructor(&mut self) -> crate::Result<CslLineConstructor<'_, DS, IS, OS, D>>
+  pub fn reverse_outermost(&mut self)
   where
-    DS: Push<Input = DATA>,
-    IS: Push<Input = usize>,
-    OS: Push<Input = usize>,
+    IS: AsMut<[usize]>,
+    OS: AsMut<[usize]>,
   {
-    CslLineConstructor::new(self)
+    reverse_outermost(self)
   }
 
-  /// Mutable version of [`data`](#method.data).
+  /// Mutable version of [`sub_dim`](#method.sub_dim).
   #[inline]
-  pub fn data_mut(&mut self) -> &mut [DATA] {
-    self.data.as_mut()
+  pub fn sub_dim_mut<const TD: usize>(
+    &mut self,
+    range: Range<usize>,
+  ) -> Option<CslMut<'_, DATA, TD>> {
+    sub_dim_mut(self, range)
   }
 
-  /// Mutable version of [`line`](#method.line).
+  /// Swaps the lines of the outermost indices `a` and `b` in place, moving their data and
+  /// indices segments and adjusting all offsets in between. Useful for pivoting without
+  /// allocating a whole permuted copy like [`permute_outermost`](#method.permute_outermost).
+  ///
+  /// # Arguments
+  ///
+  /// * `a`: First outermost index
+  /// * `b`: Second outermost index
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{csl::CslError, fixtures::csl_vec_4};
+  /// let mut csl = csl_vec_4();
+  /// csl.swap_outermost_lines(0, 1).unwrap();
+  /// assert_eq!(Ok(csl.clone()), csl_vec_4().permute_outermost(&[1, 0]));
+  /// assert_eq!(
+  ///   csl.swap_outermost_lines(0, 2),
+  ///   Err(CslError::IndcsGreaterThanEqualDimLength { dim_len: 2, idx: 2 }.into())
+  /// );
+  /// ```
   #[inline]
-  pub fn line_mut(&mut self, indcs: [usize; D]) -> Option<CslMut<'_, DATA, 1>> {
-    line_mut(self, indcs)
+  pub fn swap_outermost_lines(&mut self, a: usize, b: usize) -> crate::Result<()>
+  where
+    IS: AsMut<[usize]>,
+    OS: AsMut<[usize]>,
+  {
+    swap_outermost_lines(self, a, b)
   }
 
-  /// Mutable version of [`outermost_line_iter`](#method.outermost_line_iter).
+  /// Intra-swap a single data value.
+  ///
+  /// # Arguments
+  ///
+  /// * `a`: First set of indices
+  /// * `b`: Second set of indices
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::fixtures::csl_vec_4;
+  /// let mut csl = csl_vec_4();
+  /// csl.swap_value([0, 0, 0, 0], [1, 0, 2, 2]);
+  /// assert_eq!(csl.data(), &[9, 2, 3, 4, 5, 6, 7, 8, 1]);
+  /// ```
   #[inline]
-  pub fn outermost_line_iter_mut(&mut self) -> crate::Result<CslLineIterMut<'_, DATA, D>> {
-    CslLineIterMut::new(self.dims, self.data.as_mut(), self.indcs.as_ref(), self.offs.as_ref())
+  pub fn swap_value(&mut self, a: [usize; D], b: [usize; D]) -> bool {
+    if let Some(a_idx) = data_idx(self, a) {
+      if let Some(b_idx) = data_idx(self, b) {
+        self.data.as_mut().swap(a_idx, b_idx);
+        return true;
+      }
+    }
+    false
   }
 
-  /// Mutable version of [`outermost_line_rayon_iter`](#method.outermost_line_rayon_iter).
+  /// Shrinks all outer dimensions to their last non-empty line and recomputes the offsets.
+  /// The innermost dimension and the stored elements are untouched. See
+  /// [`effective_dims`](#method.effective_dims).
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let mut csl = CslVec::new([2, 3, 2], vec![1, 2], vec![1, 0], vec![0, 1, 1, 1, 1, 2, 2])?;
+  /// csl.trim_trailing_empty_lines();
+  /// assert_eq!(csl, CslVec::new([2, 2, 2], vec![1, 2], vec![1, 0], vec![0, 1, 1, 1, 2])?);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn trim_trailing_empty_lines(&mut self)
+  where
+    OS: AsMut<[usize]> + Truncate<Input = usize>,
+  {
+    trim_trailing_empty_lines(self)
+  }
+
+  /// Truncates the structure to the point `indcs`, i.e., removes every element that is equal or
+  /// greater than `indcs` in lexicographic order. Dimensions are not modified, which means that
+  /// all lines after the point are kept but become empty.
+  ///
+  /// For the per-axis alternative that also shrinks dimensions, see
+  /// [`truncate_outermost`](#method.truncate_outermost).
+  ///
+  /// # Arguments
+  ///
+  /// * `indcs`: Indices of all dimensions of the first removed position
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{
+  ///   csl::{CslError, CslVec},
+  ///   fixtures::csl_vec_4,
+  /// };
+  /// let mut csl = csl_vec_4();
+  /// csl.truncate([0, 0, 3, 0]).unwrap();
+  /// assert_eq!(
+  ///   Ok(csl.clone()),
+  ///   CslVec::new([2, 3, 4, 5], vec![1, 2, 3], vec![0, 3, 1], {
+  ///     let mut offs = vec![0, 2, 3];
+  ///     offs.resize(25, 3);
+  ///     offs
+  ///   })
+  /// );
+  /// csl.truncate([0, 0, 0, 3]).unwrap();
+  /// assert_eq!(csl.data(), &[1]);
+  /// assert_eq!(
+  ///   csl.truncate([0, 3, 0, 0]),
+  ///   Err(CslError::IndcsGreaterThanEqualDimLength { dim_len: 3, idx: 3 }.into())
+  /// );
+  /// ```
+  #[inline]
+  pub fn truncate(&mut self, indcs: [usize; D]) -> crate::Result<()>
+  where
+    DS: Truncate<Input = usize>,
+    IS: Truncate<Input = usize>,
+    OS: AsMut<[usize]>,
+  {
+    truncate(self, indcs)
+  }
+
+  /// Keeps only the first `len` indices of the outermost dimension and discards the rest,
+  /// shrinking the outermost dimension accordingly. Has no effect if `len` is equal or greater
+  /// than the outermost dimension length, just like `Vec::truncate`.
+  ///
+  /// # Arguments
+  ///
+  /// * `len`: New length of the outermost dimension
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{csl::CslVec, fixtures::csl_vec_4};
+  /// let mut csl = csl_vec_4();
+  /// csl.truncate_outermost(1);
+  /// assert_eq!(
+  ///   Ok(csl),
+  ///   CslVec::new(
+  ///     [1, 3, 4, 5],
+  ///     vec![1, 2, 3, 4, 5, 6, 7, 8],
+  ///     vec![0, 3, 1, 3, 4, 2, 2, 4],
+  ///     vec![0, 2, 3, 3, 5, 6, 6, 6, 6, 7, 8, 8, 8]
+  ///   )
+  /// );
+  /// ```
+  #[inline]
+  pub fn truncate_outermost(&mut self, len: usize)
+  where
+    DS: Truncate<Input = usize>,
+    IS: Truncate<Input = usize>,
+    OS: AsMut<[usize]> + Truncate<Input = usize>,
+  {
+    truncate_outermost(self, len)
+  }
+
+  /// Mutable version of [`try_line`](#method.try_line).
+  #[inline]
+  pub fn try_line_mut(&mut self, indcs: [usize; D]) -> crate::Result<CslMut<'_, DATA, 1>> {
+    try_line_mut(self, indcs)
+  }
+
+  /// Mutable version of [`try_sub_dim`](#method.try_sub_dim).
+  #[inline]
+  pub fn try_sub_dim_mut<const TD: usize>(
+    &mut self,
+    range: Range<usize>,
+  ) -> crate::Result<CslMut<'_, DATA, TD>> {
+    try_sub_dim_mut(self, range)
+  }
+
+  /// Mutable version of [`value`](#method.value).
+  #[inline]
+  pub fn value_mut(&mut self, indcs: [usize; D]) -> Option<&mut DATA> {
+    let idx = data_idx(self, indcs)?;
+    self.data.as_mut().get_mut(idx)
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<DATA, DS, IS, OS> Csl<DS, IS, OS, 3>
+where
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  /// Treats the structure as a batch of matrices, where the outermost dimension is the batch,
+  /// and multiplies each matrix by its own dense vector.
+  ///
+  /// # Arguments
+  ///
+  /// * `vectors`: All vectors concatenated in batch order
+  /// * `vec_len`: Length of each vector, which must be equal to the innermost dimension
+  ///
+  /// The returned vector contains the rows of all results concatenated in batch order.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// use num_bigint::BigUint;
+  /// // [[[1, 0], [0, 2]], [[0, 3], [4, 0]]]
+  /// let csl = CslVec::new([2, 2, 2], vec![1, 2, 3, 4], vec![0, 1, 1, 0], vec![0, 1, 2, 3, 4])?;
+  /// assert_eq!(csl.batch_mat_vec_mul(&[1, 1, 2, 3], 2)?, vec![1, 2, 9, 8]);
+  /// let big = CslVec::new([1, 1, 1], vec![BigUint::from(u64::MAX)], vec![0], vec![0, 1])?;
+  /// let rslt = big.batch_mat_vec_mul(&[BigUint::from(u64::MAX)], 1)?;
+  /// assert_eq!(rslt, vec![BigUint::from(u64::MAX) * u64::MAX]);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn batch_mat_vec_mul(&self, vectors: &[DATA], vec_len: usize) -> crate::Result<Vec<DATA>>
+  where
+    DATA: Add<Output = DATA> + Clone + Default + Mul<Output = DATA>,
+  {
+    let (rows, mut rslt) = batch_mat_vec_mul_init(&self.dims, vectors.len(), vec_len)?;
+    if rows == 0 {
+      return Ok(rslt);
+    }
+    let csl: CslRef<'_, DATA, 3> = self.into();
+    for (batch, out) in rslt.chunks_mut(rows).enumerate() {
+      batch_mat_vec_mul_matrix(batch, &csl, out, vectors);
+    }
+    Ok(rslt)
+  }
+
+  /// Parallel version of [`batch_mat_vec_mul`](#method.batch_mat_vec_mul) using `rayon`, where
+  /// each matrix of the batch is a unit of work.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-rayon", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-rayon"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let csl = CslVec::new([2, 2, 2], vec![1, 2, 3, 4], vec![0, 1, 1, 0], vec![0, 1, 2, 3, 4])?;
+  /// assert_eq!(csl.par_batch_mat_vec_mul(&[1, 1, 2, 3], 2)?, vec![1, 2, 9, 8]);
+  /// # Ok(()) }
+  /// ```
   #[cfg(feature = "with-rayon")]
   #[inline]
-  pub fn outermost_line_rayon_iter_mut(
-    &mut self,
-  ) -> crate::Result<crate::ParallelIteratorWrapper<CslLineIterMut<'_, DATA, D>>> {
-    Ok(crate::ParallelIteratorWrapper(self.outermost_line_iter_mut()?))
+  pub fn par_batch_mat_vec_mul(&self, vectors: &[DATA], vec_len: usize) -> crate::Result<Vec<DATA>>
+  where
+    DATA: Add<Output = DATA> + Clone + Default + Mul<Output = DATA> + Send + Sync,
+  {
+    use rayon::prelude::*;
+    let (rows, mut rslt) = batch_mat_vec_mul_init(&self.dims, vectors.len(), vec_len)?;
+    if rows == 0 {
+      return Ok(rslt);
+    }
+    let csl: CslRef<'_, DATA, 3> = self.into();
+    rslt.par_chunks_mut(rows).enumerate().for_each(|(batch, out)| {
+      batch_mat_vec_mul_matrix(batch, &csl, out, vectors);
+    });
+    Ok(rslt)
+  }
+}
+
+#[cfg(feature = "with-sprs")]
+impl<DATA, DS, IS, OS> Csl<DS, IS, OS, 2>
+where
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  /// Zero-copy CSR view of a 2D structure, where the outermost dimension represents rows.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-sprs", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-sprs"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslArray;
+  /// let csl = CslArray::new([2, 3], [1, 2, 3], [0, 2, 1], [0, 2, 3])?;
+  /// let view = csl.to_sprs_view()?;
+  /// assert_eq!(view.get(0, 2), Some(&2));
+  /// assert_eq!(view.get(1, 1), Some(&3));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn to_sprs_view(&self) -> crate::Result<sprs::CsMatView<'_, DATA>> {
+    let [rows, cols] = self.dims;
+    let offs = self.offs();
+    let indptr = if offs.is_empty() { &[0][..] } else { offs };
+    sprs::CsMatView::try_new((rows, cols), indptr, self.indcs(), self.data())
+      .map_err(|_err| crate::Error::UnknownError)
+  }
+}
+
+#[cfg(feature = "with-sprs")]
+impl<DATA> CslVec<DATA, 2> {
+  /// Creates a valid instance from any `sprs` matrix. CSR matrices are moved without copying
+  /// while CSC matrices are converted first.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-sprs", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-sprs"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let mat = sprs::CsMat::new_csc((2, 3), vec![0, 1, 2, 3], vec![0, 1, 0], vec![1, 3, 2]);
+  /// let csl = CslVec::from_sprs(mat)?;
+  /// assert_eq!(csl.value([0, 2]), Some(&2));
+  /// assert_eq!(csl.value([1, 1]), Some(&3));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn from_sprs(mat: sprs::CsMat<DATA>) -> crate::Result<Self>
+  where
+    DATA: Clone + Default,
+  {
+    let mat = if mat.is_csc() { mat.to_other_storage() } else { mat };
+    let dims = [mat.rows(), mat.cols()];
+    let (offs, indcs, data) = mat.into_raw_storage();
+    Self::new(dims, data, indcs, offs)
   }
 
-  /// Mutable version of [`sub_dim`](#method.sub_dim).
+  /// Converts this instance into a CSR `sprs` matrix without copying.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-sprs", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-sprs"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let csl = CslVec::new([2, 3], vec![1, 2, 3], vec![0, 2, 1], vec![0, 2, 3])?;
+  /// let mat = csl.into_sprs()?;
+  /// assert_eq!(mat.get(0, 2), Some(&2));
+  /// # Ok(()) }
+  /// ```
   #[inline]
-  pub fn sub_dim_mut<const TD: usize>(
-    &mut self,
-    range: Range<usize>,
-  ) -> Option<CslMut<'_, DATA, TD>> {
-    sub_dim_mut(self, range)
+  pub fn into_sprs(self) -> crate::Result<sprs::CsMat<DATA>> {
+    let [rows, cols] = self.dims;
+    let mut offs = self.offs;
+    if offs.is_empty() {
+      offs.push(0);
+    }
+    sprs::CsMat::try_new((rows, cols), offs, self.indcs, self.data)
+      .map_err(|_err| crate::Error::UnknownError)
   }
+}
 
-  /// Intra-swap a single data value.
-  ///
-  /// # Arguments
-  ///
-  /// * `a`: First set of indices
-  /// * `b`: Second set of indices
+#[cfg(feature = "with-nalgebra")]
+impl<DATA, DS, IS, OS> Csl<DS, IS, OS, 2>
+where
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  /// Dense `nalgebra` matrix where the outermost dimension represents rows. Missing elements
+  /// are filled with `DATA::default()`.
   ///
   /// # Example
-  #[cfg_attr(feature = "alloc", doc = "```rust")]
-  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
-  /// use ndsparse::doc_tests::csl_vec_4;
-  /// let mut csl = csl_vec_4();
-  /// csl.swap_value([0, 0, 0, 0], [1, 0, 2, 2]);
-  /// assert_eq!(csl.data(), &[9, 2, 3, 4, 5, 6, 7, 8, 1]);
+  #[cfg_attr(feature = "with-nalgebra", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-nalgebra"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslArray;
+  /// let csl = CslArray::new([2, 3], [1, 2, 3], [0, 2, 1], [0, 2, 3])?;
+  /// assert_eq!(csl.to_dmatrix(), nalgebra::DMatrix::from_row_slice(2, 3, &[1, 0, 2, 0, 3, 0]));
+  /// # Ok(()) }
   /// ```
   #[inline]
-  pub fn swap_value(&mut self, a: [usize; D], b: [usize; D]) -> bool {
-    if let Some(a_idx) = data_idx(self, a) {
-      if let Some(b_idx) = data_idx(self, b) {
-        self.data.as_mut().swap(a_idx, b_idx);
-        return true;
+  pub fn to_dmatrix(&self) -> nalgebra::DMatrix<DATA>
+  where
+    DATA: Default + nalgebra::Scalar,
+  {
+    let [rows, cols] = self.dims;
+    let mut rslt = nalgebra::DMatrix::from_element(rows, cols, DATA::default());
+    let (data, indcs) = (self.data(), self.indcs());
+    for (row, range) in line_ranges(self.offs()).enumerate() {
+      let values = data.get(range.clone()).unwrap_or_default();
+      for (col, value) in indcs.get(range).unwrap_or_default().iter().zip(values) {
+        if let Some(elem) = rslt.get_mut((row, *col)) {
+          *elem = value.clone();
+        }
       }
     }
-    false
+    rslt
   }
 
-  /// Truncates all values in the exactly exclusive line defined by `indcs`. The last index is ignored.
+  /// `nalgebra_sparse` CSR matrix where the outermost dimension represents rows.
   ///
   /// # Example
-  #[cfg_attr(feature = "alloc", doc = "```rust")]
-  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
-  /// use ndsparse::{csl::CslVec, doc_tests::csl_vec_4};
-  /// let mut csl = csl_vec_4();
-  /// csl.truncate([0, 0, 3, 0]);
-  /// assert_eq!(
-  ///   Ok(csl),
-  ///   CslVec::new([0, 0, 4, 5], vec![1, 2, 3], vec![0, 3, 1], vec![0, 2, 3, 3, 3])
-  /// );
+  #[cfg_attr(feature = "with-nalgebra", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-nalgebra"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslArray;
+  /// let csl = CslArray::new([2, 3], [1, 2, 3], [0, 2, 1], [0, 2, 3])?;
+  /// let mat = csl.to_nalgebra_csr()?;
+  /// assert_eq!(mat.row_offsets(), &[0, 2, 3]);
+  /// assert_eq!(mat.col_indices(), &[0, 2, 1]);
+  /// # Ok(()) }
   /// ```
   #[inline]
-  pub fn truncate(&mut self, indcs: [usize; D])
+  pub fn to_nalgebra_csr(&self) -> crate::Result<nalgebra_sparse::CsrMatrix<DATA>>
   where
-    DS: Truncate<Input = usize>,
-    IS: Truncate<Input = usize>,
-    OS: AsMut<[usize]> + Truncate<Input = usize>,
+    DATA: Clone,
   {
-    let [offs_indcs, values] = if let Some(r) = line_offs(&self.dims, &indcs, self.offs.as_ref()) {
-      r
-    } else {
-      return;
-    };
-    let cut_point = values.start;
-    let _ = self.data.truncate(cut_point);
-    let _ = self.indcs.truncate(cut_point);
-    let _ = self.offs.truncate(offs_indcs.end);
-    let iter = indcs.iter().zip(self.dims.iter_mut()).rev().skip(1).rev();
-    iter.filter(|&(a, _)| *a == 0).for_each(|(_, b)| *b = 0);
-    let before_last = if let Some(rslt) = self.offs.as_ref().get(offs_indcs.end.saturating_sub(2)) {
-      *rslt
-    } else {
-      return;
-    };
-    if let Some(rslt) = self.offs.as_mut().get_mut(offs_indcs.end.saturating_sub(1)) {
-      *rslt = before_last;
+    let [rows, cols] = self.dims;
+    let mut row_offsets = rebased_offs(self.offs())?;
+    if row_offsets.is_empty() {
+      row_offsets.push(0);
     }
+    nalgebra_sparse::CsrMatrix::try_from_csr_data(
+      rows,
+      cols,
+      row_offsets,
+      self.indcs().to_vec(),
+      self.data().to_vec(),
+    )
+    .map_err(|_err| crate::Error::UnknownError)
   }
+}
 
-  /// Mutable version of [`value`](#method.value).
+#[cfg(feature = "with-nalgebra")]
+impl<DATA> CslVec<DATA, 2> {
+  /// Creates a valid instance from a `nalgebra_sparse` CSR matrix without copying.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "with-nalgebra", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-nalgebra"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let mat =
+  ///   nalgebra_sparse::CsrMatrix::try_from_csr_data(2, 3, vec![0, 2, 3], vec![0, 2, 1], vec![1, 2, 3])
+  ///     .unwrap();
+  /// let csl = CslVec::from_nalgebra_csr(mat)?;
+  /// assert_eq!(csl.value([1, 1]), Some(&3));
+  /// # Ok(()) }
+  /// ```
   #[inline]
-  pub fn value_mut(&mut self, indcs: [usize; D]) -> Option<&mut DATA> {
-    let idx = data_idx(self, indcs)?;
-    self.data.as_mut().get_mut(idx)
+  pub fn from_nalgebra_csr(mat: nalgebra_sparse::CsrMatrix<DATA>) -> crate::Result<Self> {
+    let dims = [mat.nrows(), mat.ncols()];
+    let (offs, indcs, data) = mat.disassemble();
+    Self::new(dims, data, indcs, offs)
   }
 }
 
@@ -573,11 +2710,162 @@ where
     F: FnMut(&mut R, [usize; D]) -> DATA,
     R: rand::Rng,
   {
-    let mut csl = Csl { dims, ..Default::default() };
+    let mut csl = Csl { dims, outermost_stride: outermost_stride(&dims), ..Default::default() };
     csl_rnd::CslRnd::new(&mut csl, nnz, rng)?.fill(cb)?;
     Self::new(csl.dims, csl.data, csl.indcs, csl.offs)
   }
 
+  /// Creates a new banded instance where, for every line, all innermost indices that are at
+  /// most `lower` positions before or `upper` positions after the index of the second innermost
+  /// dimension are stored. Values are created by `cb`.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  /// * `lower`: Number of stored sub-diagonals
+  /// * `upper`: Number of stored super-diagonals
+  /// * `rng`: `rand::Rng` trait
+  /// * `cb`: Callback to control data creation
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// use rand::{Rng, rngs::mock::StepRng};
+  /// let mut rng = StepRng::new(0, 1);
+  /// let csl: CslVec<u8, 2> = CslVec::new_random_banded([4, 4], 1, 0, &mut rng, |r, _| r.gen())?;
+  /// assert_eq!(csl.indcs(), &[0, 0, 1, 1, 2, 2, 3]);
+  /// assert_eq!(csl.offs(), &[0, 1, 3, 5, 7]);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn new_random_banded<F, R>(
+    dims: [usize; D],
+    lower: usize,
+    upper: usize,
+    rng: &mut R,
+    cb: F,
+  ) -> crate::Result<Self>
+  where
+    F: FnMut(&mut R, [usize; D]) -> DATA,
+    R: rand::Rng,
+  {
+    let mut csl = Csl { dims, outermost_stride: outermost_stride(&dims), ..Default::default() };
+    csl_rnd::fill_pattern(
+      &mut csl,
+      rng,
+      |_, row| {
+        let range = row.saturating_sub(lower)..row.saturating_add(upper).saturating_add(1);
+        let len = range.len();
+        (range, len)
+      },
+      cb,
+    )?;
+    Self::new(csl.dims, csl.data, csl.indcs, csl.offs)
+  }
+
+  /// Creates a new block diagonal instance made of square blocks of length `block_len` along the
+  /// two innermost dimensions, i.e., every element that belongs to a block is stored. Values are
+  /// created by `cb`.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  /// * `block_len`: Length of each block. Zero produces an instance without elements
+  /// * `rng`: `rand::Rng` trait
+  /// * `cb`: Callback to control data creation
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// use rand::{Rng, rngs::mock::StepRng};
+  /// let mut rng = StepRng::new(0, 1);
+  /// let csl: CslVec<u8, 2>;
+  /// csl = CslVec::new_random_block_diagonal([3, 3], 2, &mut rng, |r, _| r.gen())?;
+  /// assert_eq!(csl.indcs(), &[0, 1, 0, 1, 2]);
+  /// assert_eq!(csl.offs(), &[0, 2, 4, 5]);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn new_random_block_diagonal<F, R>(
+    dims: [usize; D],
+    block_len: usize,
+    rng: &mut R,
+    cb: F,
+  ) -> crate::Result<Self>
+  where
+    F: FnMut(&mut R, [usize; D]) -> DATA,
+    R: rand::Rng,
+  {
+    let mut csl = Csl { dims, outermost_stride: outermost_stride(&dims), ..Default::default() };
+    csl_rnd::fill_pattern(
+      &mut csl,
+      rng,
+      |_, row| {
+        if block_len == 0 {
+          return (0..0, 0);
+        }
+        let start = row / block_len * block_len;
+        (start..start.saturating_add(block_len), block_len)
+      },
+      cb,
+    )?;
+    Self::new(csl.dims, csl.data, csl.indcs, csl.offs)
+  }
+
+  /// Creates a new instance where the number of elements of each line is sampled from
+  /// `nnz_per_line_distribution` and the innermost indices of each line are uniformly chosen.
+  /// Heavy-tailed distributions, like Zipf or Pareto, produce the skewed patterns commonly found
+  /// in real-world graphs and matrices. Samples greater than the innermost dimension length are
+  /// clamped.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  /// * `nnz_per_line_distribution`: Distribution of the number of elements of each line
+  /// * `rng`: `rand::Rng` trait
+  /// * `cb`: Callback to control data creation
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// use rand::{distributions::Uniform, Rng, rngs::mock::StepRng};
+  /// let mut rng = StepRng::new(0, 1);
+  /// let dist = Uniform::from(2..3);
+  /// let csl: CslVec<u8, 3>;
+  /// csl = CslVec::new_random_power_law([2, 3, 4], dist, &mut rng, |r, _| r.gen())?;
+  /// assert_eq!(csl.nnz(), 12);
+  /// assert!(csl.offs().windows(2).all(|w| w[1] - w[0] == 2));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn new_random_power_law<F, P, R>(
+    dims: [usize; D],
+    nnz_per_line_distribution: P,
+    rng: &mut R,
+    cb: F,
+  ) -> crate::Result<Self>
+  where
+    F: FnMut(&mut R, [usize; D]) -> DATA,
+    P: rand::distributions::Distribution<usize>,
+    R: rand::Rng,
+  {
+    let innermost_len = dims.last().copied().unwrap_or_default();
+    let mut csl = Csl { dims, outermost_stride: outermost_stride(&dims), ..Default::default() };
+    csl_rnd::fill_pattern(
+      &mut csl,
+      rng,
+      |rng, _| (0..innermost_len, nnz_per_line_distribution.sample(rng)),
+      cb,
+    )?;
+    Self::new(csl.dims, csl.data, csl.indcs, csl.offs)
+  }
+
   /// Creates a new random and valid instance.
   ///
   /// # Arguments
@@ -606,10 +2894,122 @@ where
     rand::distributions::Standard: rand::distributions::Distribution<DATA>,
   {
     let dims = crate::utils::valid_random_dims(rng, upper_bound);
-    let max_nnz = max_nnz(&dims);
+    let max_nnz = crate::utils::max_nnz(&dims);
     let nnz = if max_nnz == 0 { 0 } else { rng.gen_range(0..max_nnz) };
     Self::new_controlled_random_rand(dims, nnz, rng, |rng, _| rng.gen())
   }
+
+  /// Creates a new instance where the number of elements of each line and the innermost indices
+  /// are sampled from the distributions of `config`. Values are created by `cb`.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  /// * `config`: Distributions of the number of elements of each line and of the innermost
+  /// indices
+  /// * `rng`: `rand::Rng` trait
+  /// * `cb`: Callback to control data creation
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::{CslVec, LineNnzDistribution, RandomConfig};
+  /// use rand::{distributions::Uniform, Rng, rngs::mock::StepRng};
+  /// let mut rng = StepRng::new(0, 1);
+  /// let config = RandomConfig::new(LineNnzDistribution::uniform(0..3)?, Uniform::from(0..2));
+  /// let csl: CslVec<u8, 2>;
+  /// csl = CslVec::new_random_with_config([3, 50], &config, &mut rng, |r, _| r.gen())?;
+  /// assert!(csl.indcs().iter().all(|&idx| idx < 2));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn new_random_with_config<F, I, N, R>(
+    dims: [usize; D],
+    config: &RandomConfig<I, N>,
+    rng: &mut R,
+    cb: F,
+  ) -> crate::Result<Self>
+  where
+    F: FnMut(&mut R, [usize; D]) -> DATA,
+    I: rand::distributions::Distribution<usize>,
+    N: rand::distributions::Distribution<usize>,
+    R: rand::Rng,
+  {
+    let mut csl = Csl { dims, outermost_stride: outermost_stride(&dims), ..Default::default() };
+    csl_rnd::fill_config(&mut csl, rng, config, cb)?;
+    Self::new(csl.dims, csl.data, csl.indcs, csl.offs)
+  }
+
+  /// Creates a new random and valid instance with up to `nnz` elements that is fully determined
+  /// by `seed`, which is useful for reproducible fixtures. Sampled ranges depend on the width of
+  /// `usize`, therefore, the same seed only produces the same instance on platforms with the
+  /// same pointer width.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  /// * `nnz`: Number of Non-Zero elements
+  /// * `seed`: Seed of the internal generator
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::csl::CslVec;
+  /// let a = CslVec::<u8, 3>::new_random_with_seed([1, 2, 3], 4, 42);
+  /// let b = CslVec::<u8, 3>::new_random_with_seed([1, 2, 3], 4, 42);
+  /// assert!(a.is_ok());
+  /// assert_eq!(a, b);
+  /// ```
+  #[inline]
+  pub fn new_random_with_seed(dims: [usize; D], nnz: usize, seed: u64) -> crate::Result<Self>
+  where
+    rand::distributions::Standard: rand::distributions::Distribution<DATA>,
+  {
+    use rand::Rng;
+    let mut rng = crate::utils::XorShift64::new(seed);
+    Self::new_controlled_random_rand(dims, nnz, &mut rng, |rng, _| rng.gen())
+  }
+}
+
+#[cfg(all(feature = "with-rand", feature = "with-rayon"))]
+impl<DATA, const D: usize> CslVec<DATA, D>
+where
+  DATA: Send,
+{
+  /// Parallel version of [`new_random_with_seed`](#method.new_random_with_seed) for huge
+  /// instances, e.g., benchmark fixtures with hundreds of millions of elements.
+  ///
+  /// The number of elements of every line is known upfront, which allows the offsets to be
+  /// prefix-summed before the indices and the data of all lines are generated in parallel.
+  /// Elements are evenly distributed, i.e., each line stores `nnz / lines` or `nnz / lines + 1`
+  /// elements, and the outcome is the same regardless of the number of threads.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  /// * `nnz`: Number of Non-Zero elements
+  /// * `seed`: Seed of the internal generators
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let csl = CslVec::<u8, 3>::par_new_random_with_seed([2, 3, 4], 14, 42)?;
+  /// assert_eq!(csl.nnz(), 14);
+  /// assert!(csl.offs().windows(2).all(|w| w[1] - w[0] == 2 || w[1] - w[0] == 3));
+  /// assert_eq!(csl, CslVec::par_new_random_with_seed([2, 3, 4], 14, 42)?);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn par_new_random_with_seed(dims: [usize; D], nnz: usize, seed: u64) -> crate::Result<Self>
+  where
+    rand::distributions::Standard: rand::distributions::Distribution<DATA>,
+  {
+    let csl = csl_rnd::par_fill_with_seed(dims, nnz, seed)?;
+    Self::new(csl.dims, csl.data, csl.indcs, csl.offs)
+  }
 }
 
 impl<DS, IS, OS, const D: usize> Default for Csl<DS, IS, OS, D>
@@ -620,11 +3020,101 @@ where
 {
   #[inline]
   fn default() -> Self {
+    let dims = cl_traits::default_array();
     Self {
       data: Default::default(),
-      dims: cl_traits::default_array(),
+      dims,
       indcs: Default::default(),
       offs: Default::default(),
+      outermost_stride: outermost_stride(&dims),
+    }
+  }
+}
+
+/// Summary with the dimensions and the number of stored elements, which avoids sending the whole
+/// structure through the wire.
+#[cfg(feature = "with-defmt")]
+impl<DATA, DS, IS, OS, const D: usize> defmt::Format for Csl<DS, IS, OS, D>
+where
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+{
+  #[inline]
+  fn format(&self, fmt: defmt::Formatter<'_>) {
+    defmt::write!(fmt, "Csl {{ dims: {}, nnz: {} }}", self.dims, self.data.as_ref().len());
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<DATA, const D: usize, const N: usize, const O: usize> From<CslArray<DATA, D, N, O>>
+  for CslVec<DATA, D>
+{
+  #[inline]
+  fn from(from: CslArray<DATA, D, N, O>) -> Self {
+    Self {
+      data: from.data.into(),
+      dims: from.dims,
+      indcs: from.indcs.into(),
+      offs: from.offs.into(),
+      outermost_stride: from.outermost_stride,
+    }
+  }
+}
+
+/// ```rust
+/// use ndsparse::{csl::CslRef, fixtures::csl_array_4};
+/// let csl = csl_array_4();
+/// let csl_ref = CslRef::from(&csl);
+/// assert_eq!(csl_ref.data(), csl.data());
+/// assert_eq!(csl_ref.line([1, 0, 2, 0]), csl.line([1, 0, 2, 0]));
+/// ```
+impl<'a, DATA, DS, IS, OS, const D: usize> From<&'a Csl<DS, IS, OS, D>> for CslRef<'a, DATA, D>
+where
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  #[inline]
+  fn from(from: &'a Csl<DS, IS, OS, D>) -> Self {
+    Self {
+      data: from.data.as_ref(),
+      dims: from.dims,
+      indcs: from.indcs.as_ref(),
+      offs: from.offs.as_ref(),
+      outermost_stride: from.outermost_stride,
     }
   }
 }
+
+/// The number of stored elements must be equal to `N` and the offsets length must be equal to
+/// `O`.
+///
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// # fn main() -> ndsparse::Result<()> {
+/// use core::convert::TryFrom;
+/// use ndsparse::csl::{CslArray, CslVec};
+/// let csl = CslVec::new([2, 3], vec![1, 2, 3], vec![0, 2, 1], vec![0, 2, 3])?;
+/// let array = CslArray::<_, 2, 3, 3>::try_from(csl.clone())?;
+/// assert_eq!(array.data(), &[1, 2, 3]);
+/// assert_eq!(CslVec::from(array), csl);
+/// assert!(CslArray::<_, 2, 4, 3>::try_from(csl).is_err());
+/// # Ok(()) }
+/// ```
+#[cfg(feature = "alloc")]
+impl<DATA, const D: usize, const N: usize, const O: usize> TryFrom<CslVec<DATA, D>>
+  for CslArray<DATA, D, N, O>
+{
+  type Error = crate::Error;
+
+  #[inline]
+  fn try_from(from: CslVec<DATA, D>) -> crate::Result<Self> {
+    let found = from.data.len();
+    let err = || CslError::InvalidArrayLength { expected: N, found };
+    let data = <[DATA; N]>::try_from(from.data).map_err(|_err| err())?;
+    let indcs = <[usize; N]>::try_from(from.indcs).map_err(|_err| err())?;
+    let found = from.offs.len();
+    let offs = <[usize; O]>::try_from(from.offs)
+      .map_err(|_err| CslError::InvalidArrayLength { expected: O, found })?;
+    Ok(Self { data, dims: from.dims, indcs, offs, outermost_stride: from.outermost_stride })
+  }
+}
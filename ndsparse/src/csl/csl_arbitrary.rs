@@ -0,0 +1,31 @@
+use crate::csl::{correct_offs_len, CslVec};
+use alloc::vec::Vec;
+use arbitrary::{Arbitrary, Unstructured};
+
+// Dimensions are generated first and then every line is filled by picking which innermost
+// indices are stored, which means that all generated instances are valid.
+impl<'a, DATA, const D: usize> Arbitrary<'a> for CslVec<DATA, D>
+where
+  DATA: Arbitrary<'a>,
+{
+  #[inline]
+  fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+    let dims = crate::utils::arbitrary_dims(u)?;
+    let lines = correct_offs_len(&dims).map_err(|_err| arbitrary::Error::IncorrectFormat)? - 1;
+    let innermost_len = dims.last().copied().unwrap_or_default();
+    let mut data = Vec::new();
+    let mut indcs = Vec::new();
+    let mut offs = Vec::with_capacity(lines.saturating_add(1));
+    offs.push(0);
+    for _ in 0..lines {
+      for innermost_idx in 0..innermost_len {
+        if bool::arbitrary(u)? {
+          data.push(DATA::arbitrary(u)?);
+          indcs.push(innermost_idx);
+        }
+      }
+      offs.push(data.len());
+    }
+    Self::new(dims, data, indcs, offs).map_err(|_err| arbitrary::Error::IncorrectFormat)
+  }
+}
@@ -0,0 +1,100 @@
+use crate::csl::{rebased_offs, CslError, CslVec};
+use alloc::{sync::Arc, vec::Vec};
+use arrow_array::{
+  types::UInt64Type, Array, ArrayRef, ArrowPrimitiveType, ListArray, PrimitiveArray, StructArray,
+};
+use arrow_buffer::OffsetBuffer;
+use arrow_schema::{DataType, Field};
+use core::convert::TryFrom;
+
+pub(crate) const DATA: &str = "data";
+pub(crate) const DIMS: &str = "dims";
+pub(crate) const INDCS: &str = "indcs";
+pub(crate) const OFFS: &str = "offs";
+
+#[inline]
+pub(crate) fn from_arrow<T, const D: usize>(
+  array: &StructArray,
+  row: usize,
+) -> crate::Result<CslVec<T::Native, D>>
+where
+  T: ArrowPrimitiveType,
+{
+  let err = || crate::Error::from(CslError::InvalidArrowArray);
+  let dims_vec = usize_values(array, DIMS, row).ok_or_else(err)?;
+  if dims_vec.len() != D {
+    return Err(err());
+  }
+  let dims = cl_traits::try_create_array(|idx| dims_vec.get(idx).copied().ok_or(()))
+    .map_err(|_err| err())?;
+  let data = primitive_values::<T>(array, DATA, row).ok_or_else(err)?.values().to_vec();
+  let indcs = usize_values(array, INDCS, row).ok_or_else(err)?;
+  let offs = usize_values(array, OFFS, row).ok_or_else(err)?;
+  CslVec::new(dims, data, indcs, offs)
+}
+
+#[inline]
+pub(crate) fn to_arrow<T>(
+  dims: &[usize],
+  data: &[T::Native],
+  indcs: &[usize],
+  offs: &[usize],
+) -> crate::Result<StructArray>
+where
+  T: ArrowPrimitiveType,
+{
+  let offs = rebased_offs(offs)?;
+  let to_u64 = |x: usize| x as u64;
+  Ok(StructArray::from(alloc::vec![
+    single_row_list(
+      DIMS,
+      PrimitiveArray::<UInt64Type>::from_iter_values(dims.iter().copied().map(to_u64))
+    ),
+    single_row_list(DATA, PrimitiveArray::<T>::from_iter_values(data.iter().copied())),
+    single_row_list(
+      INDCS,
+      PrimitiveArray::<UInt64Type>::from_iter_values(indcs.iter().copied().map(to_u64))
+    ),
+    single_row_list(
+      OFFS,
+      PrimitiveArray::<UInt64Type>::from_iter_values(offs.into_iter().map(to_u64))
+    ),
+  ]))
+}
+
+#[inline]
+fn primitive_values<T>(array: &StructArray, name: &str, row: usize) -> Option<PrimitiveArray<T>>
+where
+  T: ArrowPrimitiveType,
+{
+  let list = array.column_by_name(name)?.as_any().downcast_ref::<ListArray>()?;
+  if row >= list.len() || list.is_null(row) {
+    return None;
+  }
+  let values = list.value(row);
+  let primitive = values.as_any().downcast_ref::<PrimitiveArray<T>>()?;
+  if primitive.null_count() != 0 {
+    return None;
+  }
+  Some(primitive.clone())
+}
+
+#[inline]
+fn single_row_list<T>(name: &str, values: PrimitiveArray<T>) -> (Arc<Field>, ArrayRef)
+where
+  T: ArrowPrimitiveType,
+{
+  let item = Arc::new(Field::new("item", T::DATA_TYPE, false));
+  let offsets = OffsetBuffer::from_lengths([values.len()]);
+  let list = ListArray::new(Arc::clone(&item), offsets, Arc::new(values), None);
+  (Arc::new(Field::new(name, DataType::List(item), false)), Arc::new(list))
+}
+
+#[inline]
+fn usize_values(array: &StructArray, name: &str, row: usize) -> Option<Vec<usize>> {
+  primitive_values::<UInt64Type>(array, name, row)?
+    .values()
+    .iter()
+    .map(|&x| usize::try_from(x).ok())
+    .collect()
+}
@@ -0,0 +1,234 @@
+use crate::csl::{line_ranges, merge_lines, Csl, CslError, CslVec};
+use alloc::{vec, vec::Vec};
+use cl_traits::Storage;
+
+impl<DS, IS, OS, const D: usize> Csl<DS, IS, OS, D>
+where
+  DS: AsRef<[bool]> + Storage<Item = bool>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  /// Element-wise logical AND. Stored `false` values are treated as missing elements and only
+  /// `true` values are stored in the returned structure.
+  ///
+  /// # Arguments
+  ///
+  /// * `other`: Structure with the same dimensions
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let a = CslVec::new([2, 3], vec![true, true, false], vec![0, 2, 1], vec![0, 2, 3])?;
+  /// let b = CslVec::new([2, 3], vec![true, true], vec![2, 1], vec![0, 1, 2])?;
+  /// assert_eq!(a.and(&b)?, CslVec::new([2, 3], vec![true], vec![2], vec![0, 1, 1])?);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn and<DS2, IS2, OS2>(&self, other: &Csl<DS2, IS2, OS2, D>) -> crate::Result<CslVec<bool, D>>
+  where
+    DS2: AsRef<[bool]> + Storage<Item = bool>,
+    IS2: AsRef<[usize]>,
+    OS2: AsRef<[usize]>,
+  {
+    merge_lines(self, other, |a, b| logical(a, b, |a, b| a && b))
+  }
+
+  /// Element-wise logical OR. Stored `false` values are treated as missing elements and only
+  /// `true` values are stored in the returned structure.
+  ///
+  /// # Arguments
+  ///
+  /// * `other`: Structure with the same dimensions
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let a = CslVec::new([2, 3], vec![true, true, false], vec![0, 2, 1], vec![0, 2, 3])?;
+  /// let b = CslVec::new([2, 3], vec![true, true], vec![2, 1], vec![0, 1, 2])?;
+  /// assert_eq!(a.or(&b)?, CslVec::new([2, 3], vec![true; 3], vec![0, 2, 1], vec![0, 2, 3])?);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn or<DS2, IS2, OS2>(&self, other: &Csl<DS2, IS2, OS2, D>) -> crate::Result<CslVec<bool, D>>
+  where
+    DS2: AsRef<[bool]> + Storage<Item = bool>,
+    IS2: AsRef<[usize]>,
+    OS2: AsRef<[usize]>,
+  {
+    merge_lines(self, other, |a, b| logical(a, b, |a, b| a || b))
+  }
+
+  /// Element-wise logical XOR. Stored `false` values are treated as missing elements and only
+  /// `true` values are stored in the returned structure.
+  ///
+  /// # Arguments
+  ///
+  /// * `other`: Structure with the same dimensions
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let a = CslVec::new([2, 3], vec![true, true, false], vec![0, 2, 1], vec![0, 2, 3])?;
+  /// let b = CslVec::new([2, 3], vec![true, true], vec![2, 1], vec![0, 1, 2])?;
+  /// assert_eq!(a.xor(&b)?, CslVec::new([2, 3], vec![true; 2], vec![0, 1], vec![0, 1, 2])?);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn xor<DS2, IS2, OS2>(&self, other: &Csl<DS2, IS2, OS2, D>) -> crate::Result<CslVec<bool, D>>
+  where
+    DS2: AsRef<[bool]> + Storage<Item = bool>,
+    IS2: AsRef<[usize]>,
+    OS2: AsRef<[usize]>,
+  {
+    merge_lines(self, other, |a, b| logical(a, b, |a, b| a != b))
+  }
+}
+
+impl<DATA, DS, IS, OS> Csl<DS, IS, OS, 2>
+where
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  /// Boolean matrix product of the patterns of `self` and `other`, i.e., the element `[i, j]` is
+  /// stored if there is any `k` where both `self[i, k]` and `other[k, j]` are stored. Values are
+  /// ignored, use [`compress`](#method.compress) beforehand to discard explicit `false`s.
+  ///
+  /// # Arguments
+  ///
+  /// * `other`: Matrix whose number of rows is equal to the number of columns of `self`
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let a = CslVec::new([2, 3], vec![(), ()], vec![0, 2], vec![0, 2, 2])?;
+  /// let b = CslVec::new([3, 2], vec![(), ()], vec![1, 0], vec![0, 1, 1, 2])?;
+  /// assert_eq!(a.pattern_mul(&b)?, CslVec::new([2, 2], vec![(); 2], vec![0, 1], vec![0, 2, 2])?);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn pattern_mul<DATA2, DS2, IS2, OS2>(
+    &self,
+    other: &Csl<DS2, IS2, OS2, 2>,
+  ) -> crate::Result<CslVec<(), 2>>
+  where
+    DS2: AsRef<[DATA2]> + Storage<Item = DATA2>,
+    IS2: AsRef<[usize]>,
+    OS2: AsRef<[usize]>,
+  {
+    let [rows, inner] = self.dims;
+    let [other_rows, cols] = other.dims;
+    if inner != other_rows {
+      return Err(CslError::DiffDims.into());
+    }
+    let other_lines: Vec<_> = line_ranges(other.offs.as_ref()).collect();
+    let other_indcs = other.indcs.as_ref();
+    let mut marked = vec![false; cols];
+    let mut rslt =
+      CslVec { dims: [rows, cols], offs: vec![0], outermost_stride: Some(1), ..Default::default() };
+    for range in line_ranges(self.offs.as_ref()) {
+      let start = rslt.indcs.len();
+      for &k in self.indcs.as_ref().get(range).unwrap_or_default() {
+        let other_range = other_lines.get(k).cloned().unwrap_or_default();
+        for &col in other_indcs.get(other_range).unwrap_or_default() {
+          mark(&mut marked, &mut rslt.indcs, col);
+        }
+      }
+      finish_line(&mut marked, &mut rslt, start);
+    }
+    Ok(rslt)
+  }
+
+  /// Pattern of all vertices reachable through paths of at least one edge when `self` is seen as
+  /// the adjacency matrix of a directed graph, i.e., `[i, j]` is stored if `j` can be reached from
+  /// `i`. Values are ignored just like in [`pattern_mul`](#method.pattern_mul).
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// // 0 -> 1 -> 2
+  /// let csl = CslVec::new([3, 3], vec![(), ()], vec![1, 2], vec![0, 1, 2, 2])?;
+  /// assert_eq!(
+  ///   csl.transitive_closure()?,
+  ///   CslVec::new([3, 3], vec![(); 3], vec![1, 2, 2], vec![0, 2, 3, 3])?
+  /// );
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn transitive_closure(&self) -> crate::Result<CslVec<(), 2>> {
+    let [rows, cols] = self.dims;
+    if rows != cols {
+      return Err(CslError::DiffDims.into());
+    }
+    let lines: Vec<_> = line_ranges(self.offs.as_ref()).collect();
+    let indcs = self.indcs.as_ref();
+    let neighbors =
+      |vertex: usize| indcs.get(lines.get(vertex).cloned().unwrap_or_default()).unwrap_or_default();
+    let mut marked = vec![false; cols];
+    let outermost_stride = self.outermost_stride;
+    let mut rslt =
+      CslVec { dims: self.dims, offs: vec![0], outermost_stride, ..Default::default() };
+    for vertex in 0..lines.len() {
+      let start = rslt.indcs.len();
+      for &neighbor in neighbors(vertex) {
+        mark(&mut marked, &mut rslt.indcs, neighbor);
+      }
+      // The newly reached vertices of this line double as the traversal stack
+      let mut curr = start;
+      while let Some(&reached) = rslt.indcs.get(curr) {
+        for &neighbor in neighbors(reached) {
+          mark(&mut marked, &mut rslt.indcs, neighbor);
+        }
+        curr += 1;
+      }
+      finish_line(&mut marked, &mut rslt, start);
+    }
+    Ok(rslt)
+  }
+}
+
+// Sorts the indices of the line that starts at `start`, clears their marks and closes the line.
+#[inline]
+fn finish_line(marked: &mut [bool], rslt: &mut CslVec<(), 2>, start: usize) {
+  let line = rslt.indcs.get_mut(start..).unwrap_or_default();
+  line.sort_unstable();
+  for &idx in line.iter() {
+    if let Some(r) = marked.get_mut(idx) {
+      *r = false;
+    }
+  }
+  rslt.data.resize(rslt.indcs.len(), ());
+  rslt.offs.push(rslt.indcs.len());
+}
+
+#[inline]
+fn logical<F>(a: Option<&bool>, b: Option<&bool>, cb: F) -> Option<bool>
+where
+  F: FnOnce(bool, bool) -> bool,
+{
+  if cb(a.copied().unwrap_or_default(), b.copied().unwrap_or_default()) {
+    Some(true)
+  } else {
+    None
+  }
+}
+
+#[inline]
+fn mark(marked: &mut [bool], indcs: &mut Vec<usize>, idx: usize) {
+  if let Some(r) = marked.get_mut(idx) {
+    if !*r {
+      *r = true;
+      indcs.push(idx);
+    }
+  }
+}
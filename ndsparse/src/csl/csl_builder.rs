@@ -0,0 +1,181 @@
+use crate::csl::{
+  correct_offs_len, has_innermost_dims_zero, outermost_stride, CslError, CslLineConstructorError,
+  CslVec,
+};
+use alloc::vec::Vec;
+use core::{convert::TryFrom, marker::PhantomData};
+
+/// Marker of a [`CslBuilder`] whose dimensions weren't declared yet.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NoDims {}
+
+/// Marker of a [`CslBuilder`] whose dimensions are already declared.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WithDims {}
+
+/// Typestate builder that only accepts lines after the dimensions are declared and returns a
+/// structure that doesn't need to be validated again.
+///
+/// Every pushed line is checked when it is pushed and the number of lines is checked by
+/// [`finish`](#method.finish), which means that all invariants of [`Csl`](crate::csl::Csl) are
+/// upheld without visiting the elements a second time.
+///
+/// # Types
+///
+/// * `DATA`: Data
+/// * `S`: State, either [`NoDims`] or [`WithDims`]
+/// * `D`: Number of dimensions
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::csl::{CslBuilder, CslVec};
+/// let csl = CslBuilder::dims([2, 3])?
+///   .push_line([(0, 1), (2, 2)].iter().copied())?
+///   .lines(vec![vec![(1, 3)]])?
+///   .finish()?;
+/// assert_eq!(csl, CslVec::new([2, 3], vec![1, 2, 3], vec![0, 2, 1], vec![0, 2, 3])?);
+/// # Ok(()) }
+/// ```
+///
+/// Lines can't be pushed before the dimensions are declared.
+///
+/// ```compile_fail,E0599
+/// use ndsparse::csl::{CslBuilder, NoDims};
+/// let _ = CslBuilder::<i32, NoDims, 2>::push_line;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CslBuilder<DATA, S, const D: usize> {
+  csl: CslVec<DATA, D>,
+  lines: usize,
+  state: PhantomData<S>,
+}
+
+impl<DATA, const D: usize> CslBuilder<DATA, NoDims, D> {
+  /// Declares the dimensions of the structure, which also defines the number of lines that
+  /// must be pushed.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Array of dimensions
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::csl::{CslBuilder, CslError};
+  /// let builder = CslBuilder::<i32, _, 3>::dims([2, 0, 3]);
+  /// assert_eq!(builder.err(), Some(ndsparse::Error::Csl(CslError::InnermostDimsZero)));
+  /// ```
+  #[inline]
+  pub fn dims(dims: [usize; D]) -> crate::Result<CslBuilder<DATA, WithDims, D>> {
+    if has_innermost_dims_zero(&dims) {
+      return Err(CslError::InnermostDimsZero.into());
+    }
+    let max_nnz = crate::utils::checked_max_nnz(&dims);
+    if max_nnz.and_then(|elem| usize::try_from(elem).ok()).is_none() {
+      return Err(CslError::DimsProductOverflow.into());
+    }
+    let lines = correct_offs_len(&dims)?.saturating_sub(1);
+    let outermost_stride = outermost_stride(&dims);
+    let csl =
+      CslVec { data: Vec::new(), dims, indcs: Vec::new(), offs: alloc::vec![0], outermost_stride };
+    Ok(CslBuilder { csl, lines, state: PhantomData })
+  }
+}
+
+impl<DATA, const D: usize> CslBuilder<DATA, WithDims, D> {
+  /// Checks if all declared lines were pushed and returns the resulting structure.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::csl::{CslBuilder, CslError};
+  /// let builder = CslBuilder::dims([3, 4]).unwrap();
+  /// let builder = builder.push_line([(1, 1)].iter().copied()).unwrap();
+  /// let err = CslError::InvalidNumOfLines { expected: 3, found: 1 };
+  /// assert_eq!(builder.finish(), Err(ndsparse::Error::Csl(err)));
+  /// ```
+  #[inline]
+  pub fn finish(self) -> crate::Result<CslVec<DATA, D>> {
+    let found = self.csl.offs.len().saturating_sub(1);
+    if found != self.lines {
+      return Err(CslError::InvalidNumOfLines { expected: self.lines, found }.into());
+    }
+    Ok(self.csl)
+  }
+
+  /// Pushes several lines at once. See [`push_line`](#method.push_line).
+  ///
+  /// # Arguments
+  ///
+  /// * `lines`: Iterator of lines, each one an iterator of innermost indices and their data
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslBuilder;
+  /// let lines = vec![vec![(0, 1)], vec![], vec![(2, 2)]];
+  /// let csl = CslBuilder::dims([3, 3])?.lines(lines)?.finish()?;
+  /// assert_eq!(csl.value([2, 2]), Some(&2));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn lines<I, L>(mut self, lines: I) -> crate::Result<Self>
+  where
+    I: IntoIterator<Item = L>,
+    L: IntoIterator<Item = (usize, DATA)>,
+  {
+    for line in lines {
+      self = self.push_line(line)?;
+    }
+    Ok(self)
+  }
+
+  /// Pushes a new line whose indices must be in strictly ascending order and less than the
+  /// innermost dimension length.
+  ///
+  /// # Arguments
+  ///
+  /// * `di`: Iterator of innermost indices and their data.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::csl::{CslBuilder, CslError};
+  /// let builder = CslBuilder::dims([2, 3]).unwrap();
+  /// let err = CslError::IndcsGreaterThanEqualDimLength { dim_len: 3, idx: 3 };
+  /// let rslt = builder.push_line([(3, 1)].iter().copied());
+  /// assert_eq!(rslt.err(), Some(ndsparse::Error::Csl(err)));
+  /// ```
+  #[inline]
+  pub fn push_line<L>(mut self, line: L) -> crate::Result<Self>
+  where
+    L: IntoIterator<Item = (usize, DATA)>,
+  {
+    let line_idx = self.csl.offs.len().saturating_sub(1);
+    if line_idx >= self.lines {
+      return Err(CslLineConstructorError::MaxNumOfLines.into());
+    }
+    let dim_len = self.csl.dims.last().copied().unwrap_or_default();
+    let mut last_idx = None;
+    for (idx, elem) in line {
+      if idx >= dim_len {
+        return Err(CslError::IndcsGreaterThanEqualDimLength { dim_len, idx }.into());
+      }
+      match last_idx {
+        Some(last) if last == idx => {
+          return Err(CslError::DuplicatedIndices { idx, line_idx }.into());
+        }
+        Some(last) if last > idx => return Err(CslError::UnsortedIndices { line_idx }.into()),
+        _ => {}
+      }
+      last_idx = Some(idx);
+      self.csl.data.push(elem);
+      self.csl.indcs.push(idx);
+    }
+    self.csl.offs.push(self.csl.data.len());
+    Ok(self)
+  }
+}
@@ -0,0 +1,130 @@
+use crate::csl::{line_ranges, outermost_indcs, Csl};
+use cl_traits::Storage;
+use core::{
+  cmp::Ordering,
+  hash::{Hash, Hasher},
+};
+
+/// Wrapper that compares, orders and hashes structures by their dimensions and by their stored
+/// elements in lexicographic order of indices, regardless of how the storages are laid out.
+///
+/// Two structures that represent the same tensor are equal even if their offsets start at
+/// different positions or if their storages are of different types, which makes the wrapper
+/// suitable as a deterministic key of a `BTreeMap` or a `HashMap`.
+///
+/// # Example
+///
+/// ```rust
+/// use ndsparse::csl::{CanonicalKey, CslArray, CslRef};
+/// let a = CslArray::new([2, 3], [1, 2], [2, 0], [0, 1, 2]).unwrap();
+/// let b = CslRef::new([2, 3], &[1, 2][..], &[2, 0][..], &[5, 6, 7][..]).unwrap();
+/// assert_eq!(CanonicalKey(&a), CanonicalKey(&b));
+/// let c = CslArray::new([2, 3], [1, 2], [2, 1], [0, 1, 2]).unwrap();
+/// assert!(CanonicalKey(&a) < CanonicalKey(&c));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CanonicalKey<S>(pub S);
+
+impl<DATA, DS, IS, OS, const D: usize> Eq for CanonicalKey<&Csl<DS, IS, OS, D>>
+where
+  DATA: Eq,
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+}
+
+impl<DATA, DS, IS, OS, const D: usize> Hash for CanonicalKey<&Csl<DS, IS, OS, D>>
+where
+  DATA: Hash,
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  #[inline]
+  fn hash<H>(&self, state: &mut H)
+  where
+    H: Hasher,
+  {
+    self.0.dims.hash(state);
+    for elem in elements(self.0) {
+      elem.hash(state);
+    }
+  }
+}
+
+impl<DATA, DS, IS, OS, const D: usize> Ord for CanonicalKey<&Csl<DS, IS, OS, D>>
+where
+  DATA: Ord,
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  #[inline]
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.0.dims.cmp(&other.0.dims).then_with(|| elements(self.0).cmp(elements(other.0)))
+  }
+}
+
+impl<DATA, DS, DS2, IS, IS2, OS, OS2, const D: usize>
+  PartialEq<CanonicalKey<&Csl<DS2, IS2, OS2, D>>> for CanonicalKey<&Csl<DS, IS, OS, D>>
+where
+  DATA: PartialEq,
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  DS2: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  IS2: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+  OS2: AsRef<[usize]>,
+{
+  #[inline]
+  fn eq(&self, other: &CanonicalKey<&Csl<DS2, IS2, OS2, D>>) -> bool {
+    self.0.dims == other.0.dims && elements(self.0).eq(elements(other.0))
+  }
+}
+
+impl<DATA, DS, DS2, IS, IS2, OS, OS2, const D: usize>
+  PartialOrd<CanonicalKey<&Csl<DS2, IS2, OS2, D>>> for CanonicalKey<&Csl<DS, IS, OS, D>>
+where
+  DATA: PartialOrd,
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  DS2: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  IS2: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+  OS2: AsRef<[usize]>,
+{
+  #[inline]
+  fn partial_cmp(&self, other: &CanonicalKey<&Csl<DS2, IS2, OS2, D>>) -> Option<Ordering> {
+    match self.0.dims.cmp(&other.0.dims) {
+      Ordering::Equal => elements(self.0).partial_cmp(elements(other.0)),
+      ordering => Some(ordering),
+    }
+  }
+}
+
+// Full indices and values of all stored elements in lexicographic order of indices.
+fn elements<'a, DATA, DS, IS, OS, const D: usize>(
+  csl: &'a Csl<DS, IS, OS, D>,
+) -> impl Iterator<Item = ([usize; D], &'a DATA)>
+where
+  DATA: 'a,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  let data = csl.data.as_ref();
+  let indcs = csl.indcs.as_ref();
+  line_ranges(csl.offs.as_ref()).enumerate().flat_map(move |(line, range)| {
+    let outermost = outermost_indcs(&csl.dims, line);
+    let line_data = data.get(range.clone()).unwrap_or_default();
+    let line_indcs = indcs.get(range).unwrap_or_default();
+    line_indcs.iter().zip(line_data).map(move |(&innermost_idx, value)| {
+      let mut elem_indcs = outermost;
+      if let Some(r) = elem_indcs.last_mut() {
+        *r = innermost_idx;
+      }
+      (elem_indcs, value)
+    })
+  })
+}
@@ -0,0 +1,102 @@
+use crate::csl::{outermost_indcs, try_line_offs, CslRef};
+
+/// Forward cursor over the flattened stream of stored elements, i.e., all `(indices, value)`
+/// pairs in lexicographic order.
+///
+/// Besides sequential reading, the cursor can jump to any line, which suits merge-style
+/// consumers like joins against a sorted external stream. Nothing is allocated.
+///
+/// Created by [`cursor`](crate::csl::Csl#method.cursor).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CslCursor<'a, DATA, const D: usize> {
+  csl: CslRef<'a, DATA, D>,
+  line: usize,
+  pos: usize,
+}
+
+impl<'a, DATA, const D: usize> CslCursor<'a, DATA, D> {
+  #[inline]
+  pub(crate) fn new(csl: CslRef<'a, DATA, D>) -> Self {
+    let mut this = Self { csl, line: 0, pos: 0 };
+    this.skip_consumed_lines();
+    this
+  }
+
+  /// Returns the current element and moves to the next one.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let mut cursor = csl.cursor();
+  /// assert_eq!(cursor.next_entry(), Some(([0, 0, 0, 0], &1)));
+  /// assert_eq!(cursor.next_entry(), Some(([0, 0, 0, 3], &2)));
+  /// assert_eq!(cursor.next_entry(), Some(([0, 0, 1, 1], &3)));
+  /// ```
+  #[inline]
+  pub fn next_entry(&mut self) -> Option<([usize; D], &'a DATA)> {
+    let entry = self.peek()?;
+    self.pos = self.pos.saturating_add(1);
+    self.skip_consumed_lines();
+    Some(entry)
+  }
+
+  /// Returns the current element without moving the cursor.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let mut cursor = csl.cursor();
+  /// assert_eq!(cursor.peek(), Some(([0, 0, 0, 0], &1)));
+  /// assert_eq!(cursor.peek(), cursor.next_entry());
+  /// ```
+  #[inline]
+  pub fn peek(&self) -> Option<([usize; D], &'a DATA)> {
+    let data = self.csl.data;
+    let value = data.get(self.pos)?;
+    let mut indcs = outermost_indcs(&self.csl.dims, self.line);
+    *indcs.last_mut()? = *self.csl.indcs.get(self.pos)?;
+    Some((indcs, value))
+  }
+
+  /// Moves the cursor to the first element of the line determined by `indcs` or, if the line is
+  /// empty, to the first element of the following lines. The innermost dimension is ignored.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let mut cursor = csl.cursor();
+  /// cursor.seek_line([0, 0, 2, 0]).unwrap();
+  /// assert_eq!(cursor.next_entry(), Some(([0, 0, 3, 3], &4)));
+  /// cursor.seek_line([1, 0, 2, 0]).unwrap();
+  /// assert_eq!(cursor.next_entry(), Some(([1, 0, 2, 2], &9)));
+  /// assert_eq!(cursor.next_entry(), None);
+  /// assert!(cursor.seek_line([0, 0, 4, 0]).is_err());
+  /// ```
+  #[inline]
+  pub fn seek_line(&mut self, indcs: [usize; D]) -> crate::Result<()> {
+    let csl = &self.csl;
+    let [lines, range] = try_line_offs(&csl.dims, csl.outermost_stride, &indcs, csl.offs)?;
+    self.line = lines.start;
+    self.pos = range.start;
+    self.skip_consumed_lines();
+    Ok(())
+  }
+
+  // Advances `line` until it contains `pos`, which skips empty lines.
+  fn skip_consumed_lines(&mut self) {
+    let first = self.csl.offs.first().copied().unwrap_or_default();
+    let rest = self.csl.offs.get(self.line.saturating_add(1)..).unwrap_or_default();
+    for end in rest.iter().map(|off| off.saturating_sub(first)) {
+      if end > self.pos {
+        break;
+      }
+      self.line = self.line.saturating_add(1);
+    }
+  }
+}
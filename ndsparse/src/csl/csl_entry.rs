@@ -0,0 +1,128 @@
+use crate::csl::{try_line_offs, Csl, CslError};
+use cl_traits::{Insert, Storage};
+
+/// A view into a single position of a CSL, which may or may not have a stored element.
+///
+/// Created by [`entry`](Csl#method.entry).
+#[derive(Debug, PartialEq)]
+pub struct CslEntry<'a, DS, IS, OS, const D: usize> {
+  csl: &'a mut Csl<DS, IS, OS, D>,
+  data_idx: usize,
+  innermost_idx: usize,
+  is_occupied: bool,
+  line: usize,
+}
+
+impl<'a, DATA, DS, IS, OS, const D: usize> CslEntry<'a, DS, IS, OS, D>
+where
+  DS: AsMut<[DATA]> + AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  #[inline]
+  pub(crate) fn new(csl: &'a mut Csl<DS, IS, OS, D>, indcs: [usize; D]) -> crate::Result<Self> {
+    let [offs_indcs, offs_values] =
+      try_line_offs(&csl.dims, csl.outermost_stride, &indcs, csl.offs.as_ref())?;
+    let innermost_idx = indcs.last().copied().unwrap_or_default();
+    let dim_len = csl.dims.last().copied().unwrap_or_default();
+    if innermost_idx >= dim_len {
+      return Err(CslError::IndcsGreaterThanEqualDimLength { dim_len, idx: innermost_idx }.into());
+    }
+    let line_indcs = csl.indcs.as_ref().get(offs_values.clone()).unwrap_or_default();
+    let (data_idx, is_occupied) = match line_indcs.binary_search(&innermost_idx) {
+      Ok(idx) => (offs_values.start + idx, true),
+      Err(idx) => (offs_values.start + idx, false),
+    };
+    Ok(Self { csl, data_idx, innermost_idx, is_occupied, line: offs_indcs.start })
+  }
+
+  /// Calls `cb` with the stored element, if any.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let mut csl = CslVec::new([2, 3], vec![1], vec![1], vec![0, 1, 1])?;
+  /// let _ = csl.entry([0, 1])?.and_modify(|value| *value *= 10).or_insert(0)?;
+  /// let _ = csl.entry([1, 1])?.and_modify(|value| *value *= 10).or_insert(0)?;
+  /// assert_eq!(csl, CslVec::new([2, 3], vec![10, 0], vec![1, 1], vec![0, 1, 2])?);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn and_modify<F>(self, cb: F) -> Self
+  where
+    F: FnOnce(&mut DATA),
+  {
+    if self.is_occupied {
+      if let Some(value) = self.csl.data.as_mut().get_mut(self.data_idx) {
+        cb(value);
+      }
+    }
+    self
+  }
+
+  /// Whether there is a stored element at the position of the entry.
+  #[inline]
+  pub fn is_occupied(&self) -> bool {
+    self.is_occupied
+  }
+
+  /// Ensures that an element is stored by inserting the default value if vacant.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let mut histogram = CslVec::new([2, 3], vec![1], vec![1], vec![0, 1, 1])?;
+  /// for indcs in [[0, 1], [1, 2], [1, 2], [0, 0]].iter().copied() {
+  ///   *histogram.entry(indcs)?.or_default()? += 1;
+  /// }
+  /// assert_eq!(histogram, CslVec::new([2, 3], vec![1, 2, 2], vec![0, 1, 2], vec![0, 2, 3])?);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn or_default(self) -> crate::Result<&'a mut DATA>
+  where
+    DATA: Default,
+    DS: Insert<Input = (usize, DATA)>,
+    IS: Insert<Input = (usize, usize)>,
+    OS: AsMut<[usize]>,
+  {
+    self.or_insert_with(DATA::default)
+  }
+
+  /// Ensures that an element is stored by inserting `value` if vacant.
+  #[inline]
+  pub fn or_insert(self, value: DATA) -> crate::Result<&'a mut DATA>
+  where
+    DS: Insert<Input = (usize, DATA)>,
+    IS: Insert<Input = (usize, usize)>,
+    OS: AsMut<[usize]>,
+  {
+    self.or_insert_with(|| value)
+  }
+
+  /// Ensures that an element is stored by inserting the result of `cb` if vacant. The
+  /// subsequent elements are shifted and the offsets of the subsequent lines are incremented.
+  #[inline]
+  pub fn or_insert_with<F>(self, cb: F) -> crate::Result<&'a mut DATA>
+  where
+    DS: Insert<Input = (usize, DATA)>,
+    F: FnOnce() -> DATA,
+    IS: Insert<Input = (usize, usize)>,
+    OS: AsMut<[usize]>,
+  {
+    let Self { csl, data_idx, innermost_idx, is_occupied, line } = self;
+    if !is_occupied {
+      let rslt = csl.indcs.insert((data_idx, innermost_idx));
+      rslt.map_err(|_err| crate::Error::InsufficientCapacity)?;
+      csl.data.insert((data_idx, cb())).map_err(|_err| crate::Error::InsufficientCapacity)?;
+      for off in csl.offs.as_mut().iter_mut().skip(line + 1) {
+        *off += 1;
+      }
+    }
+    csl.data.as_mut().get_mut(data_idx).ok_or(crate::Error::UnknownError)
+  }
+}
@@ -1,6 +1,7 @@
 use core::fmt;
 
 /// Any error related to Csl operations
+#[cfg_attr(feature = "with-defmt", derive(defmt::Format))]
 #[derive(Debug, PartialEq)]
 #[non_exhaustive]
 pub enum CslError {
@@ -20,26 +21,93 @@ pub enum CslError {
   #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
   /// use ndsparse::csl::{ CslError, CslVec};
   /// let csl = CslVec::new([10], vec![8, 9], vec![0], vec![0, 2]);
-  /// assert_eq!(csl, Err(ndsparse::Error::Csl(CslError::DiffDataIndcsLength)));
+  /// assert_eq!(csl, Err(ndsparse::Error::Csl(CslError::DiffDataIndcsLength { data_len: 2, indcs_len: 1 })));
   /// ```
-  DiffDataIndcsLength,
+  DiffDataIndcsLength {
+    /// Data length
+    data_len: usize,
+    /// Indices length
+    indcs_len: usize,
+  },
+
+  /// Two structures that should share the same dimensions have different dimensions
+  ///
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::csl::{CslError, CslVec};
+  /// let a = CslVec::new([10], vec![8], vec![0], vec![0, 1]).unwrap();
+  /// let b = CslVec::new([11], vec![8], vec![0], vec![0, 1]).unwrap();
+  /// assert_eq!(a.union_pattern(&b), Err(ndsparse::Error::Csl(CslError::DiffDims)));
+  /// ```
+  DiffDims,
+
+  /// The product of all dimensions doesn't fit in an `usize`
+  ///
+  /// ```rust
+  /// use ndsparse::csl::{CslArray, CslError};
+  /// let csl = CslArray::<i32, 2, 0, 3>::new([2, usize::MAX], [], [], [0, 0, 0]);
+  /// assert_eq!(csl, Err(ndsparse::Error::Csl(CslError::DimsProductOverflow)));
+  /// ```
+  DimsProductOverflow,
 
   /// Duplicated indices in a line
   /// ```rust
   /// use ndsparse::csl::{CslArray, CslError};
   /// let csl = CslArray::new([10], [8, 9], [0, 0], [0, 2]);
-  /// assert_eq!(csl, Err(ndsparse::Error::Csl(CslError::DuplicatedIndices)));
+  /// let err = CslError::DuplicatedIndices { idx: 0, line_idx: 0 };
+  /// assert_eq!(csl, Err(ndsparse::Error::Csl(err)));
   /// ```
-  DuplicatedIndices,
+  DuplicatedIndices {
+    /// The duplicated innermost index
+    idx: usize,
+    /// Position of the line in the offsets
+    line_idx: usize,
+  },
+
+  /// The HDF5 library couldn't write a dataset or an attribute
+  #[cfg(feature = "with-hdf5")]
+  Hdf5,
 
   /// A index is greater or equal to the innermost dimension length
   ///
   /// ```rust
   /// use ndsparse::csl::{CslArray, CslError};
   /// let csl = CslArray::new([10], [8, 9], [0, 10], [0, 2]);
-  /// assert_eq!(csl, Err(ndsparse::Error::Csl(CslError::IndcsGreaterThanEqualDimLength)));
+  /// let err = CslError::IndcsGreaterThanEqualDimLength { dim_len: 10, idx: 10 };
+  /// assert_eq!(csl, Err(ndsparse::Error::Csl(err)));
+  /// ```
+  IndcsGreaterThanEqualDimLength {
+    /// Length of the dimension that should contain `idx`
+    dim_len: usize,
+    /// The offending index
+    idx: usize,
+  },
+
+  /// An outer index is greater or equal to the length of its dimension
+  ///
+  /// ```rust
+  /// use ndsparse::{csl::CslError, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// let err = CslError::IndexOutOfBounds { axis: 2, dim_len: 4, idx: 4 };
+  /// assert_eq!(csl.try_line([0, 0, 4, 0]), Err(ndsparse::Error::Csl(err)));
   /// ```
-  IndcsGreaterThanEqualDimLength,
+  IndexOutOfBounds {
+    /// Axis of the offending index
+    axis: usize,
+    /// Length of the dimension
+    dim_len: usize,
+    /// The offending index
+    idx: usize,
+  },
+
+  /// Some index or offset doesn't fit in the target integer type
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{csl::{CslError, CslVec}, gpu::GpuCsl};
+  /// let csl = CslVec::new([1, usize::MAX], vec![1.0f32], vec![usize::MAX - 1], vec![0, 1]).unwrap();
+  /// assert_eq!(GpuCsl::pack(&csl), Err(ndsparse::Error::Csl(CslError::IndexOverflow)));
+  /// ```
+  IndexOverflow,
 
   /// Some innermost dimension length is equal to zero
   #[cfg_attr(feature = "alloc", doc = "```rust")]
@@ -51,6 +119,107 @@ pub enum CslError {
   /// ```
   InnermostDimsZero,
 
+  /// Arrow array doesn't have the expected layout or types
+  #[cfg_attr(feature = "with-arrow", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-arrow"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::{CslError, CslVec};
+  /// let array = CslVec::<i32, 2>::default().to_arrow::<arrow_array::types::Int32Type>()?;
+  /// let csl = CslVec::<i64, 2>::from_arrow::<arrow_array::types::Int64Type>(&array, 0);
+  /// assert_eq!(csl, Err(ndsparse::Error::Csl(CslError::InvalidArrowArray)));
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "with-arrow")]
+  InvalidArrowArray,
+
+  /// A storage can't be converted into an array of a different length
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use core::convert::TryFrom;
+  /// use ndsparse::csl::{CslArray, CslError, CslVec};
+  /// let csl = CslVec::new([10], vec![8, 9], vec![0, 5], vec![0, 2]).unwrap();
+  /// let err = CslError::InvalidArrayLength { expected: 3, found: 2 };
+  /// assert_eq!(CslArray::<_, 1, 3, 2>::try_from(csl), Err(ndsparse::Error::Csl(err)));
+  /// ```
+  InvalidArrayLength {
+    /// Length of the array
+    expected: usize,
+    /// Length of the storage
+    found: usize,
+  },
+
+  /// Axis isn't one of the dimensions of the structure
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{csl::CslError, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// let rslt = csl.mode_n_product(&[1], 1, 4);
+  /// assert_eq!(rslt, Err(ndsparse::Error::Csl(CslError::InvalidAxis { axis: 4 })));
+  /// ```
+  InvalidAxis {
+    /// The offending axis
+    axis: usize,
+  },
+
+  /// The length or the alignment of a byte slice isn't compatible with its element type
+  #[cfg_attr(feature = "with-bytemuck", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-bytemuck"), doc = "```ignore")]
+  /// use ndsparse::csl::{CslError, CslRef};
+  /// let csl = CslRef::<u8, 1>::from_bytes([2], &[1, 2], &[0; 3], &[0; 16]);
+  /// assert_eq!(csl, Err(ndsparse::Error::Csl(CslError::InvalidBytes)));
+  /// ```
+  #[cfg(feature = "with-bytemuck")]
+  InvalidBytes,
+
+  /// Two dimensions that are aligned by broadcasting have different lengths and none of them
+  /// is equal to one
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::csl::{ops::broadcast_apply, CslError, CslVec};
+  /// let a = CslVec::new([2, 3], vec![1], vec![0], vec![0, 1, 1]).unwrap();
+  /// let b = CslVec::new([2], vec![1], vec![0], vec![0, 1]).unwrap();
+  /// let rslt: ndsparse::Result<CslVec<(), 2>> = broadcast_apply(&a, &b, |_, _| Some(()));
+  /// assert_eq!(rslt, Err(ndsparse::Error::Csl(CslError::InvalidBroadcast { axis: 1 })));
+  /// ```
+  InvalidBroadcast {
+    /// Axis of the resulting structure
+    axis: usize,
+  },
+
+  /// Chunk iterators must cover at least one outermost index
+  /// ```rust
+  /// use ndsparse::{csl::CslError, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// assert_eq!(csl.outermost_chunks(0), Err(ndsparse::Error::Csl(CslError::InvalidChunkSize)));
+  /// ```
+  InvalidChunkSize,
+
+  /// Dense operand doesn't have the number of elements required by its shape
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{csl::CslError, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// let rslt = csl.mode_n_product(&[1, 2], 2, 0);
+  /// let err = CslError::InvalidDenseLength { expected: 4, found: 2 };
+  /// assert_eq!(rslt, Err(ndsparse::Error::Csl(err)));
+  /// ```
+  InvalidDenseLength {
+    /// Number of elements required by the shape
+    expected: usize,
+    /// Number of provided elements
+    found: usize,
+  },
+
+  /// The parameters of a random distribution are out of their valid ranges
+  ///
+  /// ```rust
+  /// use ndsparse::csl::{CslError, LineNnzDistribution};
+  /// let dist = LineNnzDistribution::binomial(4, 1.5);
+  /// assert_eq!(dist.err(), Some(ndsparse::Error::Csl(CslError::InvalidDistribution)));
+  /// ```
+  #[cfg(feature = "with-rand")]
+  InvalidDistribution,
+
   /// Line iterator must deal with non-empty dimensions
   #[cfg_attr(feature = "alloc", doc = "```rust")]
   #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
@@ -60,6 +229,72 @@ pub enum CslError {
   /// ```
   InvalidIterDim,
 
+  /// The mapping doesn't have one entry for each index of the axis or maps different indices to
+  /// the same index
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{csl::CslError, fixtures::csl_vec_4};
+  /// let csl = csl_vec_4();
+  /// let rslt = csl.remap_indices(0, &[0, 0]);
+  /// assert_eq!(rslt, Err(ndsparse::Error::Csl(CslError::InvalidMapping)));
+  /// ```
+  InvalidMapping,
+
+  /// The number of pushed lines is different than the number of lines of the dimensions
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::csl::{CslBuilder, CslError};
+  /// let builder = CslBuilder::<i32, _, 2>::dims([2, 3]).unwrap();
+  /// let err = CslError::InvalidNumOfLines { expected: 2, found: 0 };
+  /// assert_eq!(builder.finish(), Err(ndsparse::Error::Csl(err)));
+  /// ```
+  InvalidNumOfLines {
+    /// Number of lines of the dimensions
+    expected: usize,
+    /// Number of pushed lines
+    found: usize,
+  },
+
+  /// The permutation isn't a valid rearrangement of the outermost dimension
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{csl::CslError, fixtures::csl_vec_4};
+  /// let csl = csl_vec_4();
+  /// let rslt = csl.permute_outermost(&[1, 1]);
+  /// assert_eq!(rslt, Err(ndsparse::Error::Csl(CslError::InvalidPermutation)));
+  /// ```
+  InvalidPermutation,
+
+  /// The range is reversed or goes beyond the stored lines
+  ///
+  /// ```rust
+  /// use ndsparse::{csl::CslError, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// let err = CslError::InvalidRange { start: 0, end: 3 };
+  /// assert_eq!(csl.try_sub_dim::<4>(0..3), Err(ndsparse::Error::Csl(err)));
+  /// ```
+  InvalidRange {
+    /// Start of the range
+    start: usize,
+    /// End of the range
+    end: usize,
+  },
+
+  /// The number of dimensions of the resulting structure isn't compatible with the operation
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{csl::CslError, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// let rslt = csl.unsqueeze::<4>(0);
+  /// assert_eq!(rslt, Err(ndsparse::Error::Csl(CslError::InvalidRank { expected: 5, found: 4 })));
+  /// ```
+  InvalidRank {
+    /// Required number of dimensions
+    expected: usize,
+    /// Provided number of dimensions
+    found: usize,
+  },
+
   /// Offsets length is different than the dimensions product
   /// (without the innermost dimension) plus one.
   /// This rule doesn't not apply to an empty dimension.
@@ -67,27 +302,55 @@ pub enum CslError {
   #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
   /// use ndsparse::csl::{CslError, CslVec};
   /// let csl = CslVec::new([10], vec![8, 9], vec![0, 5], vec![0, 2, 4]);
-  /// assert_eq!(csl, Err(ndsparse::Error::Csl(CslError::InvalidOffsetsLength)));
+  /// let err = CslError::InvalidOffsetsLength { expected: 2, found: 3 };
+  /// assert_eq!(csl, Err(ndsparse::Error::Csl(err)));
   /// ```
-  InvalidOffsetsLength,
+  InvalidOffsetsLength {
+    /// Expected offsets length
+    expected: usize,
+    /// Actual offsets length
+    found: usize,
+  },
 
   /// Offsets aren't in ascending order
   ///
   /// ```rust
   /// use ndsparse::csl::{CslArray, CslError};
   /// let csl = CslArray::new([10], [8, 9], [0, 5], [2, 0]);
-  /// assert_eq!(csl, Err(ndsparse::Error::Csl(CslError::InvalidOffsetsOrder)));
+  /// assert_eq!(csl, Err(ndsparse::Error::Csl(CslError::InvalidOffsetsOrder { offs_idx: 1 })));
   /// ```
-  InvalidOffsetsOrder,
+  InvalidOffsetsOrder {
+    /// Position of the first offset that is smaller than its predecessor
+    offs_idx: usize,
+  },
 
   /// Last offset is not equal to the nnz
   ///
   /// ```rust
   /// use ndsparse::csl::{CslArray, CslError};
   /// let csl = CslArray::new([10], [8, 9], [0, 5], [0, 4]);
-  /// assert_eq!(csl, Err(ndsparse::Error::Csl(CslError::LastOffsetDifferentNnz)));
+  /// let err = CslError::LastOffsetDifferentNnz { last_off: 4, nnz: 2 };
+  /// assert_eq!(csl, Err(ndsparse::Error::Csl(err)));
   /// ```
-  LastOffsetDifferentNnz,
+  LastOffsetDifferentNnz {
+    /// Last offset relative to the first offset
+    last_off: usize,
+    /// Number of stored elements
+    nnz: usize,
+  },
+
+  /// A symmetric structure has an element below the main diagonal
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::csl::{CslError, CslVec, SymCsl};
+  /// let csl = CslVec::new([2, 2], vec![1, 2], vec![1, 0], vec![0, 1, 2]).unwrap();
+  /// let err = CslError::LowerTriangleElement { line_idx: 1 };
+  /// assert_eq!(SymCsl::new(csl), Err(ndsparse::Error::Csl(err)));
+  /// ```
+  LowerTriangleElement {
+    /// Position of the line in the offsets
+    line_idx: usize,
+  },
 
   /// nnz is greater than the maximum permitted number of nnz
   #[cfg_attr(all(feature = "alloc", feature = "with-rand"), doc = "```rust")]
@@ -103,6 +366,36 @@ pub enum CslError {
   #[cfg(feature = "with-rand")]
   NnzGreaterThanMaximumNnz,
 
+  /// The operation requires the same number of rows and columns
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::csl::{CslError, CslVec, SymCsl};
+  /// let csl = CslVec::new([2, 3], vec![1], vec![2], vec![0, 1, 1]).unwrap();
+  /// let err = CslError::NonSquare { cols: 3, rows: 2 };
+  /// assert_eq!(SymCsl::new(csl), Err(ndsparse::Error::Csl(err)));
+  /// ```
+  NonSquare {
+    /// Number of columns
+    cols: usize,
+    /// Number of rows
+    rows: usize,
+  },
+
+  /// Only dimensions of length one can be removed
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// use ndsparse::{csl::CslError, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// let rslt = csl.squeeze::<3>(0);
+  /// assert_eq!(rslt, Err(ndsparse::Error::Csl(CslError::NonUnitAxis { axis: 0, dim_len: 2 })));
+  /// ```
+  NonUnitAxis {
+    /// The offending axis
+    axis: usize,
+    /// Length of the axis
+    dim_len: usize,
+  },
+
   /// It isn't possible to have more lines than usize::MAX - 2
   ///
   /// ```rust
@@ -111,6 +404,18 @@ pub enum CslError {
   /// assert_eq!(csl, Err(ndsparse::Error::Csl(CslError::OffsLengthOverflow)));
   /// ```
   OffsLengthOverflow,
+
+  /// The indices of a line aren't in ascending order
+  ///
+  /// ```rust
+  /// use ndsparse::csl::{CslArray, CslError};
+  /// let csl = CslArray::new([10], [8, 9], [5, 0], [0, 2]);
+  /// assert_eq!(csl, Err(ndsparse::Error::Csl(CslError::UnsortedIndices { line_idx: 0 })));
+  /// ```
+  UnsortedIndices {
+    /// Position of the line in the offsets
+    line_idx: usize,
+  },
 }
 
 impl fmt::Display for CslError {
@@ -118,17 +423,79 @@ impl fmt::Display for CslError {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     let s = match *self {
       Self::DataIndcsLengthGreaterThanDimsLength => "DataIndcsLengthGreaterThanDimsLength",
-      Self::DiffDataIndcsLength => "DiffDataIndcsLength",
-      Self::DuplicatedIndices => "DuplicatedIndices",
-      Self::IndcsGreaterThanEqualDimLength => "IndcsGreaterThanEqualDimLength",
+      Self::DiffDataIndcsLength { data_len, indcs_len } => {
+        return write!(f, "DiffDataIndcsLength (data: {}, indices: {})", data_len, indcs_len);
+      }
+      Self::DiffDims => "DiffDims",
+      Self::DimsProductOverflow => "DimsProductOverflow",
+      Self::DuplicatedIndices { idx, line_idx } => {
+        return write!(f, "DuplicatedIndices (index {} of line {})", idx, line_idx);
+      }
+      #[cfg(feature = "with-hdf5")]
+      Self::Hdf5 => "Hdf5",
+      Self::IndcsGreaterThanEqualDimLength { dim_len, idx } => {
+        return write!(f, "IndcsGreaterThanEqualDimLength (index {} of length {})", idx, dim_len);
+      }
+      Self::IndexOutOfBounds { axis, dim_len, idx } => {
+        return write!(
+          f,
+          "IndexOutOfBounds (index {} of axis {} of length {})",
+          idx, axis, dim_len
+        );
+      }
+      Self::IndexOverflow => "IndexOverflow",
       Self::InnermostDimsZero => "InnermostDimsZero",
+      #[cfg(feature = "with-arrow")]
+      Self::InvalidArrowArray => "InvalidArrowArray",
+      Self::InvalidArrayLength { expected, found } => {
+        return write!(f, "InvalidArrayLength (expected: {}, found: {})", expected, found);
+      }
+      Self::InvalidAxis { axis } => return write!(f, "InvalidAxis ({})", axis),
+      #[cfg(feature = "with-bytemuck")]
+      Self::InvalidBytes => "InvalidBytes",
+      Self::InvalidBroadcast { axis } => return write!(f, "InvalidBroadcast (axis {})", axis),
+      Self::InvalidChunkSize => "InvalidChunkSize",
+      Self::InvalidDenseLength { expected, found } => {
+        return write!(f, "InvalidDenseLength (expected: {}, found: {})", expected, found);
+      }
+      #[cfg(feature = "with-rand")]
+      Self::InvalidDistribution => "InvalidDistribution",
       Self::InvalidIterDim => "InvalidIterDim",
-      Self::InvalidOffsetsLength => "InvalidOffsetsLength",
-      Self::InvalidOffsetsOrder => "InvalidOffsetsOrder",
-      Self::LastOffsetDifferentNnz => "LastOffsetDifferentNnz",
+      Self::InvalidMapping => "InvalidMapping",
+      Self::InvalidNumOfLines { expected, found } => {
+        return write!(f, "InvalidNumOfLines (expected: {}, found: {})", expected, found);
+      }
+      Self::InvalidPermutation => "InvalidPermutation",
+      Self::InvalidRange { start, end } => {
+        return write!(f, "InvalidRange ({}..{})", start, end);
+      }
+      Self::InvalidRank { expected, found } => {
+        return write!(f, "InvalidRank (expected: {}, found: {})", expected, found);
+      }
+      Self::InvalidOffsetsLength { expected, found } => {
+        return write!(f, "InvalidOffsetsLength (expected: {}, found: {})", expected, found);
+      }
+      Self::InvalidOffsetsOrder { offs_idx } => {
+        return write!(f, "InvalidOffsetsOrder (offset {})", offs_idx);
+      }
+      Self::LastOffsetDifferentNnz { last_off, nnz } => {
+        return write!(f, "LastOffsetDifferentNnz (last offset: {}, nnz: {})", last_off, nnz);
+      }
+      Self::LowerTriangleElement { line_idx } => {
+        return write!(f, "LowerTriangleElement (line {})", line_idx);
+      }
       #[cfg(feature = "with-rand")]
       Self::NnzGreaterThanMaximumNnz => "NnzGreaterThanMaximumNnz",
-      Self::OffsLengthOverflow => "OffsLengthOverflowb",
+      Self::NonSquare { cols, rows } => {
+        return write!(f, "NonSquare (rows: {}, cols: {})", rows, cols);
+      }
+      Self::NonUnitAxis { axis, dim_len } => {
+        return write!(f, "NonUnitAxis (axis {} of length {})", axis, dim_len);
+      }
+      Self::OffsLengthOverflow => "OffsLengthOverflow",
+      Self::UnsortedIndices { line_idx } => {
+        return write!(f, "UnsortedIndices (line {})", line_idx);
+      }
     };
     write!(f, "{}", s)
   }
@@ -0,0 +1,41 @@
+use crate::csl::{rebased_offs, CslError};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use hdf5::{Group, H5Type};
+
+pub(crate) const DATA: &str = "data";
+pub(crate) const INDICES: &str = "indices";
+pub(crate) const INDPTR: &str = "indptr";
+pub(crate) const SHAPE: &str = "shape";
+
+#[inline]
+pub(crate) fn to_hdf5<T>(
+  group: &Group,
+  dims: &[usize],
+  data: &[T],
+  indcs: &[usize],
+  offs: &[usize],
+) -> crate::Result<()>
+where
+  T: H5Type,
+{
+  let shape = i64_values(dims.iter().copied())?;
+  let indices = i64_values(indcs.iter().copied())?;
+  let indptr = i64_values(rebased_offs(offs)?.into_iter())?;
+  let _dataset = group.new_dataset_builder().with_data(data).create(DATA).map_err(hdf5_err)?;
+  let _dataset =
+    group.new_dataset_builder().with_data(&indices).create(INDICES).map_err(hdf5_err)?;
+  let _dataset = group.new_dataset_builder().with_data(&indptr).create(INDPTR).map_err(hdf5_err)?;
+  let _attr = group.new_attr_builder().with_data(&shape).create(SHAPE).map_err(hdf5_err)?;
+  Ok(())
+}
+
+#[inline]
+fn hdf5_err(_err: hdf5::Error) -> crate::Error {
+  CslError::Hdf5.into()
+}
+
+#[inline]
+fn i64_values(iter: impl Iterator<Item = usize>) -> crate::Result<Vec<i64>> {
+  iter.map(|x| i64::try_from(x).map_err(|_err| CslError::IndexOverflow.into())).collect()
+}
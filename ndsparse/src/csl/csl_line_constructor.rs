@@ -1,5 +1,9 @@
-use crate::csl::{manage_last_offset, Csl};
-use cl_traits::{Push, Storage};
+use crate::csl::{manage_last_offset, outermost_stride, Csl, CslError};
+#[cfg(feature = "alloc")]
+use crate::utils::windows2;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use cl_traits::{Push, Storage, Truncate};
 use core::fmt;
 
 /// Constructs valid lines in a easy and interactive manner, abstracting away the complexity
@@ -57,6 +61,7 @@ where
     self.curr_dim_idx =
       self.curr_dim_idx.checked_sub(1).ok_or(CslLineConstructorError::DimsOverflow)?;
     *self.curr_dim() = len;
+    self.csl.outermost_stride = outermost_stride(&self.csl.dims);
     Ok(self)
   }
 
@@ -73,21 +78,20 @@ where
   /// assert_eq!(csl.line([0, 0, 0]), CslRef::new([3], &[][..], &[][..], &[0, 0][..]).ok());
   /// # Ok(()) }
   #[inline]
-  pub fn push_empty_line(self) -> crate::Result<Self> {
-    let _ = self.csl.offs.push(self.last_off).map_err(|_err| crate::Error::InsufficientCapacity)?;
+  pub fn push_empty_line(mut self) -> crate::Result<Self> {
+    self.do_push_empty_line()?;
     Ok(self)
   }
 
   /// Pushes a new compressed line, modifying the internal structure and if applicable,
   /// increases the current dimension length.
   ///
-  /// The iterator will be truncated to (usize::Max - last offset value + 1) or (last dimension value)
-  /// and it can lead to a situation where no values will be inserted.
+  /// Indices must be in ascending order and less than the innermost dimension length. Storages
+  /// that can't hold all items return [`Error::InsufficientCapacity`](crate::Error).
   ///
   /// # Arguments
   ///
-  /// * `data`:  Iterator of cloned items.
-  /// * `indcs`: Iterator of the respective indices of each item.
+  /// * `di`: Iterator of owned items and their respective innermost indices.
   ///
   /// # Example
   #[cfg_attr(feature = "alloc", doc = "```rust")]
@@ -104,41 +108,169 @@ where
   where
     DI: Iterator<Item = (usize, DATA)>,
   {
-    let nnz_iter = 1..self.last_dim().saturating_add(1);
-    let off_iter = self.last_off.saturating_add(1)..;
-    let mut iter = off_iter.zip(nnz_iter.zip(di));
-    let mut last_off = self.last_off;
-    let mut nnz = 0;
+    self.do_push_line(di, &mut 0, |value| value)?;
+    Ok(self)
+  }
 
-    let mut push = |curr_last_off, curr_nnz, idx, value| {
-      let _ = self.csl.indcs.push(idx).map_err(|_err| crate::Error::InsufficientCapacity)?;
-      let _ = self.csl.data.push(value).map_err(|_err| crate::Error::InsufficientCapacity)?;
-      nnz = curr_nnz;
-      last_off = curr_last_off;
-      Ok::<(), crate::Error>(())
-    };
+  /// Borrowed version of [`push_line`](#method.push_line).
+  ///
+  /// Each item is only cloned after its index is validated, which is useful for non-`Copy`
+  /// payloads that are already stored somewhere else.
+  ///
+  /// # Arguments
+  ///
+  /// * `di`: Iterator of borrowed items and their respective innermost indices.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let names = [String::from("foo"), String::from("bar")];
+  /// let mut csl = CslVec::<String, 2>::default();
+  /// csl.constructor()?.next_outermost_dim(3)?.push_line_ref([1, 2].iter().copied().zip(&names))?;
+  /// assert_eq!(csl.value([0, 1]).map(|s| s.as_str()), Some("foo"));
+  /// assert_eq!(csl.value([0, 2]).map(|s| s.as_str()), Some("bar"));
+  /// # Ok(()) }
+  #[inline]
+  pub fn push_line_ref<'b, DI>(mut self, di: DI) -> crate::Result<Self>
+  where
+    DATA: Clone + 'b,
+    DI: Iterator<Item = (usize, &'b DATA)>,
+  {
+    self.do_push_line(di, &mut 0, Clone::clone)?;
+    Ok(self)
+  }
 
-    let mut last_line_idx = if let Some((curr_last_off, (curr_nnz, (idx, value)))) = iter.next() {
-      push(curr_last_off, curr_nnz, idx, value)?;
-      idx
-    } else {
-      return self.push_empty_line();
-    };
+  /// Unsorted version of [`push_line`](#method.push_line).
+  ///
+  /// Items are buffered, sorted by their innermost indices and then pushed. The buffer never
+  /// holds more than the innermost dimension length plus one items because any additional item
+  /// is necessarily a duplicate.
+  ///
+  /// # Arguments
+  ///
+  /// * `di`: Iterator of owned items and their respective innermost indices in any order.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::{CslLineConstructorError, CslVec};
+  /// let mut csl = CslVec::<i32, 2>::default();
+  /// let constructor = csl.constructor()?.next_outermost_dim(4)?;
+  /// constructor.push_line_unsorted([(3, 1), (0, 2), (2, 3)].iter().copied())?;
+  /// assert_eq!(csl.data(), &[2, 3, 1]);
+  /// assert_eq!(csl.indcs(), &[0, 2, 3]);
+  /// let constructor = csl.constructor()?;
+  /// let rslt = constructor.push_line_unsorted([(1, 1), (0, 2), (1, 3)].iter().copied());
+  /// let err = CslLineConstructorError::DuplicatedIndices { idx: 1 };
+  /// assert_eq!(rslt.err(), Some(ndsparse::Error::CslLineConstructor(err)));
+  /// # Ok(()) }
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn push_line_unsorted<DI>(mut self, di: DI) -> crate::Result<Self>
+  where
+    DI: Iterator<Item = (usize, DATA)>,
+  {
+    let dim_len = self.last_dim();
+    let mut buffer = Vec::with_capacity(di.size_hint().0.min(dim_len));
+    for (idx, value) in di {
+      if idx >= dim_len {
+        return Err(CslError::IndcsGreaterThanEqualDimLength { dim_len, idx }.into());
+      }
+      buffer.push((idx, value));
+      if buffer.len() > dim_len {
+        break;
+      }
+    }
+    buffer.sort_unstable_by_key(|elem| elem.0);
+    if let Some([(idx, _), _]) = windows2(&buffer).find(|[prev, next]| prev.0 == next.0) {
+      return Err(CslLineConstructorError::DuplicatedIndices { idx: *idx }.into());
+    }
+    self.do_push_line(buffer.into_iter(), &mut 0, |value| value)?;
+    Ok(self)
+  }
 
-    for (curr_last_off, (curr_nnz, (idx, value))) in iter {
-      if idx <= last_line_idx {
-        return Err(CslLineConstructorError::UnsortedIndices.into());
+  /// Transactional version of [`push_line`](#method.push_line).
+  ///
+  /// If any error occurs, e.g., the storages of a fixed-capacity structure are full, all pushed
+  /// items of the current line are removed and the structure is left untouched.
+  ///
+  /// Capacity exhaustion is reported as [`CslLineConstructorError::InsufficientCapacity`] with
+  /// the number of items that were taken out of `di`, including the one that couldn't be stored.
+  ///
+  /// # Arguments
+  ///
+  /// * `di`: Iterator of owned items and their respective innermost indices.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "alloc", doc = "```rust")]
+  #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// let mut csl = CslVec::<i32, 2>::default();
+  /// let constructor = csl.constructor()?.next_outermost_dim(3)?;
+  /// assert!(constructor.try_push_line([(1, 1), (0, 2)].iter().copied()).is_err());
+  /// assert_eq!(csl.data(), &[]);
+  /// assert_eq!(csl.indcs(), &[]);
+  /// # Ok(()) }
+  #[inline]
+  pub fn try_push_line<DI>(mut self, di: DI) -> crate::Result<Self>
+  where
+    DI: Iterator<Item = (usize, DATA)>,
+    DS: Truncate<Input = usize>,
+    IS: Truncate<Input = usize>,
+  {
+    let data_len = self.csl.data.as_ref().len();
+    let indcs_len = self.csl.indcs.as_ref().len();
+    let mut consumed = 0;
+    if let Err(err) = self.do_push_line(di, &mut consumed, |value| value) {
+      let _ = self.csl.data.truncate(data_len);
+      let _ = self.csl.indcs.truncate(indcs_len);
+      if err == crate::Error::InsufficientCapacity {
+        return Err(CslLineConstructorError::InsufficientCapacity { consumed }.into());
       }
-      push(curr_last_off, curr_nnz, idx, value)?;
-      last_line_idx = idx;
+      return Err(err);
     }
+    Ok(self)
+  }
 
-    if nnz == 0 {
-      return self.push_empty_line();
+  #[inline]
+  fn do_push_line<DI, F, T>(&mut self, di: DI, consumed: &mut usize, mut cb: F) -> crate::Result<()>
+  where
+    DI: Iterator<Item = (usize, T)>,
+    F: FnMut(T) -> DATA,
+  {
+    let dim_len = self.last_dim();
+    let mut last_idx = None;
+    let mut last_off = self.last_off;
+    for (idx, value) in di {
+      *consumed = consumed.saturating_add(1);
+      if idx >= dim_len {
+        return Err(CslError::IndcsGreaterThanEqualDimLength { dim_len, idx }.into());
+      }
+      if last_idx.is_some_and(|last| idx <= last) {
+        return Err(CslLineConstructorError::UnsortedIndices.into());
+      }
+      last_off = last_off.checked_add(1).ok_or(crate::Error::InsufficientCapacity)?;
+      let _ = self.csl.indcs.push(idx).map_err(|_err| crate::Error::InsufficientCapacity)?;
+      let _ = self.csl.data.push(cb(value)).map_err(|_err| crate::Error::InsufficientCapacity)?;
+      last_idx = Some(idx);
+    }
+    if last_idx.is_none() {
+      return self.do_push_empty_line();
     }
     let _ = self.csl.offs.push(last_off).map_err(|_err| crate::Error::InsufficientCapacity)?;
     self.last_off = last_off;
-    Ok(self)
+    Ok(())
+  }
+
+  #[inline]
+  fn do_push_empty_line(&mut self) -> crate::Result<()> {
+    let _ = self.csl.offs.push(self.last_off).map_err(|_err| crate::Error::InsufficientCapacity)?;
+    Ok(())
   }
 
   #[allow(
@@ -161,11 +293,22 @@ where
 }
 
 /// Contains all errors related to CslLineConstructor.
+#[cfg_attr(feature = "with-defmt", derive(defmt::Format))]
 #[derive(Debug, PartialEq)]
 #[non_exhaustive]
 pub enum CslLineConstructorError {
   /// The maximum number of dimenstions has been reached
   DimsOverflow,
+  /// Duplicated innermost index in a line
+  DuplicatedIndices {
+    /// The duplicated innermost index
+    idx: usize,
+  },
+  /// The storages are full, which happened after `consumed` items were taken out of the line
+  InsufficientCapacity {
+    /// Number of items taken out of the line
+    consumed: usize,
+  },
   /// All indices must be in ascending order
   UnsortedIndices,
   /// It isn't possible to construct new elements in an empty dimension
@@ -179,6 +322,10 @@ impl fmt::Display for CslLineConstructorError {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     let s = match *self {
       Self::DimsOverflow => "DimsOverflow",
+      Self::DuplicatedIndices { idx } => return write!(f, "DuplicatedIndices ({})", idx),
+      Self::InsufficientCapacity { consumed } => {
+        return write!(f, "InsufficientCapacity ({} consumed items)", consumed);
+      }
       Self::UnsortedIndices => "UnsortedIndices",
       Self::EmptyDimension => "EmptyDimension",
       Self::MaxNumOfLines => "MaxNumOfLines",
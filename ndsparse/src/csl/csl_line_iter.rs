@@ -3,28 +3,44 @@ use core::mem;
 
 macro_rules! impl_iter {
   ($csl_iter:ident, $data_type:ty, $split_at:ident, $ref:ident) => {
-    /// Iterator of a CSL dimension.
+    /// Iterator of a CSL dimension where each item covers `chunk_size` outermost indices, the
+    /// last one possibly fewer.
     #[derive(Debug, PartialEq)]
     pub struct $csl_iter<'a, T, const D: usize> {
+      chunk_size: usize,
       curr_idx: usize,
       data: $data_type,
       dims: [usize; D],
       indcs: &'a [usize],
       max_idx: usize,
       offs: &'a [usize],
+      outermost_stride: usize,
     }
 
     impl<'a, T, const D: usize> $csl_iter<'a, T, D> {
       pub(crate) fn new(
-        mut dims: [usize; D],
+        chunk_size: usize,
+        dims: [usize; D],
+        outermost_stride: Option<usize>,
         data: $data_type,
         indcs: &'a [usize],
         offs: &'a [usize],
       ) -> crate::Result<Self> {
-        if let Some(r) = dims.first_mut() {
-          let max_idx = *r;
-          *r = 1;
-          Ok($csl_iter { curr_idx: 0, data, dims, indcs, max_idx, offs })
+        if chunk_size == 0 {
+          return Err(CslError::InvalidChunkSize.into());
+        }
+        let outermost_stride = outermost_stride.ok_or(CslError::OffsLengthOverflow)?;
+        if let Some(max_idx) = dims.first().copied() {
+          Ok($csl_iter {
+            chunk_size,
+            curr_idx: 0,
+            data,
+            dims,
+            indcs,
+            max_idx,
+            offs,
+            outermost_stride,
+          })
         } else {
           Err(CslError::InvalidIterDim.into())
         }
@@ -32,50 +48,69 @@ macro_rules! impl_iter {
 
       #[cfg(feature = "with-rayon")]
       pub(crate) fn split_at(self, idx: usize) -> [Self; 2] {
-        let cut_point = self.curr_idx + idx;
-        let [_, values] = outermost_offs(&self.dims, self.offs, self.curr_idx..cut_point);
+        let cut_point =
+          idx.saturating_mul(self.chunk_size).saturating_add(self.curr_idx).min(self.max_idx);
+        let range = self.curr_idx..cut_point;
+        let [_, values] =
+          outermost_offs(Some(self.outermost_stride), self.offs, range).unwrap_or_default();
         let (data_head, data_tail) = self.data.$split_at(values.end - values.start);
         let (indcs_head, indcs_tail) = self.indcs.split_at(values.end - values.start);
         [
           $csl_iter {
+            chunk_size: self.chunk_size,
             curr_idx: self.curr_idx,
             data: data_head,
             dims: self.dims,
             indcs: indcs_head,
             max_idx: cut_point,
             offs: self.offs,
+            outermost_stride: self.outermost_stride,
           },
           $csl_iter {
+            chunk_size: self.chunk_size,
             curr_idx: cut_point,
             data: data_tail,
             dims: self.dims,
             indcs: indcs_tail,
             max_idx: self.max_idx,
             offs: self.offs,
+            outermost_stride: self.outermost_stride,
           },
         ]
       }
+
+      fn item_dims(&self, len: usize) -> [usize; D] {
+        let mut dims = self.dims;
+        if let Some(r) = dims.first_mut() {
+          *r = len;
+        }
+        dims
+      }
     }
 
     impl<T, const D: usize> DoubleEndedIterator for $csl_iter<'_, T, D> {
       #[inline]
       fn next_back(&mut self) -> Option<Self::Item> {
-        if self.curr_idx == 0 {
-          return None;
-        }
-        let range = self.curr_idx - 1..self.curr_idx;
-        self.curr_idx -= 1;
-        let [indcs, values] = outermost_offs(&self.dims, self.offs, range);
+        let remaining = self.max_idx.checked_sub(self.curr_idx).filter(|&n| n > 0)?;
+        let last_len = match remaining % self.chunk_size {
+          0 => self.chunk_size,
+          n => n,
+        };
+        let range = self.max_idx - last_len..self.max_idx;
+        self.max_idx -= last_len;
+        let [indcs, values] = outermost_offs(Some(self.outermost_stride), self.offs, range)?;
+        let nnz = values.end - values.start;
         let data = mem::take(&mut self.data);
-        let (data_head, data_tail) = data.$split_at(values.end - values.start);
-        let (indcs_head, indcs_tail) = self.indcs.split_at(values.end - values.start);
-        self.data = data_tail;
-        self.indcs = indcs_tail;
+        let (data_head, data_tail) = data.$split_at(data.len().checked_sub(nnz)?);
+        let (indcs_head, indcs_tail) = self.indcs.split_at(self.indcs.len().checked_sub(nnz)?);
+        self.data = data_head;
+        self.indcs = indcs_head;
         Some($ref {
-          data: data_head,
-          dims: self.dims,
-          indcs: indcs_head,
+          data: data_tail,
+          dims: self.item_dims(last_len),
+          indcs: indcs_tail,
           offs: self.offs.get(indcs)?,
+          outermost_stride: Some(self.outermost_stride),
         })
       }
     }
@@ -90,9 +125,11 @@ macro_rules! impl_iter {
         if self.curr_idx >= self.max_idx {
           return None;
         }
-        let range = self.curr_idx..self.curr_idx + 1;
-        self.curr_idx += 1;
-        let [indcs, values] = outermost_offs(&self.dims, self.offs, range);
+        let end = self.curr_idx.saturating_add(self.chunk_size).min(self.max_idx);
+        let range = self.curr_idx..end;
+        let len = end - self.curr_idx;
+        self.curr_idx = end;
+        let [indcs, values] = outermost_offs(Some(self.outermost_stride), self.offs, range)?;
         let data = mem::take(&mut self.data);
         let (data_head, data_tail) = data.$split_at(values.end - values.start);
         let (indcs_head, indcs_tail) = self.indcs.split_at(values.end - values.start);
@@ -100,15 +137,18 @@ macro_rules! impl_iter {
         self.indcs = indcs_tail;
         Some($ref {
           data: data_head,
-          dims: self.dims,
+          dims: self.item_dims(len),
           indcs: indcs_head,
           offs: self.offs.get(indcs)?,
+          outermost_stride: Some(self.outermost_stride),
         })
       }
 
       #[inline]
       fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.max_idx, Some(self.max_idx))
+        let remaining = self.max_idx.saturating_sub(self.curr_idx);
+        let len = remaining / self.chunk_size + usize::from(remaining % self.chunk_size != 0);
+        (len, Some(len))
       }
     }
   };
@@ -0,0 +1,89 @@
+use crate::csl::{line_ranges, try_line_idx, CslError, CslRef};
+use alloc::{vec, vec::Vec};
+
+const WORD_BITS: usize = 64;
+
+/// Read-only view of a CSL with a direct-mapped bitset for every line, which replaces the binary
+/// searches of [`value`](crate::csl::Csl#method.value) by a constant number of operations.
+///
+/// Each line stores one bit for every position of the innermost dimension plus the number of
+/// preceding elements for each group of 64 positions, i.e., around two bits for each position of
+/// the tensor. Because of that, the structure is only worth it for lookup-heavy workloads over
+/// structures with a moderate number of lines, like embedding tables.
+///
+/// Created by [`build_lookup`](crate::csl::Csl#method.build_lookup).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CslWithLookup<'a, DATA, const D: usize> {
+  csl: CslRef<'a, DATA, D>,
+  ranks: Vec<usize>,
+  words: Vec<u64>,
+  words_per_line: usize,
+}
+
+impl<'a, DATA, const D: usize> CslWithLookup<'a, DATA, D> {
+  #[inline]
+  pub(crate) fn new(csl: CslRef<'a, DATA, D>) -> crate::Result<Self> {
+    let innermost_len = csl.dims.last().copied().unwrap_or_default();
+    let words_per_line = innermost_len / WORD_BITS + usize::from(innermost_len % WORD_BITS != 0);
+    let lines = csl.offs.len().saturating_sub(1);
+    let len = lines.checked_mul(words_per_line).ok_or(CslError::DimsProductOverflow)?;
+    let mut ranks = vec![0; len];
+    let mut words = vec![0u64; len];
+    for (line, range) in line_ranges(csl.offs).enumerate() {
+      let line_words = line * words_per_line..(line + 1) * words_per_line;
+      let line_ranks = ranks.get_mut(line_words.clone()).unwrap_or_default();
+      let line_words = words.get_mut(line_words).unwrap_or_default();
+      for &idx in csl.indcs.get(range.clone()).unwrap_or_default() {
+        if let Some(word) = line_words.get_mut(idx / WORD_BITS) {
+          *word |= 1 << (idx % WORD_BITS);
+        }
+      }
+      let mut preceding = range.start;
+      for (rank, word) in line_ranks.iter_mut().zip(line_words.iter()) {
+        *rank = preceding;
+        preceding += word.count_ones() as usize;
+      }
+    }
+    Ok(Self { csl, ranks, words, words_per_line })
+  }
+
+  /// The underlying structure.
+  #[inline]
+  pub fn csl(&self) -> &CslRef<'a, DATA, D> {
+    &self.csl
+  }
+
+  /// Retrieves an immutable reference of a single data value in constant time.
+  ///
+  /// # Arguments
+  ///
+  /// * `indcs`: Indices of all dimensions
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let lookup = csl.build_lookup().unwrap();
+  /// assert_eq!(lookup.value([1, 0, 2, 2]), Some(&9));
+  /// assert_eq!(lookup.value([0, 0, 3, 3]), Some(&4));
+  /// assert_eq!(lookup.value([0, 0, 3, 2]), None);
+  /// assert_eq!(lookup.value([0, 0, 3, 5]), None);
+  /// ```
+  #[inline]
+  pub fn value(&self, indcs: [usize; D]) -> Option<&'a DATA> {
+    let innermost_idx = *indcs.last()?;
+    if innermost_idx >= *self.csl.dims.last()? {
+      return None;
+    }
+    let line = try_line_idx(&self.csl.dims, self.csl.outermost_stride, &indcs).ok()?;
+    let word_idx = line.checked_mul(self.words_per_line)?.checked_add(innermost_idx / WORD_BITS)?;
+    let word = *self.words.get(word_idx)?;
+    let bit = innermost_idx % WORD_BITS;
+    if word & (1 << bit) == 0 {
+      return None;
+    }
+    let preceding = (word & ((1 << bit) - 1)).count_ones() as usize;
+    self.csl.data.get(self.ranks.get(word_idx)? + preceding)
+  }
+}
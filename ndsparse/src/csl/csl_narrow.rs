@@ -0,0 +1,100 @@
+use crate::csl::{Csl, CslError};
+use alloc::vec::Vec;
+use cl_traits::Storage;
+use core::convert::TryFrom;
+
+impl<DATA, DS, IS, OS, const D: usize> Csl<DS, IS, OS, D>
+where
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  /// Re-encodes indices and offsets into a smaller integer type like `u32`, which can reduce
+  /// the memory used by structures with many elements. Data is moved without copying.
+  ///
+  /// Methods that read indices require `usize` storages, therefore, narrowed structures are
+  /// meant to be stored or transferred and should be converted back with
+  /// [`widen_indices`](#method.widen_indices) before being used.
+  ///
+  /// Returns [`CslError::IndexOverflow`] if the innermost dimension or the last offset doesn't
+  /// fit in `I`.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::{CslError, CslVec};
+  /// let csl = CslVec::new([2, 300], vec![1, 2, 3], vec![0, 299, 1], vec![0, 2, 3])?;
+  /// let narrowed = csl.clone().narrow_indices::<u16>()?;
+  /// assert_eq!(narrowed.widen_indices()?, csl);
+  /// let err = csl.narrow_indices::<u8>();
+  /// assert_eq!(err.err(), Some(ndsparse::Error::Csl(CslError::IndexOverflow)));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn narrow_indices<I>(self) -> crate::Result<Csl<DS, Vec<I>, Vec<I>, D>>
+  where
+    I: TryFrom<usize>,
+  {
+    let dim_len = self.dims.last().copied().unwrap_or_default();
+    let last_off = self.offs.as_ref().last().copied().unwrap_or_default();
+    if I::try_from(dim_len.saturating_sub(1)).is_err() || I::try_from(last_off).is_err() {
+      return Err(CslError::IndexOverflow.into());
+    }
+    let indcs = convert_all(self.indcs.as_ref())?;
+    let offs = convert_all(self.offs.as_ref())?;
+    Ok(Csl {
+      data: self.data,
+      dims: self.dims,
+      indcs,
+      offs,
+      outermost_stride: self.outermost_stride,
+    })
+  }
+}
+
+impl<DS, IS, OS, const D: usize> Csl<DS, IS, OS, D> {
+  /// Converts indices and offsets of any integer type back into `usize` storages. See
+  /// [`narrow_indices`](#method.narrow_indices).
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslArray;
+  /// let csl = CslArray::new([2, 3], [1, 2, 3], [0, 2, 1], [0, 2, 3])?;
+  /// let narrowed = csl.narrow_indices::<u32>()?;
+  /// assert_eq!(narrowed.widen_indices()?.value([0, 2]), Some(&2));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn widen_indices<I>(self) -> crate::Result<Csl<DS, Vec<usize>, Vec<usize>, D>>
+  where
+    I: Copy,
+    IS: AsRef<[I]>,
+    OS: AsRef<[I]>,
+    usize: TryFrom<I>,
+  {
+    let indcs = convert_all(self.indcs.as_ref())?;
+    let offs = convert_all(self.offs.as_ref())?;
+    Ok(Csl {
+      data: self.data,
+      dims: self.dims,
+      indcs,
+      offs,
+      outermost_stride: self.outermost_stride,
+    })
+  }
+}
+
+fn convert_all<A, B>(slice: &[A]) -> crate::Result<Vec<B>>
+where
+  A: Copy,
+  B: TryFrom<A>,
+{
+  let mut rslt = Vec::with_capacity(slice.len());
+  for elem in slice.iter().copied() {
+    rslt.push(B::try_from(elem).map_err(|_err| CslError::IndexOverflow)?);
+  }
+  Ok(rslt)
+}
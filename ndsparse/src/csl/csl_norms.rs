@@ -0,0 +1,331 @@
+use crate::csl::{line_ranges, Csl};
+use cl_traits::Storage;
+use core::ops::{Add, Div, Mul};
+
+/// Norm applied to each line by [`normalize_lines`](Csl#method.normalize_lines).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Norm {
+  /// Sum of absolute values
+  L1,
+  /// Square root of the sum of squares
+  #[cfg(feature = "std")]
+  L2,
+  /// Greatest absolute value
+  Max,
+}
+
+/// Floating-point types that can be used to compute norms and other reductions.
+pub trait Real:
+  Add<Output = Self> + Copy + Default + Div<Output = Self> + Mul<Output = Self> + PartialOrd
+{
+  /// Type used to accumulate intermediate results. Half-precision types accumulate in `f32` to
+  /// avoid the rounding errors of long sums.
+  type Acc: Real;
+
+  /// Absolute value.
+  fn abs(self) -> Self;
+
+  /// Converts an accumulated value back into `Self`.
+  fn from_acc(acc: Self::Acc) -> Self;
+
+  /// Square root.
+  #[cfg(feature = "std")]
+  fn sqrt(self) -> Self;
+
+  /// Converts `self` into the accumulator type.
+  fn to_acc(self) -> Self::Acc;
+}
+
+macro_rules! impl_real {
+  ($($ty:ident),*) => {
+    $(
+      impl Real for $ty {
+        type Acc = Self;
+
+        #[inline]
+        fn abs(self) -> Self {
+          if self < 0.0 {
+            -self
+          } else {
+            self
+          }
+        }
+
+        #[inline]
+        fn from_acc(acc: Self::Acc) -> Self {
+          acc
+        }
+
+        #[cfg(feature = "std")]
+        #[inline]
+        fn sqrt(self) -> Self {
+          $ty::sqrt(self)
+        }
+
+        #[inline]
+        fn to_acc(self) -> Self::Acc {
+          self
+        }
+      }
+    )*
+  };
+}
+
+impl_real!(f32, f64);
+
+#[cfg(feature = "with-half")]
+macro_rules! impl_half_real {
+  ($($ty:ty),*) => {
+    $(
+      impl Real for $ty {
+        type Acc = f32;
+
+        // Both formats store the sign in the most significant bit.
+        #[inline]
+        fn abs(self) -> Self {
+          Self::from_bits(self.to_bits() & 0x7fff)
+        }
+
+        #[inline]
+        fn from_acc(acc: Self::Acc) -> Self {
+          Self::from_f32(acc)
+        }
+
+        #[cfg(feature = "std")]
+        #[inline]
+        fn sqrt(self) -> Self {
+          Self::from_f32(self.to_f32().sqrt())
+        }
+
+        #[inline]
+        fn to_acc(self) -> Self::Acc {
+          self.to_f32()
+        }
+      }
+    )*
+  };
+}
+
+#[cfg(feature = "with-half")]
+impl_half_real!(half::bf16, half::f16);
+
+/// Real or complex numbers supported by reductions and arithmetic operations like
+/// [`spmv`](crate::csl::ops::spmv). Every [`Real`] is also a `Scalar`.
+pub trait Scalar: Add<Output = Self> + Copy + Default + Mul<Output = Self> {
+  /// Type used to accumulate sums and products.
+  type Acc: Add<Output = Self::Acc> + Copy + Default + Mul<Output = Self::Acc>;
+
+  /// Type of the magnitude returned by [`modulus`](#tymethod.modulus).
+  type Real: Real;
+
+  /// Complex conjugate, which is `self` for real numbers.
+  fn conj(self) -> Self;
+
+  /// Absolute value of real numbers or modulus of complex numbers.
+  fn modulus(self) -> Self::Real;
+
+  /// Converts an accumulated value back into `Self`.
+  fn narrow(acc: Self::Acc) -> Self;
+
+  /// Converts `self` into the accumulator type.
+  fn widen(self) -> Self::Acc;
+}
+
+impl<T> Scalar for T
+where
+  T: Real,
+{
+  type Acc = T::Acc;
+  type Real = T;
+
+  #[inline]
+  fn conj(self) -> Self {
+    self
+  }
+
+  #[inline]
+  fn modulus(self) -> Self::Real {
+    self.abs()
+  }
+
+  #[inline]
+  fn narrow(acc: Self::Acc) -> Self {
+    T::from_acc(acc)
+  }
+
+  #[inline]
+  fn widen(self) -> Self::Acc {
+    self.to_acc()
+  }
+}
+
+#[cfg(feature = "with-num-complex")]
+macro_rules! impl_complex_scalar {
+  ($($ty:ident),*) => {
+    $(
+      impl Scalar for num_complex::Complex<$ty> {
+        type Acc = Self;
+        type Real = $ty;
+
+        #[inline]
+        fn conj(self) -> Self {
+          num_complex::Complex::conj(&self)
+        }
+
+        #[inline]
+        fn modulus(self) -> Self::Real {
+          self.norm()
+        }
+
+        #[inline]
+        fn narrow(acc: Self::Acc) -> Self {
+          acc
+        }
+
+        #[inline]
+        fn widen(self) -> Self::Acc {
+          self
+        }
+      }
+    )*
+  };
+}
+
+#[cfg(feature = "with-num-complex")]
+impl_complex_scalar!(f32, f64);
+
+impl<DATA, DS, IS, OS, const D: usize> Csl<DS, IS, OS, D>
+where
+  DATA: Scalar,
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  /// Sum of the absolute values of all stored elements.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::csl::CslArray;
+  /// let csl = CslArray::new([2, 3], [3.0, -4.0], [0, 2], [0, 2, 2]).unwrap();
+  /// assert_eq!(csl.norm_l1(), 7.0);
+  /// ```
+  #[inline]
+  pub fn norm_l1(&self) -> DATA::Real {
+    DATA::Real::from_acc(norm(Norm::L1, self.data.as_ref()))
+  }
+
+  /// Square root of the sum of the squares of all stored elements.
+  ///
+  /// # Example
+  #[cfg_attr(feature = "std", doc = "```rust")]
+  #[cfg_attr(not(feature = "std"), doc = "```ignore")]
+  /// use ndsparse::csl::CslArray;
+  /// let csl = CslArray::new([2, 3], [3.0, -4.0], [0, 2], [0, 2, 2]).unwrap();
+  /// assert_eq!(csl.norm_l2(), 5.0);
+  /// ```
+  ///
+  /// Complex elements contribute with their moduli.
+  #[cfg_attr(feature = "with-num-complex", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-num-complex"), doc = "```ignore")]
+  /// use ndsparse::csl::CslArray;
+  /// use num_complex::Complex;
+  /// let data = [Complex::new(3.0, 4.0), Complex::new(0.0, -12.0)];
+  /// let csl = CslArray::new([2, 3], data, [0, 2], [0, 2, 2]).unwrap();
+  /// assert_eq!(csl.norm_l2(), 13.0);
+  /// ```
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn norm_l2(&self) -> DATA::Real {
+    DATA::Real::from_acc(norm(Norm::L2, self.data.as_ref()))
+  }
+
+  /// Greatest absolute value of all stored elements or zero if there are none.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::csl::CslArray;
+  /// let csl = CslArray::new([2, 3], [3.0, -4.0], [0, 2], [0, 2, 2]).unwrap();
+  /// assert_eq!(csl.norm_max(), 4.0);
+  /// ```
+  #[inline]
+  pub fn norm_max(&self) -> DATA::Real {
+    DATA::Real::from_acc(norm(Norm::Max, self.data.as_ref()))
+  }
+
+  /// Sum of all stored elements, which is accumulated in [`Scalar::Acc`].
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::csl::CslArray;
+  /// let csl = CslArray::new([2, 3], [3.0, -4.0, 2.5], [0, 2, 1], [0, 2, 3]).unwrap();
+  /// assert_eq!(csl.sum(), 1.5);
+  /// ```
+  ///
+  /// Half-precision values are summed in `f32`, i.e., a sum of ones doesn't stop at 2048.
+  #[cfg_attr(all(feature = "alloc", feature = "with-half"), doc = "```rust")]
+  #[cfg_attr(not(all(feature = "alloc", feature = "with-half")), doc = "```ignore")]
+  /// use half::f16;
+  /// use ndsparse::csl::CslVec;
+  /// let csl = CslVec::new([4096], vec![f16::ONE; 4096], (0..4096).collect(), vec![0, 4096]);
+  /// assert_eq!(csl.unwrap().sum(), f16::from_f32(4096.0));
+  /// ```
+  #[inline]
+  pub fn sum(&self) -> DATA {
+    DATA::narrow(
+      self.data.as_ref().iter().fold(DATA::Acc::default(), |acc, &elem| acc + elem.widen()),
+    )
+  }
+}
+
+impl<DATA, DS, IS, OS, const D: usize> Csl<DS, IS, OS, D>
+where
+  DATA: Real,
+  DS: AsMut<[DATA]> + AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  /// Scales the elements of each innermost line so that the line has an unit `norm`. Lines
+  /// without elements or whose norm is zero are untouched.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::csl::{CslArray, Norm};
+  /// let mut csl = CslArray::new([2, 3], [1.0, -3.0, 2.0], [0, 2, 1], [0, 2, 3]).unwrap();
+  /// csl.normalize_lines(Norm::L1);
+  /// assert_eq!(csl.data(), &[0.25, -0.75, 1.0]);
+  /// csl.normalize_lines(Norm::Max);
+  /// assert_eq!(csl.data(), &[1.0 / 3.0, -1.0, 1.0]);
+  /// ```
+  #[inline]
+  pub fn normalize_lines(&mut self, norm_kind: Norm) {
+    let data = self.data.as_mut();
+    for range in line_ranges(self.offs.as_ref()) {
+      if let Some(line) = data.get_mut(range) {
+        let line_norm = norm(norm_kind, line);
+        if line_norm != DATA::Acc::default() {
+          line.iter_mut().for_each(|elem| *elem = DATA::from_acc(elem.to_acc() / line_norm));
+        }
+      }
+    }
+  }
+}
+
+#[inline]
+fn norm<DATA>(norm_kind: Norm, data: &[DATA]) -> <DATA::Real as Real>::Acc
+where
+  DATA: Scalar,
+{
+  let iter = data.iter().map(|elem| elem.modulus().to_acc());
+  let zero = <DATA::Real as Real>::Acc::default();
+  match norm_kind {
+    Norm::L1 => iter.fold(zero, |acc, elem| acc + elem),
+    #[cfg(feature = "std")]
+    Norm::L2 => iter.fold(zero, |acc, elem| acc + elem * elem).sqrt(),
+    Norm::Max => iter.fold(zero, |acc, elem| if elem > acc { elem } else { acc }),
+  }
+}
@@ -0,0 +1,163 @@
+use crate::csl::Csl;
+#[cfg(feature = "alloc")]
+use crate::{
+  coo::CooVec,
+  csl::{line_ranges, outermost_indcs, rebased_offs, CslVec},
+};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use cl_traits::Storage;
+
+/// Defines which axis is compressed into lines.
+#[cfg_attr(feature = "with-defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "with-serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Order {
+  /// Lines run along the first axis, e.g., the columns of a CSC matrix. The underlying structure
+  /// stores all axes in reverse order.
+  ColMajor,
+  /// Lines run along the last axis, e.g., the rows of a CSR matrix. The underlying structure
+  /// stores all axes in the logical order.
+  RowMajor,
+}
+
+impl Default for Order {
+  #[inline]
+  fn default() -> Self {
+    Self::RowMajor
+  }
+}
+
+/// CSL whose lines can run along the first or the last logical axis, which allows CSC-like
+/// structures without permuting every access by hand.
+///
+/// Indices and dimensions given to or returned by this structure are always in logical order
+/// while [`csl`](#method.csl) exposes the stored order.
+///
+/// # Example
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::csl::{CslVec, CslWithOrder, Order};
+/// // [1, _, 2]
+/// // [_, 3, _]
+/// let csl = CslVec::new([2, 3], vec![1, 2, 3], vec![0, 2, 1], vec![0, 2, 3])?;
+/// let csr = CslWithOrder::new(csl);
+/// let csc = csr.to_order(Order::ColMajor)?;
+/// assert_eq!(csc.dims(), [2, 3]);
+/// assert_eq!(csc.csl().dims(), &[3, 2]);
+/// assert_eq!(csc.csl().data(), &[1, 3, 2]);
+/// assert_eq!(csc.csl().offs(), &[0, 1, 2, 3]);
+/// assert_eq!(csc.value([1, 1]), Some(&3));
+/// assert_eq!(csc.to_order(Order::RowMajor)?, csr);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CslWithOrder<DS, IS, OS, const D: usize> {
+  csl: Csl<DS, IS, OS, D>,
+  order: Order,
+}
+
+impl<DS, IS, OS, const D: usize> CslWithOrder<DS, IS, OS, D> {
+  /// Wraps a structure whose lines run along the last logical axis.
+  #[inline]
+  pub fn new(csl: Csl<DS, IS, OS, D>) -> Self {
+    Self { csl, order: Order::RowMajor }
+  }
+
+  /// Wraps a structure that is already stored in `order`, i.e., the axes of `csl` must be
+  /// reversed for [`Order::ColMajor`].
+  #[inline]
+  pub fn with_order(csl: Csl<DS, IS, OS, D>, order: Order) -> Self {
+    Self { csl, order }
+  }
+
+  /// The underlying structure in the stored order.
+  #[inline]
+  pub fn csl(&self) -> &Csl<DS, IS, OS, D> {
+    &self.csl
+  }
+
+  /// The logical definitions of all dimensions.
+  #[inline]
+  pub fn dims(&self) -> [usize; D] {
+    self.logical(self.csl.dims)
+  }
+
+  /// Unwraps the underlying structure in the stored order.
+  #[inline]
+  pub fn into_csl(self) -> Csl<DS, IS, OS, D> {
+    self.csl
+  }
+
+  /// The current order.
+  #[inline]
+  pub fn order(&self) -> Order {
+    self.order
+  }
+
+  // Reversing is an involution, so the same method converts stored indices into logical indices
+  // and logical indices into stored indices.
+  fn logical(&self, mut indcs: [usize; D]) -> [usize; D] {
+    if self.order == Order::ColMajor {
+      indcs.reverse();
+    }
+    indcs
+  }
+}
+
+impl<DATA, DS, IS, OS, const D: usize> CslWithOrder<DS, IS, OS, D>
+where
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  /// Copies all elements into a new structure stored in `order`. The lines of the new
+  /// structure are rebuilt when `order` is different than the current order.
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn to_order(
+    &self,
+    order: Order,
+  ) -> crate::Result<CslWithOrder<Vec<DATA>, Vec<usize>, Vec<usize>, D>>
+  where
+    DATA: Clone,
+  {
+    let dims = self.csl.dims;
+    let data = self.csl.data.as_ref();
+    let indcs = self.csl.indcs.as_ref();
+    if order == self.order {
+      let offs = rebased_offs(self.csl.offs.as_ref())?;
+      let outermost_stride = self.csl.outermost_stride;
+      let csl = CslVec { data: data.to_vec(), dims, indcs: indcs.to_vec(), offs, outermost_stride };
+      return Ok(CslWithOrder { csl, order });
+    }
+    let mut triplets = Vec::with_capacity(data.len());
+    for (line, range) in line_ranges(self.csl.offs.as_ref()).enumerate() {
+      let line_indcs = indcs.get(range.clone()).unwrap_or_default();
+      for (&idx, value) in line_indcs.iter().zip(data.get(range).unwrap_or_default()) {
+        let mut elem_indcs = outermost_indcs(&dims, line);
+        if let Some(last) = elem_indcs.last_mut() {
+          *last = idx;
+        }
+        elem_indcs.reverse();
+        triplets.push((elem_indcs, value.clone()));
+      }
+    }
+    triplets.sort_by_key(|elem| elem.0);
+    let mut reversed_dims = dims;
+    reversed_dims.reverse();
+    let csl = CooVec::new(reversed_dims, triplets)?.to_csl()?;
+    Ok(CslWithOrder { csl, order })
+  }
+
+  /// Retrieves an immutable reference of a single data value.
+  ///
+  /// # Arguments
+  ///
+  /// * `indcs`: Logical indices of all dimensions
+  #[inline]
+  pub fn value(&self, indcs: [usize; D]) -> Option<&DATA> {
+    self.csl.value(self.logical(indcs))
+  }
+}
@@ -0,0 +1,114 @@
+use crate::csl::{correct_offs_len, CslVec};
+use alloc::{boxed::Box, vec::Vec};
+use quickcheck::{Arbitrary, Gen};
+
+impl<DATA, const D: usize> Arbitrary for CslVec<DATA, D>
+where
+  DATA: Arbitrary,
+{
+  #[inline]
+  fn arbitrary(g: &mut Gen) -> Self {
+    let dims = crate::utils::quickcheck_dims(g);
+    let lines = correct_offs_len(&dims).unwrap_or(1).saturating_sub(1);
+    let innermost_len = dims.last().copied().unwrap_or_default();
+    let mut data = Vec::new();
+    let mut indcs = Vec::new();
+    let mut offs = Vec::with_capacity(lines.saturating_add(1));
+    offs.push(0);
+    for _ in 0..lines {
+      for innermost_idx in 0..innermost_len {
+        if bool::arbitrary(g) {
+          data.push(DATA::arbitrary(g));
+          indcs.push(innermost_idx);
+        }
+      }
+      offs.push(data.len());
+    }
+    Self::new(dims, data, indcs, offs).unwrap_or_default()
+  }
+
+  /// Candidates are yielded from the most aggressive to the least aggressive, i.e., halves of
+  /// the outermost dimension, instances without the first or the last outermost index,
+  /// instances without one of the stored elements, a shorter innermost dimension and finally
+  /// instances where a single value is shrunk.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::{csl::CslVec, fixtures::csl_vec_4};
+  /// use quickcheck::Arbitrary;
+  /// let csl = csl_vec_4();
+  /// let mut candidates = csl.shrink();
+  /// assert_eq!(candidates.next().map(|elem| *elem.dims()), Some([1, 3, 4, 5]));
+  /// assert!(candidates.all(|elem| elem.validate().is_ok() && elem.nnz() <= csl.nnz()));
+  /// ```
+  #[inline]
+  fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+    let outermost_len = if D > 1 { self.dims.first().copied().unwrap_or_default() } else { 0 };
+    let mut ranges = Vec::new();
+    if outermost_len > 1 {
+      let half = outermost_len / 2;
+      ranges.extend([0..half, half..outermost_len]);
+    }
+    if outermost_len > 2 {
+      ranges.extend([0..outermost_len - 1, 1..outermost_len]);
+    }
+    let this = self.clone();
+    let outermost =
+      ranges.into_iter().filter_map(move |range| this.sub_dim::<D>(range)?.to_vec_csl().ok());
+
+    let this = self.clone();
+    let elements = (0..self.data.len()).map(move |idx| remove_element(&this, idx));
+
+    let this = self.clone();
+    let innermost = shorter_innermost_dim(self).into_iter().map(move |innermost_len| {
+      let mut csl = this.clone();
+      if let Some(r) = csl.dims.last_mut() {
+        *r = innermost_len;
+      }
+      csl
+    });
+
+    let this = self.clone();
+    let values = (0..self.data.len()).flat_map(move |idx| {
+      let this = this.clone();
+      let value = this.data.get(idx).cloned();
+      value.into_iter().flat_map(|value| value.shrink()).map(move |value| {
+        let mut csl = this.clone();
+        if let Some(r) = csl.data.get_mut(idx) {
+          *r = value;
+        }
+        csl
+      })
+    });
+
+    Box::new(outermost.chain(elements).chain(innermost).chain(values))
+  }
+}
+
+fn remove_element<DATA, const D: usize>(csl: &CslVec<DATA, D>, idx: usize) -> CslVec<DATA, D>
+where
+  DATA: Clone,
+{
+  let mut rslt = csl.clone();
+  if idx < rslt.data.len() {
+    let _ = rslt.data.remove(idx);
+    let _ = rslt.indcs.remove(idx);
+  }
+  let first_off = csl.offs.first().copied().unwrap_or_default();
+  for off in rslt.offs.iter_mut().filter(|off| **off > first_off.saturating_add(idx)) {
+    *off -= 1;
+  }
+  rslt
+}
+
+// Innermost dimension length that is just enough to hold all stored indices, if any.
+fn shorter_innermost_dim<DATA, const D: usize>(csl: &CslVec<DATA, D>) -> Option<usize> {
+  let innermost_len = csl.dims.last().copied()?;
+  let needed = csl.indcs.iter().copied().max().map_or(1, |idx| idx.saturating_add(1));
+  if needed < innermost_len {
+    Some(needed)
+  } else {
+    None
+  }
+}
@@ -1,11 +1,135 @@
-use crate::csl::{correct_offs_len, manage_last_offset, outermost_stride, Csl, CslError};
+use crate::csl::{correct_offs_len, manage_last_offset, outermost_indcs, Csl, CslError};
+#[cfg(feature = "with-rayon")]
+use crate::{
+  csl::{outermost_stride, CslVec},
+  utils::XorShift64,
+};
+#[cfg(feature = "with-rayon")]
+use alloc::vec::Vec;
 use cl_traits::{Push, Storage};
-use core::cmp::Ordering;
+use core::{cmp::Ordering, ops::Range};
 use rand::{
-  distributions::{Distribution, Uniform},
+  distributions::{Bernoulli, Distribution, Uniform},
   Rng,
 };
 
+// Innermost indices are sampled at most this number of times for each requested element, which
+// avoids endless loops with distributions that rarely or never produce some indices.
+const MAX_ATTEMPTS_PER_ELEMENT: usize = 32;
+
+/// Distribution of the number of elements of each line.
+#[derive(Clone, Copy, Debug)]
+pub enum LineNnzDistribution {
+  /// Number of successes of `trials` independent experiments
+  Binomial {
+    /// Outcome of each experiment
+    bernoulli: Bernoulli,
+    /// Number of experiments
+    trials: usize,
+  },
+  /// All numbers of a range are equally likely
+  Uniform(Uniform<usize>),
+  /// Heavy-tailed distribution where few lines store most elements
+  #[cfg(feature = "with-rand-distr")]
+  Zipf(rand_distr::Zipf<f64>),
+}
+
+impl LineNnzDistribution {
+  /// Number of successes of `trials` independent experiments that succeed with `probability`.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::csl::LineNnzDistribution;
+  /// assert!(LineNnzDistribution::binomial(4, 0.5).is_ok());
+  /// assert!(LineNnzDistribution::binomial(4, -0.5).is_err());
+  /// ```
+  #[inline]
+  pub fn binomial(trials: usize, probability: f64) -> crate::Result<Self> {
+    let bernoulli = Bernoulli::new(probability).map_err(|_err| CslError::InvalidDistribution)?;
+    Ok(Self::Binomial { bernoulli, trials })
+  }
+
+  /// All numbers of `range` are equally likely.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::csl::LineNnzDistribution;
+  /// assert!(LineNnzDistribution::uniform(0..4).is_ok());
+  /// assert!(LineNnzDistribution::uniform(4..4).is_err());
+  /// ```
+  #[inline]
+  pub fn uniform(range: Range<usize>) -> crate::Result<Self> {
+    if range.start >= range.end {
+      return Err(CslError::InvalidDistribution.into());
+    }
+    Ok(Self::Uniform(Uniform::from(range)))
+  }
+
+  /// Zipf distribution over `1..=elements` where the probability of `k` is proportional to
+  /// `1 / k^exponent`.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::csl::LineNnzDistribution;
+  /// assert!(LineNnzDistribution::zipf(4, 1.5).is_ok());
+  /// assert!(LineNnzDistribution::zipf(0, 1.5).is_err());
+  /// ```
+  #[cfg(feature = "with-rand-distr")]
+  #[inline]
+  pub fn zipf(elements: usize, exponent: f64) -> crate::Result<Self> {
+    let rslt = rand_distr::Zipf::new(elements as u64, exponent);
+    let zipf = rslt.map_err(|_err| CslError::InvalidDistribution)?;
+    Ok(Self::Zipf(zipf))
+  }
+}
+
+impl Distribution<usize> for LineNnzDistribution {
+  #[inline]
+  fn sample<R>(&self, rng: &mut R) -> usize
+  where
+    R: Rng + ?Sized,
+  {
+    match *self {
+      Self::Binomial { bernoulli, trials } => (0..trials).filter(|_| bernoulli.sample(rng)).count(),
+      Self::Uniform(uniform) => uniform.sample(rng),
+      #[cfg(feature = "with-rand-distr")]
+      Self::Zipf(zipf) => zipf.sample(rng) as usize,
+    }
+  }
+}
+
+/// Controls how random instances are generated.
+///
+/// # Types
+///
+/// * `I`: Distribution of the innermost indices
+/// * `N`: Distribution of the number of elements of each line
+#[derive(Clone, Copy, Debug)]
+pub struct RandomConfig<I, N> {
+  innermost_idx_distribution: I,
+  line_nnz_distribution: N,
+}
+
+impl<I, N> RandomConfig<I, N>
+where
+  I: Distribution<usize>,
+  N: Distribution<usize>,
+{
+  /// # Arguments
+  ///
+  /// * `line_nnz_distribution`: Distribution of the number of elements of each line, e.g.,
+  ///   [`LineNnzDistribution`]. Samples greater than the innermost dimension length are clamped.
+  /// * `innermost_idx_distribution`: Distribution of the innermost indices. Samples that are out
+  ///   of bounds or already stored in the current line are discarded.
+  #[inline]
+  pub fn new(line_nnz_distribution: N, innermost_idx_distribution: I) -> Self {
+    Self { innermost_idx_distribution, line_nnz_distribution }
+  }
+}
+
 #[derive(Debug)]
 pub(crate) struct CslRnd<'a, DS, IS, OS, R, const D: usize> {
   csl: &'a mut Csl<DS, IS, OS, D>,
@@ -57,7 +181,7 @@ where
     let data = &mut self.csl.data;
     let indcs = self.csl.indcs.as_ref();
     let orig_dims = self.csl.dims;
-    let outermost_stride = outermost_stride(&orig_dims);
+    let outermost_stride = self.csl.outermost_stride?;
     let rng = &mut self.rng;
 
     for (line_idx, offset) in self.csl.offs.as_ref().windows(2).enumerate() {
@@ -159,3 +283,190 @@ where
     Some(idx - 1)
   }
 }
+
+/// Fills an empty `csl` line by line according to the distributions of `config`.
+#[inline]
+pub(crate) fn fill_config<DATA, DS, F, I, IS, N, OS, R, const D: usize>(
+  csl: &mut Csl<DS, IS, OS, D>,
+  rng: &mut R,
+  config: &RandomConfig<I, N>,
+  mut cb: F,
+) -> crate::Result<()>
+where
+  DS: Push<Input = DATA> + Storage<Item = DATA>,
+  F: FnMut(&mut R, [usize; D]) -> DATA,
+  I: Distribution<usize>,
+  IS: AsMut<[usize]> + AsRef<[usize]> + Push<Input = usize>,
+  N: Distribution<usize>,
+  OS: Push<Input = usize>,
+  R: Rng,
+{
+  let _ = csl.offs.push(0).map_err(|_err| crate::Error::InsufficientCapacity)?;
+  let lines = correct_offs_len(&csl.dims)? - 1;
+  let innermost_len = csl.dims.last().copied().unwrap_or_default();
+  let mut nnz = 0;
+  for line in 0..lines {
+    let line_nnz = config.line_nnz_distribution.sample(rng).min(innermost_len);
+    let mut attempts = line_nnz.saturating_mul(MAX_ATTEMPTS_PER_ELEMENT);
+    let mut pushed = 0;
+    while pushed < line_nnz && attempts > 0 {
+      attempts -= 1;
+      let innermost_idx = config.innermost_idx_distribution.sample(rng);
+      let line_indcs = csl.indcs.as_ref().get(nnz..).unwrap_or_default();
+      if innermost_idx >= innermost_len || line_indcs.contains(&innermost_idx) {
+        continue;
+      }
+      let _ = csl.indcs.push(innermost_idx).map_err(|_err| crate::Error::InsufficientCapacity)?;
+      pushed += 1;
+    }
+    csl.indcs.as_mut().get_mut(nnz..).unwrap_or_default().sort_unstable();
+    let mut indcs = outermost_indcs(&csl.dims, line);
+    for &innermost_idx in csl.indcs.as_ref().get(nnz..).unwrap_or_default() {
+      if let Some(last) = indcs.last_mut() {
+        *last = innermost_idx;
+      }
+      let _ = csl.data.push(cb(rng, indcs)).map_err(|_err| crate::Error::InsufficientCapacity)?;
+    }
+    nnz += pushed;
+    let _ = csl.offs.push(nnz).map_err(|_err| crate::Error::InsufficientCapacity)?;
+  }
+  Ok(())
+}
+
+/// Fills an empty `csl` line by line. `pattern` receives the index of the second innermost
+/// dimension of each line and returns the range of innermost indices that can be stored along
+/// with how many of them should be picked.
+#[inline]
+pub(crate) fn fill_pattern<DATA, DS, F, IS, OS, P, R, const D: usize>(
+  csl: &mut Csl<DS, IS, OS, D>,
+  rng: &mut R,
+  mut pattern: P,
+  mut cb: F,
+) -> crate::Result<()>
+where
+  DS: Push<Input = DATA> + Storage<Item = DATA>,
+  F: FnMut(&mut R, [usize; D]) -> DATA,
+  IS: Push<Input = usize>,
+  OS: Push<Input = usize>,
+  P: FnMut(&mut R, usize) -> (Range<usize>, usize),
+  R: Rng,
+{
+  let _ = csl.offs.push(0).map_err(|_err| crate::Error::InsufficientCapacity)?;
+  let lines = correct_offs_len(&csl.dims)? - 1;
+  let innermost_len = csl.dims.last().copied().unwrap_or_default();
+  let is_empty = crate::utils::max_nnz(&csl.dims) == 0;
+  let mut nnz = 0;
+  for line in 0..lines {
+    let mut indcs = outermost_indcs(&csl.dims, line);
+    if !is_empty {
+      let row = if D > 1 { indcs.get(D - 2).copied().unwrap_or_default() } else { 0 };
+      let (range, line_nnz) = pattern(rng, row);
+      let range = range.start.min(innermost_len)..range.end.min(innermost_len);
+      let mut needed = line_nnz.min(range.len());
+      let mut remaining = range.len();
+      // Selection sampling, which picks `needed` elements in ascending order
+      for innermost_idx in range {
+        if needed == 0 {
+          break;
+        }
+        if rng.gen_range(0..remaining) < needed {
+          if let Some(last) = indcs.last_mut() {
+            *last = innermost_idx;
+          }
+          let _ =
+            csl.data.push(cb(rng, indcs)).map_err(|_err| crate::Error::InsufficientCapacity)?;
+          let _ =
+            csl.indcs.push(innermost_idx).map_err(|_err| crate::Error::InsufficientCapacity)?;
+          needed -= 1;
+          nnz += 1;
+        }
+        remaining -= 1;
+      }
+    }
+    let _ = csl.offs.push(nnz).map_err(|_err| crate::Error::InsufficientCapacity)?;
+  }
+  Ok(())
+}
+
+/// Creates a structure with `nnz` elements where every line is generated by a different thread.
+///
+/// Each line has its own generator derived from `seed` and the line index, therefore, the
+/// resulting instance doesn't depend on the number of threads. Elements are evenly distributed,
+/// i.e., each line has `nnz / lines` or `nnz / lines + 1` elements.
+#[cfg(feature = "with-rayon")]
+#[inline]
+pub(crate) fn par_fill_with_seed<DATA, const D: usize>(
+  dims: [usize; D],
+  nnz: usize,
+  seed: u64,
+) -> crate::Result<CslVec<DATA, D>>
+where
+  DATA: Send,
+  rand::distributions::Standard: Distribution<DATA>,
+{
+  use rayon::prelude::*;
+
+  if nnz > crate::utils::max_nnz(&dims) {
+    return Err(CslError::NnzGreaterThanMaximumNnz.into());
+  }
+  let lines = correct_offs_len(&dims)? - 1;
+  let innermost_len = dims.last().copied().unwrap_or_default();
+  let (quotient, remainder) =
+    nnz.checked_div(lines).zip(nnz.checked_rem(lines)).unwrap_or_default();
+  let shift = if lines == 0 { 0 } else { XorShift64::new(seed).gen_range(0..lines) };
+  let line_nnz = |line: usize| {
+    let rotated = if line >= shift { line - shift } else { line + (lines - shift) };
+    quotient + usize::from(rotated < remainder)
+  };
+
+  let mut offs = Vec::with_capacity(lines.saturating_add(1));
+  offs.push(0);
+  let mut acc = 0;
+  for line in 0..lines {
+    acc += line_nnz(line);
+    offs.push(acc);
+  }
+
+  let (indcs, data) = (0..lines)
+    .into_par_iter()
+    .flat_map_iter(|line| {
+      let mut rng = XorShift64::new(seed.wrapping_add(line as u64).wrapping_add(1));
+      let line_indcs = sorted_sample(&mut rng, innermost_len, line_nnz(line));
+      line_indcs.into_iter().map(move |innermost_idx| (innermost_idx, rng.gen()))
+    })
+    .unzip();
+  Ok(CslVec { data, dims, indcs, offs, outermost_stride: outermost_stride(&dims) })
+}
+
+// `amount` distinct indices of `0..len` in ascending order.
+#[cfg(feature = "with-rayon")]
+#[inline]
+fn sorted_sample<R>(rng: &mut R, len: usize, amount: usize) -> Vec<usize>
+where
+  R: Rng,
+{
+  let amount = amount.min(len);
+  let mut rslt = Vec::with_capacity(amount);
+  if amount.saturating_mul(2) > len {
+    // Selection sampling, which picks `amount` elements in ascending order
+    let mut needed = amount;
+    for (idx, remaining) in (0..len).zip((1..=len).rev()) {
+      if needed == 0 {
+        break;
+      }
+      if rng.gen_range(0..remaining) < needed {
+        rslt.push(idx);
+        needed -= 1;
+      }
+    }
+  } else {
+    // Rejection sampling, which is cheaper when only a small portion of `len` is picked
+    while rslt.len() < amount {
+      let missing = amount - rslt.len();
+      rslt.extend((0..missing).map(|_| rng.gen_range(0..len)));
+      rslt.sort_unstable();
+      rslt.dedup();
+    }
+  }
+  rslt
+}
@@ -0,0 +1,166 @@
+use crate::csl::{data_idx, outermost_stride, CslMut, CslRef};
+use core::marker::PhantomData;
+
+/// Dimensions known at compile time.
+///
+/// # Example
+///
+/// ```rust
+/// use ndsparse::csl::StaticDims;
+/// struct Matrix3x4;
+/// impl StaticDims<2> for Matrix3x4 {
+///   const DIMS: [usize; 2] = [3, 4];
+/// }
+/// ```
+pub trait StaticDims<const D: usize> {
+  /// Length of each dimension. None of them can be zero.
+  const DIMS: [usize; D];
+}
+
+/// CSL backed by static arrays where the dimensions are provided by `SD` at compile time.
+///
+/// Unlike [`CslArray`](crate::csl::CslArray), no dimensions array is stored and the number of
+/// stored elements, `N`, and the number of offsets, `O`, are verified against `SD::DIMS` when
+/// the program is compiled.
+///
+/// # Types
+///
+/// * `DATA`: Data
+/// * `SD`: Static Dimensions
+/// * `N`: Number of stored elements
+/// * `O`: Number of offsets, i.e., the product of all dimensions except the innermost plus 1
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::csl::{CslStatic, StaticDims};
+/// struct Matrix3x4;
+/// impl StaticDims<2> for Matrix3x4 {
+///   const DIMS: [usize; 2] = [3, 4];
+/// }
+/// let csl = CslStatic::<i32, Matrix3x4, 2, 2, 4>::new([1, 2], [0, 3], [0, 1, 1, 2])?;
+/// assert_eq!(csl.value([2, 3]), Some(&2));
+/// # Ok(()) }
+/// ```
+///
+/// Invalid parameters are compile-time errors, e.g., 3 lines need 4 offsets.
+///
+/// ```compile_fail,E0080
+/// use ndsparse::csl::{CslStatic, StaticDims};
+/// struct Matrix3x4;
+/// impl StaticDims<2> for Matrix3x4 {
+///   const DIMS: [usize; 2] = [3, 4];
+/// }
+/// let _ = CslStatic::<i32, Matrix3x4, 2, 2, 3>::new([1, 2], [0, 3], [0, 1, 2]);
+/// ```
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct CslStatic<DATA, SD, const D: usize, const N: usize, const O: usize> {
+  data: [DATA; N],
+  indcs: [usize; N],
+  offs: [usize; O],
+  phantom: PhantomData<SD>,
+}
+
+impl<DATA, SD, const D: usize, const N: usize, const O: usize> CslStatic<DATA, SD, D, N, O>
+where
+  SD: StaticDims<D>,
+{
+  const VALID_DIMS: () = {
+    assert!(D > 0, "There must be at least one dimension");
+    assert!(non_zero_product(&SD::DIMS, D) != 0, "Dimensions can't be zero or overflow");
+    assert!(N <= non_zero_product(&SD::DIMS, D), "N is greater than the maximum nnz");
+    assert!(O == non_zero_product(&SD::DIMS, D - 1) + 1, "O isn't the number of lines plus 1");
+  };
+
+  /// Creates a valid instance. Dimensions, `N` and `O` are checked at compile time while the
+  /// contents of the arrays are checked at run time.
+  ///
+  /// # Arguments
+  ///
+  /// * `data`: Data collection
+  /// * `indcs`: Indices of each data item
+  /// * `offs`: Offset of each innermost line
+  #[allow(clippy::let_unit_value)]
+  #[inline]
+  pub fn new(data: [DATA; N], indcs: [usize; N], offs: [usize; O]) -> crate::Result<Self> {
+    let _ = Self::VALID_DIMS;
+    let _ = CslRef::new(SD::DIMS, &data[..], &indcs[..], &offs[..])?;
+    Ok(Self { data, indcs, offs, phantom: PhantomData })
+  }
+
+  /// Borrows this instance as a [`CslRef`] that gives access to all reading methods.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::{CslStatic, StaticDims};
+  /// struct Vector4;
+  /// impl StaticDims<1> for Vector4 {
+  ///   const DIMS: [usize; 1] = [4];
+  /// }
+  /// let csl = CslStatic::<i32, Vector4, 1, 2, 2>::new([1, 2], [0, 3], [0, 2])?;
+  /// assert_eq!(csl.as_ref().nnz(), 2);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn as_ref(&self) -> CslRef<'_, DATA, D> {
+    let outermost_stride = outermost_stride(&SD::DIMS);
+    CslRef {
+      data: &self.data,
+      dims: SD::DIMS,
+      indcs: &self.indcs,
+      offs: &self.offs,
+      outermost_stride,
+    }
+  }
+
+  /// Mutable version of [`as_ref`](#method.as_ref).
+  #[inline]
+  pub fn as_mut(&mut self) -> CslMut<'_, DATA, D> {
+    let outermost_stride = outermost_stride(&SD::DIMS);
+    CslMut {
+      data: &mut self.data,
+      dims: SD::DIMS,
+      indcs: &self.indcs,
+      offs: &self.offs,
+      outermost_stride,
+    }
+  }
+
+  /// See [`Csl::dims`](crate::csl::Csl#method.dims).
+  #[inline]
+  pub fn dims(&self) -> [usize; D] {
+    SD::DIMS
+  }
+
+  /// See [`Csl::value`](crate::csl::Csl#method.value).
+  #[inline]
+  pub fn value(&self, indcs: [usize; D]) -> Option<&DATA> {
+    let idx = data_idx(&self.as_ref(), indcs)?;
+    self.data.get(idx)
+  }
+
+  /// See [`Csl::value_mut`](crate::csl::Csl#method.value_mut).
+  #[inline]
+  pub fn value_mut(&mut self, indcs: [usize; D]) -> Option<&mut DATA> {
+    let idx = data_idx(&self.as_ref(), indcs)?;
+    self.data.get_mut(idx)
+  }
+}
+
+// Product of the first `len` dimensions. Returns zero if any of them is zero or if the product
+// overflows.
+const fn non_zero_product(dims: &[usize], len: usize) -> usize {
+  let mut idx = 0;
+  let mut rslt: usize = 1;
+  while idx < len {
+    rslt = match rslt.checked_mul(dims[idx]) {
+      Some(elem) => elem,
+      None => return 0,
+    };
+    idx += 1;
+  }
+  rslt
+}
@@ -0,0 +1,137 @@
+use crate::csl::{line_ranges, Csl, CslError};
+#[cfg(feature = "alloc")]
+use crate::{coo::CooVec, csl::CslVec};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use cl_traits::Storage;
+
+/// Symmetric 2D structure that only stores the upper triangle, i.e., every stored innermost
+/// index is greater or equal to the index of its line.
+///
+/// Elements of the lower triangle are answered by their mirrored counterparts, which halves the
+/// memory of symmetric matrices like covariances or undirected adjacencies.
+///
+/// # Example
+#[cfg_attr(feature = "alloc", doc = "```rust")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::csl::{CslVec, SymCsl};
+/// // [1, 2, _]
+/// // [2, _, 3]
+/// // [_, 3, 4]
+/// let upper = CslVec::new([3, 3], vec![1, 2, 3, 4], vec![0, 1, 2, 2], vec![0, 2, 3, 4])?;
+/// let sym = SymCsl::new(upper)?;
+/// assert_eq!(sym.value([0, 1]), Some(&2));
+/// assert_eq!(sym.value([1, 0]), Some(&2));
+/// assert_eq!(sym.value([1, 1]), None);
+/// let full = sym.to_full()?;
+/// assert_eq!(full.data(), &[1, 2, 2, 3, 3, 4]);
+/// assert_eq!(full.indcs(), &[0, 1, 0, 2, 1, 2]);
+/// assert_eq!(SymCsl::from_full(&full)?, sym);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SymCsl<DS, IS, OS> {
+  csl: Csl<DS, IS, OS, 2>,
+}
+
+impl<DS, IS, OS> SymCsl<DS, IS, OS> {
+  /// The underlying upper triangle.
+  #[inline]
+  pub fn csl(&self) -> &Csl<DS, IS, OS, 2> {
+    &self.csl
+  }
+
+  /// Unwraps the underlying upper triangle.
+  #[inline]
+  pub fn into_csl(self) -> Csl<DS, IS, OS, 2> {
+    self.csl
+  }
+}
+
+impl<DATA, DS, IS, OS> SymCsl<DS, IS, OS>
+where
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  /// Wraps a square structure that only has elements in the upper triangle.
+  #[inline]
+  pub fn new(csl: Csl<DS, IS, OS, 2>) -> crate::Result<Self> {
+    let [rows, cols] = csl.dims;
+    if rows != cols {
+      return Err(CslError::NonSquare { cols, rows }.into());
+    }
+    let indcs = csl.indcs.as_ref();
+    for (line_idx, range) in line_ranges(csl.offs.as_ref()).enumerate() {
+      if indcs.get(range).unwrap_or_default().first().is_some_and(|&idx| idx < line_idx) {
+        return Err(CslError::LowerTriangleElement { line_idx }.into());
+      }
+    }
+    Ok(Self { csl })
+  }
+
+  /// Retrieves an immutable reference of a single data value of any triangle.
+  ///
+  /// # Arguments
+  ///
+  /// * `indcs`: Row and column indices
+  #[inline]
+  pub fn value(&self, [row, col]: [usize; 2]) -> Option<&DATA> {
+    if row <= col {
+      self.csl.value([row, col])
+    } else {
+      self.csl.value([col, row])
+    }
+  }
+
+  /// Copies both triangles into a new structure.
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub fn to_full(&self) -> crate::Result<CslVec<DATA, 2>>
+  where
+    DATA: Clone,
+  {
+    let data = self.csl.data.as_ref();
+    let indcs = self.csl.indcs.as_ref();
+    let mut triplets = Vec::with_capacity(data.len().saturating_mul(2));
+    for (row, range) in line_ranges(self.csl.offs.as_ref()).enumerate() {
+      let line_indcs = indcs.get(range.clone()).unwrap_or_default();
+      for (&col, value) in line_indcs.iter().zip(data.get(range).unwrap_or_default()) {
+        triplets.push(([row, col], value.clone()));
+        if row != col {
+          triplets.push(([col, row], value.clone()));
+        }
+      }
+    }
+    triplets.sort_by_key(|elem| elem.0);
+    CooVec::new(self.csl.dims, triplets)?.to_csl()
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<DATA> SymCsl<Vec<DATA>, Vec<usize>, Vec<usize>> {
+  /// Copies the upper triangle of a square structure. The lower triangle is ignored, therefore,
+  /// the symmetry of `csl` isn't verified.
+  #[inline]
+  pub fn from_full<DS, IS, OS>(csl: &Csl<DS, IS, OS, 2>) -> crate::Result<Self>
+  where
+    DATA: Clone,
+    DS: AsRef<[DATA]> + Storage<Item = DATA>,
+    IS: AsRef<[usize]>,
+    OS: AsRef<[usize]>,
+  {
+    let data = csl.data.as_ref();
+    let indcs = csl.indcs.as_ref();
+    let lines = line_ranges(csl.offs.as_ref()).enumerate().map(|(line_idx, range)| {
+      let line_indcs = indcs.get(range.clone()).unwrap_or_default();
+      let line_data = data.get(range).unwrap_or_default();
+      line_indcs
+        .iter()
+        .copied()
+        .zip(line_data.iter().cloned())
+        .filter(move |&(idx, _)| idx >= line_idx)
+    });
+    Self::new(CslVec::from_lines(csl.dims, lines)?)
+  }
+}
@@ -0,0 +1,139 @@
+use crate::csl::{
+  has_innermost_dims_zero, line_ranges, outermost_indcs, Csl, CslError, CslRef, CslVec, Scalar,
+};
+use alloc::{vec, vec::Vec};
+use cl_traits::Storage;
+
+/// Read-only view of a CSL with the positions of all stored elements grouped by innermost index,
+/// i.e., a transposed copy of the indices that doesn't duplicate the data.
+///
+/// Iterating over an innermost index, like a column of a CSR matrix, only visits the stored
+/// elements of that index instead of searching every line. The cache needs two `usize` for each
+/// stored element plus one `usize` for each position of the innermost dimension.
+///
+/// Created by [`build_transpose`](crate::csl::Csl#method.build_transpose).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CslWithTranspose<'a, DATA, const D: usize> {
+  csl: CslRef<'a, DATA, D>,
+  elements: Vec<(usize, usize)>,
+  offs: Vec<usize>,
+}
+
+impl<'a, DATA, const D: usize> CslWithTranspose<'a, DATA, D> {
+  #[inline]
+  pub(crate) fn new(csl: CslRef<'a, DATA, D>) -> Self {
+    let innermost_len = csl.dims.last().copied().unwrap_or_default();
+    let mut offs = vec![0; innermost_len.saturating_add(1)];
+    for &idx in csl.indcs {
+      if let Some(off) = offs.get_mut(idx.saturating_add(1)) {
+        *off += 1;
+      }
+    }
+    let mut acc = 0;
+    for off in offs.iter_mut() {
+      acc += *off;
+      *off = acc;
+    }
+    let mut cursors = offs.clone();
+    let mut elements = vec![(0, 0); acc];
+    for (line, range) in line_ranges(csl.offs).enumerate() {
+      let line_indcs = csl.indcs.get(range.clone()).unwrap_or_default();
+      for (data_idx, &idx) in range.zip(line_indcs) {
+        let cursor = if let Some(r) = cursors.get_mut(idx) { r } else { continue };
+        if let Some(elem) = elements.get_mut(*cursor) {
+          *elem = (line, data_idx);
+        }
+        *cursor += 1;
+      }
+    }
+    Self { csl, elements, offs }
+  }
+
+  /// The underlying structure.
+  #[inline]
+  pub fn csl(&self) -> &CslRef<'a, DATA, D> {
+    &self.csl
+  }
+
+  /// Cached version of [`innermost_iter`](crate::csl::Csl#method.innermost_iter).
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::fixtures::csl_array_4;
+  /// let csl = csl_array_4();
+  /// let transpose = csl.build_transpose();
+  /// let elements = transpose.innermost_iter(2).collect::<Vec<_>>();
+  /// assert_eq!(elements, [([0, 1, 0, 2], &6), ([0, 2, 0, 2], &7), ([1, 0, 2, 2], &9)]);
+  /// assert!(elements.into_iter().eq(csl.innermost_iter(2)));
+  /// ```
+  #[inline]
+  pub fn innermost_iter(&self, idx: usize) -> impl Iterator<Item = ([usize; D], &'a DATA)> + '_ {
+    let data = self.csl.data;
+    let dims = self.csl.dims;
+    let start = self.offs.get(idx).copied().unwrap_or_default();
+    let end = self.offs.get(idx.saturating_add(1)).copied().unwrap_or(start);
+    self.elements.get(start..end).unwrap_or_default().iter().filter_map(move |&(line, data_idx)| {
+      let mut elem_indcs = outermost_indcs(&dims, line);
+      *elem_indcs.last_mut()? = idx;
+      Some((elem_indcs, data.get(data_idx)?))
+    })
+  }
+}
+
+impl<DATA, DS, IS, OS> Csl<DS, IS, OS, 2>
+where
+  DATA: Scalar,
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  /// Conjugate transpose of a matrix, which is the ordinary transpose for real numbers. Matrices
+  /// without rows but with columns can't be transposed because the resulting innermost
+  /// dimension would be zero.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// // [[1, 0, 2], [0, 3, 0]]
+  /// let csl = CslVec::new([2, 3], vec![1.0, 2.0, 3.0], vec![0, 2, 1], vec![0, 2, 3])?;
+  /// let expected = CslVec::new([3, 2], vec![1.0, 3.0, 2.0], vec![0, 1, 0], vec![0, 1, 2, 3])?;
+  /// assert_eq!(csl.hermitian_transpose()?, expected);
+  /// # Ok(()) }
+  /// ```
+  ///
+  /// Complex matrices
+  #[cfg_attr(feature = "with-num-complex", doc = "```rust")]
+  #[cfg_attr(not(feature = "with-num-complex"), doc = "```ignore")]
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::csl::CslVec;
+  /// use num_complex::Complex;
+  /// // [[0, 1 + 2i]]
+  /// let csl = CslVec::new([1, 2], vec![Complex::new(1.0, 2.0)], vec![1], vec![0, 1])?;
+  /// let expected = CslVec::new([2, 1], vec![Complex::new(1.0, -2.0)], vec![0], vec![0, 0, 1])?;
+  /// assert_eq!(csl.hermitian_transpose()?, expected);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn hermitian_transpose(&self) -> crate::Result<CslVec<DATA, 2>> {
+    let [rows, cols] = self.dims;
+    if has_innermost_dims_zero(&[cols, rows]) {
+      return Err(CslError::InnermostDimsZero.into());
+    }
+    let data = self.data.as_ref();
+    let transpose = CslWithTranspose::new(self.into());
+    Ok(CslVec {
+      data: transpose
+        .elements
+        .iter()
+        .map(|&(_, data_idx)| data.get(data_idx).copied().unwrap_or_default().conj())
+        .collect(),
+      dims: [cols, rows],
+      indcs: transpose.elements.iter().map(|&(line, _)| line).collect(),
+      offs: transpose.offs,
+      outermost_stride: Some(1),
+    })
+  }
+}
@@ -1,6 +1,22 @@
-use crate::csl::{Csl, CslError, CslMut, CslRef};
-use cl_traits::{try_create_array, Push};
-use core::ops::Range;
+#[cfg(feature = "alloc")]
+use crate::{
+  csl::CslVec,
+  utils::{insert_axis, remove_axis, roll_idx},
+};
+use crate::{
+  csl::{Csl, CslError, CslMut, CslRef},
+  utils::windows2,
+};
+#[cfg(feature = "alloc")]
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use cl_traits::{try_create_array, Push, Truncate};
+#[cfg(feature = "alloc")]
+use core::{
+  cmp::Ordering,
+  mem,
+  ops::{Add, Mul},
+};
+use core::{convert::TryFrom, ops::Range};
 
 macro_rules! create_sub_dim {
   (
@@ -10,6 +26,8 @@ macro_rules! create_sub_dim {
     $get:ident
     $line_fn:ident
     $sub_dim_fn:ident
+    $try_line_fn:ident
+    $try_sub_dim_fn:ident
     $([$mut:tt])?
   ) => {
 
@@ -18,6 +36,33 @@ pub(crate) fn $line_fn<'a: 'b, 'b, DATA, DS, IS, OS, const D: usize>(
   csl: &'a $($mut)? Csl<DS, IS, OS, D>,
   indcs: [usize; D]
 ) -> Option<$ref<'b, DATA, 1>>
+where
+  DATA: 'a,
+  DS: $trait<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  $try_line_fn(csl, indcs).ok()
+}
+
+#[inline]
+pub(crate) fn $sub_dim_fn<'a: 'b, 'b, DATA: 'a, DS, IS, OS, const FD: usize, const TD: usize>(
+  csl: &'a $($mut)? Csl<DS, IS, OS, FD>,
+  range: Range<usize>,
+) -> Option<$ref<'b, DATA, TD>>
+where
+  DS: $trait<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  $try_sub_dim_fn(csl, range).ok()
+}
+
+#[inline]
+pub(crate) fn $try_line_fn<'a: 'b, 'b, DATA, DS, IS, OS, const D: usize>(
+  csl: &'a $($mut)? Csl<DS, IS, OS, D>,
+  indcs: [usize; D]
+) -> crate::Result<$ref<'b, DATA, 1>>
 where
   DATA: 'a,
   DS: $trait<[DATA]>,
@@ -28,75 +73,99 @@ where
     *r
   }
   else {
-    return None;
+    return Err(CslError::InvalidRank { expected: 1, found: 0 }.into());
   };
-  let [offs_indcs, offs_values] = line_offs(&csl.dims, &indcs, csl.offs.as_ref())?;
-  Some($ref {
-    data: csl.data.$trait_fn().$get(offs_values.clone())?,
+  let [offs_indcs, offs_values] =
+    try_line_offs(&csl.dims, csl.outermost_stride, &indcs, csl.offs.as_ref())?;
+  let indcs_ref = csl.indcs.as_ref();
+  let offs_ref = csl.offs.as_ref();
+  let nnz_err =
+    || CslError::LastOffsetDifferentNnz { last_off: offs_values.end, nnz: indcs_ref.len() };
+  let offs_err =
+    || CslError::InvalidOffsetsLength { expected: offs_indcs.end, found: offs_ref.len() };
+  Ok($ref {
+    data: csl.data.$trait_fn().$get(offs_values.clone()).ok_or_else(nnz_err)?,
     dims: [last_dim].into(),
-    indcs: &csl.indcs.as_ref().get(offs_values)?,
-    offs: &csl.offs.as_ref().get(offs_indcs)?,
+    indcs: indcs_ref.get(offs_values.clone()).ok_or_else(nnz_err)?,
+    offs: offs_ref.get(offs_indcs.clone()).ok_or_else(offs_err)?,
+    outermost_stride: Some(1),
   })
 }
 
 #[inline]
-pub(crate) fn $sub_dim_fn<'a: 'b, 'b, DATA: 'a, DS, IS, OS, const FD: usize, const TD: usize>(
+pub(crate) fn $try_sub_dim_fn<'a: 'b, 'b, DATA: 'a, DS, IS, OS, const FD: usize, const TD: usize>(
   csl: &'a $($mut)? Csl<DS, IS, OS, FD>,
   range: Range<usize>,
-) -> Option<$ref<'b, DATA, TD>>
+) -> crate::Result<$ref<'b, DATA, TD>>
 where
   DS: $trait<[DATA]>,
   IS: AsRef<[usize]>,
   OS: AsRef<[usize]>,
 {
-  if range.start > range.end || TD > FD {
-    return None;
+  let rank_err = || CslError::InvalidRank { expected: FD, found: TD };
+  if TD == 0 || TD > FD {
+    return Err(rank_err().into());
+  }
+  if range.start > range.end {
+    return Err(CslError::InvalidRange { start: range.start, end: range.end }.into());
   }
   let data_ref = csl.data.$trait_fn();
   let dims_ref = &csl.dims;
   let indcs_ref = csl.indcs.as_ref();
   let offs_ref = csl.offs.as_ref();
-  match TD {
-    0 => None,
-    1 => {
-      let [start_off_value, end_off_value] = [0, offs_ref.get(1)? - offs_ref.first()?];
-      let indcs = indcs_ref.get(start_off_value..end_off_value)?;
-      let start = indcs.binary_search(&range.start).unwrap_or_else(|x| x);
-      let end = indcs.get(start..)?.binary_search(&range.end).unwrap_or_else(|x| x);
-      let dims_ref_idx = FD - TD;
-      let dims_array: [usize; TD] = try_create_array(|_| {
-        dims_ref.get(dims_ref_idx).copied().ok_or(())
-      }).ok()?;
-      Some($ref {
-        data: data_ref.$get(start..)?.$get(..end)?,
-        dims: dims_array.into(),
-        indcs: &indcs_ref.get(start..)?.get(..end)?,
-        offs: &offs_ref.get(0..2)?
-      })
-    },
-    _ => {
-      let dims_ref_lower_bound = FD - TD;
-      let mut dims: [usize; TD] = try_create_array(|idx| {
-        let fun = || Some(*dims_ref.get(dims_ref_lower_bound..)?.get(idx)?);
-        fun().ok_or(())
-      }).ok()?.into();
-      *dims.first_mut()? = range.end - range.start;
-      let [offs_indcs, offs_values] = outermost_offs(&dims, offs_ref, range);
-      Some($ref {
-        data: data_ref.$get(offs_values.clone())?,
-        dims,
-        indcs: &indcs_ref.get(offs_values)?,
-        offs: &offs_ref.get(offs_indcs)?,
-      })
-    },
+  let nnz_err = |last_off| CslError::LastOffsetDifferentNnz { last_off, nnz: indcs_ref.len() };
+  if TD == 1 {
+    let offs_err = || CslError::InvalidOffsetsLength { expected: 2, found: offs_ref.len() };
+    let first_off = *offs_ref.first().ok_or_else(offs_err)?;
+    let [start_off_value, end_off_value] = [0, offs_ref.get(1).ok_or_else(offs_err)? - first_off];
+    let indcs =
+      indcs_ref.get(start_off_value..end_off_value).ok_or_else(|| nnz_err(end_off_value))?;
+    let start = indcs.binary_search(&range.start).unwrap_or_else(|x| x);
+    let end =
+      indcs.get(start..).unwrap_or_default().binary_search(&range.end).unwrap_or_else(|x| x);
+    let dims_ref_idx = FD - TD;
+    let dims_array: [usize; TD] =
+      try_create_array(|_| dims_ref.get(dims_ref_idx).copied().ok_or_else(rank_err))?;
+    return Ok($ref {
+      data: data_ref.$get(start..).and_then(|x| x.$get(..end)).ok_or_else(|| nnz_err(start + end))?,
+      dims: dims_array.into(),
+      indcs: indcs_ref.get(start..).and_then(|x| x.get(..end)).ok_or_else(|| nnz_err(start + end))?,
+      offs: offs_ref.get(0..2).ok_or_else(offs_err)?,
+      outermost_stride: Some(1),
+    });
+  }
+  let dims_ref_lower_bound = FD - TD;
+  let mut dims: [usize; TD] = try_create_array(|idx| {
+    dims_ref.get(dims_ref_lower_bound..).and_then(|x| x.get(idx)).copied().ok_or_else(rank_err)
+  })?;
+  if let Some(first) = dims.first_mut() {
+    *first = range.end - range.start;
   }
+  if has_innermost_dims_zero(&dims) {
+    return Err(CslError::InnermostDimsZero.into());
+  }
+  let range_err = CslError::InvalidRange { start: range.start, end: range.end };
+  let outermost_stride = outermost_stride(&dims);
+  let [offs_indcs, offs_values] =
+    outermost_offs(outermost_stride, offs_ref, range).ok_or(range_err)?;
+  let offs_err =
+    || CslError::InvalidOffsetsLength { expected: offs_indcs.end, found: offs_ref.len() };
+  Ok($ref {
+    data: data_ref.$get(offs_values.clone()).ok_or_else(|| nnz_err(offs_values.end))?,
+    dims,
+    indcs: indcs_ref.get(offs_values.clone()).ok_or_else(|| nnz_err(offs_values.end))?,
+    offs: offs_ref.get(offs_indcs.clone()).ok_or_else(offs_err)?,
+    outermost_stride,
+  })
 }
 
   };
 }
 
-create_sub_dim!(AsMut as_mut CslMut get_mut line_mut sub_dim_mut [mut]);
-create_sub_dim!(AsRef as_ref CslRef get line sub_dim);
+create_sub_dim!(
+  AsMut as_mut CslMut get_mut line_mut sub_dim_mut try_line_mut try_sub_dim_mut [mut]
+);
+create_sub_dim!(AsRef as_ref CslRef get line sub_dim try_line try_sub_dim);
 
 // Max offset length is usize::MAX - 1
 #[inline]
@@ -108,7 +177,7 @@ pub(crate) fn correct_offs_len<const D: usize>(dims: &[usize; D]) -> crate::Resu
     _ => {
       let mut offs_len: usize = 1;
       for dim in dims.iter().copied().rev().skip(1).filter(|dim| dim != &0) {
-        offs_len = offs_len.saturating_mul(dim);
+        offs_len = offs_len.checked_mul(dim).ok_or(CslError::OffsLengthOverflow)?;
       }
       offs_len.checked_add(1).ok_or_else(|| CslError::OffsLengthOverflow.into())
     }
@@ -126,63 +195,152 @@ where
   OS: AsRef<[usize]>,
 {
   let innermost_idx = indcs.last()?;
-  let [_, offs_values] = line_offs(&csl.dims, &indcs, csl.offs.as_ref())?;
+  let [_, offs_values] = line_offs(&csl.dims, csl.outermost_stride, &indcs, csl.offs.as_ref())?;
   let start = offs_values.start;
-  if let Ok(x) = csl.indcs.as_ref().get(offs_values)?.binary_search(&innermost_idx) {
+  if let Ok(x) = csl.indcs.as_ref().get(offs_values)?.binary_search(innermost_idx) {
     Some(start + x)
   } else {
     None
   }
 }
 
+#[inline]
+pub(crate) fn has_innermost_dims_zero<const D: usize>(dims: &[usize; D]) -> bool {
+  let mut iter = dims.iter().copied();
+  for dim in iter.by_ref() {
+    if dim != 0 {
+      break;
+    }
+  }
+  iter.any(|v| v == 0)
+}
+
+// Indices of all dimensions of the line `line`. The innermost index is always zero.
+#[inline]
+pub(crate) fn outermost_indcs<const D: usize>(dims: &[usize; D], mut line: usize) -> [usize; D] {
+  let mut rslt = [0; D];
+  let iter = rslt.iter_mut().zip(dims).rev().skip(1);
+  for (idx, &dim) in iter.filter(|&(_, &dim)| dim != 0) {
+    *idx = line % dim;
+    line /= dim;
+  }
+  rslt
+}
+
+// Index of the line that contains `indcs`. The innermost index is ignored.
+//
+// Returns `None` if any outer index is out of bounds or if the resulting line overflows.
+#[inline]
+pub(crate) fn line_idx<const D: usize>(dims: &[usize; D], indcs: &[usize; D]) -> Option<usize> {
+  try_line_idx(dims, outermost_stride(dims), indcs).ok()
+}
+
 #[inline]
 pub(crate) fn line_offs<const D: usize>(
   dims: &[usize; D],
+  outermost_stride: Option<usize>,
   indcs: &[usize; D],
   offs: &[usize],
 ) -> Option<[Range<usize>; 2]> {
+  try_line_offs(dims, outermost_stride, indcs, offs).ok()
+}
+
+// Fallible version of `line_idx` that tells which outer index is out of bounds.
+//
+// `outermost_stride` must be the result of `outermost_stride(dims)`, which `Csl` caches to avoid
+// computing the product of the dimensions in every lookup.
+#[inline]
+pub(crate) fn try_line_idx<const D: usize>(
+  dims: &[usize; D],
+  outermost_stride: Option<usize>,
+  indcs: &[usize; D],
+) -> crate::Result<usize> {
   match D {
-    0 => None,
-    1 => Some({
-      let off_end = offs.get(1)?.saturating_sub(*offs.get(0)?);
-      [0..2, 0..off_end]
-    }),
+    0 => Err(CslError::InvalidRank { expected: 1, found: 0 }.into()),
+    1 => Ok(0),
     _ => {
-      let diff = indcs.len().saturating_sub(2);
+      let outer_len = dims.len() - 1;
+      let mut iter = dims.iter().zip(indcs.iter()).take(outer_len).enumerate();
+      let out_of_bounds =
+        iter.find(|&(_, (&dim, &idx))| if dim == 0 { idx != 0 } else { idx >= dim });
+      if let Some((axis, (&dim_len, &idx))) = out_of_bounds {
+        return Err(CslError::IndexOutOfBounds { axis, dim_len, idx }.into());
+      }
+      // Horner's method, i.e., `((i1 * d2 + i2) * d3 + i3) ...`, for the inner indices and the
+      // cached stride for the outermost index.
       let mut lines: usize = 0;
-      for (idx, curr_idx) in indcs.iter().copied().enumerate().take(diff) {
-        let product = dims.iter().skip(idx + 1).rev().skip(1).product::<usize>();
-        lines = lines.saturating_add(product.saturating_mul(curr_idx));
+      for (&dim, &idx) in dims.iter().zip(indcs.iter()).take(outer_len).skip(1) {
+        lines = lines
+          .checked_mul(dim)
+          .and_then(|x| x.checked_add(idx))
+          .ok_or(CslError::OffsLengthOverflow)?;
       }
-      lines = lines.saturating_add(*indcs.get(dims.len() - 2)?);
+      let outermost_idx = indcs.first().copied().unwrap_or_default();
+      let lines = outermost_stride
+        .and_then(|stride| stride.checked_mul(outermost_idx))
+        .and_then(|x| x.checked_add(lines))
+        .ok_or(CslError::OffsLengthOverflow)?;
       if lines > usize::MAX.saturating_sub(2) {
-        return None;
+        return Err(CslError::OffsLengthOverflow.into());
       }
-      let first = *offs.first()?;
-      let off_start = offs.get(lines)?.saturating_sub(first);
-      let off_end = offs.get(lines + 1)?.saturating_sub(first);
-      Some([lines..lines.saturating_add(2), off_start..off_end])
+      Ok(lines)
     }
   }
 }
 
+// Fallible version of `line_offs`.
 #[inline]
-pub(crate) fn outermost_offs<const D: usize>(
+pub(crate) fn try_line_offs<const D: usize>(
   dims: &[usize; D],
+  outermost_stride: Option<usize>,
+  indcs: &[usize; D],
+  offs: &[usize],
+) -> crate::Result<[Range<usize>; 2]> {
+  let offs_err = |expected| CslError::InvalidOffsetsLength { expected, found: offs.len() };
+  match D {
+    0 => Err(CslError::InvalidRank { expected: 1, found: 0 }.into()),
+    1 => {
+      let first = *offs.first().ok_or_else(|| offs_err(2))?;
+      let off_end = offs.get(1).ok_or_else(|| offs_err(2))?.saturating_sub(first);
+      Ok([0..2, 0..off_end])
+    }
+    _ => {
+      let lines = try_line_idx(dims, outermost_stride, indcs)?;
+      let first = *offs.first().ok_or_else(|| offs_err(lines + 2))?;
+      let off_start = offs.get(lines).ok_or_else(|| offs_err(lines + 2))?.saturating_sub(first);
+      let off_end = offs.get(lines + 1).ok_or_else(|| offs_err(lines + 2))?.saturating_sub(first);
+      Ok([lines..lines.saturating_add(2), off_start..off_end])
+    }
+  }
+}
+
+// Returns `None` if `range` isn't contained by `offs` or if the offsets computation overflows.
+//
+// `outermost_stride` must be the result of `outermost_stride` for the dimensions of `offs`.
+#[inline]
+pub(crate) fn outermost_offs(
+  outermost_stride: Option<usize>,
   offs: &[usize],
   range: Range<usize>,
-) -> [Range<usize>; 2] {
-  let outermost_stride = outermost_stride(&dims);
-  let start_off_idx = outermost_stride.saturating_mul(range.start);
-  let end_off_idx = outermost_stride.saturating_mul(range.end);
-  let off_start = *offs.get(start_off_idx).unwrap_or(&0);
-  let off_end = *offs.get(end_off_idx).unwrap_or(&0);
-  [start_off_idx..end_off_idx.saturating_add(1), off_start..off_end]
+) -> Option<[Range<usize>; 2]> {
+  let outermost_stride = outermost_stride?;
+  let start_off_idx = outermost_stride.checked_mul(range.start)?;
+  let end_off_idx = outermost_stride.checked_mul(range.end)?;
+  let first = *offs.first()?;
+  let off_start = offs.get(start_off_idx)?.checked_sub(first)?;
+  let off_end = offs.get(end_off_idx)?.checked_sub(first)?;
+  Some([start_off_idx..end_off_idx.checked_add(1)?, off_start..off_end])
 }
 
+// Returns `None` if the number of lines of each outermost index overflows.
 #[inline]
-pub(crate) fn outermost_stride<const D: usize>(dims: &[usize; D]) -> usize {
-  dims.iter().skip(1).rev().skip(1).product::<usize>()
+pub(crate) fn outermost_stride<const D: usize>(dims: &[usize; D]) -> Option<usize> {
+  checked_product(dims.iter().skip(1).rev().skip(1))
+}
+
+#[inline]
+fn checked_product<'a>(mut iter: impl Iterator<Item = &'a usize>) -> Option<usize> {
+  iter.try_fold(1usize, |acc, &elem| acc.checked_mul(elem))
 }
 
 #[inline]
@@ -197,3 +355,1290 @@ where
     0
   })
 }
+
+// Validates the arguments of `Csl::batch_mat_vec_mul` and returns the number of rows of each
+// matrix along with the zeroed result.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn batch_mat_vec_mul_init<DATA>(
+  dims: &[usize; 3],
+  vectors_len: usize,
+  vec_len: usize,
+) -> crate::Result<(usize, Vec<DATA>)>
+where
+  DATA: Clone + Default,
+{
+  let [batch, rows, cols] = *dims;
+  if vec_len != cols {
+    return Err(CslError::InvalidDenseLength { expected: cols, found: vec_len }.into());
+  }
+  let expected = batch.checked_mul(cols).ok_or(CslError::DimsProductOverflow)?;
+  if vectors_len != expected {
+    return Err(CslError::InvalidDenseLength { expected, found: vectors_len }.into());
+  }
+  let len = batch.checked_mul(rows).ok_or(CslError::DimsProductOverflow)?;
+  Ok((rows, vec![DATA::default(); len]))
+}
+
+// Multiplies the matrix `batch` of a 3D structure by its own vector, writing one element for
+// each row into `out`.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn batch_mat_vec_mul_matrix<DATA>(
+  batch: usize,
+  csl: &CslRef<'_, DATA, 3>,
+  out: &mut [DATA],
+  vectors: &[DATA],
+) where
+  DATA: Add<Output = DATA> + Clone + Default + Mul<Output = DATA>,
+{
+  let vec_len = csl.dims.last().copied().unwrap_or_default();
+  let vector = vectors.get(batch.saturating_mul(vec_len)..).unwrap_or_default();
+  let line_offs = csl.offs.get(batch.saturating_mul(out.len())..).unwrap_or_default();
+  let first = csl.offs.first().copied().unwrap_or_default();
+  for (elem, [start, end]) in out.iter_mut().zip(windows2(line_offs)) {
+    let range = start.saturating_sub(first)..end.saturating_sub(first);
+    let line_data = csl.data.get(range.clone()).unwrap_or_default();
+    let line_indcs = csl.indcs.get(range).unwrap_or_default();
+    *elem = line_indcs.iter().zip(line_data).fold(DATA::default(), |acc, (&idx, value)| {
+      acc + value.clone() * vector.get(idx).cloned().unwrap_or_default()
+    });
+  }
+}
+
+// Ranges of every line in the `data` and `indcs` slices.
+#[inline]
+pub(crate) fn line_ranges(offs: &[usize]) -> impl Iterator<Item = Range<usize>> + '_ {
+  let first = offs.first().copied().unwrap_or_default();
+  windows2(offs).map(move |[a, b]| a.saturating_sub(first)..b.saturating_sub(first))
+}
+
+// Walks each pair of lines in ascending innermost index order. Stores the value returned by `cb`,
+// if any.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn merge_lines<A, B, DSA, DSB, F, ISA, ISB, OSA, OSB, T, const D: usize>(
+  a: &Csl<DSA, ISA, OSA, D>,
+  b: &Csl<DSB, ISB, OSB, D>,
+  mut cb: F,
+) -> crate::Result<CslVec<T, D>>
+where
+  DSA: AsRef<[A]>,
+  DSB: AsRef<[B]>,
+  F: FnMut(Option<&A>, Option<&B>) -> Option<T>,
+  ISA: AsRef<[usize]>,
+  ISB: AsRef<[usize]>,
+  OSA: AsRef<[usize]>,
+  OSB: AsRef<[usize]>,
+{
+  if a.dims != b.dims {
+    return Err(CslError::DiffDims.into());
+  }
+  let [a_offs, b_offs] = [a.offs.as_ref(), b.offs.as_ref()];
+  if a_offs.len() != b_offs.len() {
+    return Err(
+      CslError::InvalidOffsetsLength { expected: a_offs.len(), found: b_offs.len() }.into(),
+    );
+  }
+  let mut rslt =
+    CslVec { dims: a.dims, outermost_stride: a.outermost_stride, ..Default::default() };
+  if a_offs.is_empty() {
+    return Ok(rslt);
+  }
+  rslt.offs.push(0);
+  for (a_range, b_range) in line_ranges(a_offs).zip(line_ranges(b_offs)) {
+    let a_data = a.data.as_ref().get(a_range.clone()).unwrap_or_default();
+    let a_indcs = a.indcs.as_ref().get(a_range).unwrap_or_default();
+    let b_data = b.data.as_ref().get(b_range.clone()).unwrap_or_default();
+    let b_indcs = b.indcs.as_ref().get(b_range).unwrap_or_default();
+    let a_iter = a_indcs.iter().copied().zip(a_data);
+    let b_iter = b_indcs.iter().copied().zip(b_data);
+    merge_line(a_iter, b_iter, &mut cb, &mut rslt);
+    rslt.offs.push(rslt.indcs.len());
+  }
+  Ok(rslt)
+}
+
+// Walks two lines in ascending innermost index order and pushes the values returned by `cb`, if
+// any, into `rslt`.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn merge_line<'a, 'b, A, B, F, T, const D: usize>(
+  a_iter: impl Iterator<Item = (usize, &'a A)>,
+  b_iter: impl Iterator<Item = (usize, &'b B)>,
+  cb: &mut F,
+  rslt: &mut CslVec<T, D>,
+) where
+  A: 'a,
+  B: 'b,
+  F: FnMut(Option<&A>, Option<&B>) -> Option<T>,
+{
+  let mut a_iter = a_iter.peekable();
+  let mut b_iter = b_iter.peekable();
+  loop {
+    let ordering = match (a_iter.peek(), b_iter.peek()) {
+      (Some(&(a_idx, _)), Some(&(b_idx, _))) => a_idx.cmp(&b_idx),
+      (Some(_), None) => Ordering::Less,
+      (None, Some(_)) => Ordering::Greater,
+      (None, None) => break,
+    };
+    let (idx, value) = match ordering {
+      Ordering::Equal => match (a_iter.next(), b_iter.next()) {
+        (Some((idx, a_value)), Some((_, b_value))) => (idx, cb(Some(a_value), Some(b_value))),
+        _ => break,
+      },
+      Ordering::Greater => match b_iter.next() {
+        Some((idx, b_value)) => (idx, cb(None, Some(b_value))),
+        None => break,
+      },
+      Ordering::Less => match a_iter.next() {
+        Some((idx, a_value)) => (idx, cb(Some(a_value), None)),
+        None => break,
+      },
+    };
+    if let Some(elem) = value {
+      rslt.data.push(elem);
+      rslt.indcs.push(idx);
+    }
+  }
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn permute_outermost<DATA, DS, IS, OS, const D: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  perm: &[usize],
+) -> crate::Result<CslVec<DATA, D>>
+where
+  DATA: Clone,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  let data = csl.data.as_ref();
+  let indcs = csl.indcs.as_ref();
+  let offs = csl.offs.as_ref();
+  let outermost_dim = csl.dims.first().copied().unwrap_or_default();
+  let inverse =
+    crate::utils::inverse_permutation(perm, outermost_dim).ok_or(CslError::InvalidPermutation)?;
+  let first = offs.first().copied().unwrap_or_default();
+  let mut rslt = CslVec {
+    data: Vec::with_capacity(data.len()),
+    dims: csl.dims,
+    indcs: Vec::with_capacity(indcs.len()),
+    offs: Vec::with_capacity(offs.len()),
+    outermost_stride: csl.outermost_stride,
+  };
+  if outermost_dim == 0 {
+    rslt.data.extend_from_slice(data);
+    rslt.indcs.extend_from_slice(indcs);
+    rslt.offs.extend(offs.iter().map(|off| off - first));
+    return Ok(rslt);
+  }
+  rslt.offs.push(0);
+  if D == 1 {
+    let mut pairs = Vec::with_capacity(data.len());
+    for (idx, value) in indcs.iter().zip(data) {
+      pairs.push((*inverse.get(*idx).ok_or(crate::Error::UnknownError)?, value.clone()));
+    }
+    pairs.sort_unstable_by_key(|pair| pair.0);
+    for (idx, value) in pairs {
+      rslt.data.push(value);
+      rslt.indcs.push(idx);
+    }
+    rslt.offs.push(rslt.indcs.len());
+    return Ok(rslt);
+  }
+  extend_with_outermost_blocks(csl, perm, &mut rslt)?;
+  Ok(rslt)
+}
+
+#[inline]
+pub(crate) fn push_outermost_block<DATA, DS, IS, OS, const D: usize, const TD: usize>(
+  csl: &mut Csl<DS, IS, OS, D>,
+  block: &CslRef<'_, DATA, TD>,
+) -> crate::Result<()>
+where
+  DATA: Clone,
+  DS: AsRef<[DATA]> + Push<Input = DATA> + Truncate<Input = usize>,
+  IS: AsRef<[usize]> + Push<Input = usize> + Truncate<Input = usize>,
+  OS: AsRef<[usize]> + Push<Input = usize> + Truncate<Input = usize>,
+{
+  if TD == 0 || TD.checked_add(1) != Some(D) {
+    return Err(CslError::DiffDims.into());
+  }
+  let dims = csl.dims;
+  let inner_dims = csl.dims.get_mut(1..).unwrap_or_default();
+  let is_empty = csl.data.as_ref().is_empty() && inner_dims.iter().all(|&dim| dim == 0);
+  if is_empty {
+    inner_dims.copy_from_slice(&block.dims);
+    csl.outermost_stride = outermost_stride(&csl.dims);
+  } else if inner_dims != &block.dims[..] {
+    return Err(CslError::DiffDims.into());
+  }
+  let lens = [csl.data.as_ref().len(), csl.indcs.as_ref().len(), csl.offs.as_ref().len()];
+  if let Err(err) = do_push_outermost_block(csl, block) {
+    let [data_len, indcs_len, offs_len] = lens;
+    let _ = csl.data.truncate(data_len);
+    let _ = csl.indcs.truncate(indcs_len);
+    let _ = csl.offs.truncate(offs_len);
+    csl.dims = dims;
+    csl.outermost_stride = outermost_stride(&dims);
+    return Err(err);
+  }
+  if let Some(r) = csl.dims.first_mut() {
+    *r = r.saturating_add(1);
+  }
+  Ok(())
+}
+
+#[inline]
+fn do_push_outermost_block<DATA, DS, IS, OS, const D: usize, const TD: usize>(
+  csl: &mut Csl<DS, IS, OS, D>,
+  block: &CslRef<'_, DATA, TD>,
+) -> crate::Result<()>
+where
+  DATA: Clone,
+  DS: AsRef<[DATA]> + Push<Input = DATA>,
+  IS: AsRef<[usize]> + Push<Input = usize>,
+  OS: AsRef<[usize]> + Push<Input = usize>,
+{
+  let mut last_off = manage_last_offset(&mut csl.offs)?;
+  for (idx, value) in block.indcs.iter().zip(block.data) {
+    let _ = csl.data.push(value.clone()).map_err(|_err| crate::Error::InsufficientCapacity)?;
+    let _ = csl.indcs.push(*idx).map_err(|_err| crate::Error::InsufficientCapacity)?;
+  }
+  for [a, b] in windows2(block.offs) {
+    last_off = last_off.saturating_add(b - a);
+    let _ = csl.offs.push(last_off).map_err(|_err| crate::Error::InsufficientCapacity)?;
+  }
+  Ok(())
+}
+
+#[inline]
+pub(crate) fn retain<DATA, DS, F, IS, OS, const D: usize>(csl: &mut Csl<DS, IS, OS, D>, mut cb: F)
+where
+  DS: AsMut<[DATA]> + Truncate<Input = usize>,
+  F: FnMut([usize; D], &DATA) -> bool,
+  IS: AsMut<[usize]> + Truncate<Input = usize>,
+  OS: AsMut<[usize]>,
+{
+  let offs = csl.offs.as_mut();
+  let first = if let Some(r) = offs.first() { *r } else { return };
+  let data = csl.data.as_mut();
+  let indcs = csl.indcs.as_mut();
+  let mut curr = 0;
+  let mut start = 0;
+  for line in 0..offs.len().saturating_sub(1) {
+    let end = offs.get(line + 1).map_or(start, |off| off - first);
+    let mut elem_indcs = outermost_indcs(&csl.dims, line);
+    for read in start..end {
+      let (idx, value) = if let (Some(idx), Some(value)) = (indcs.get(read), data.get(read)) {
+        (*idx, value)
+      } else {
+        break;
+      };
+      if let Some(r) = elem_indcs.last_mut() {
+        *r = idx;
+      }
+      if cb(elem_indcs, value) {
+        data.swap(curr, read);
+        indcs.swap(curr, read);
+        curr += 1;
+      }
+    }
+    start = end;
+    if let Some(r) = offs.get_mut(line + 1) {
+      *r = curr + first;
+    }
+  }
+  let _ = csl.data.truncate(curr);
+  let _ = csl.indcs.truncate(curr);
+}
+
+#[inline]
+pub(crate) fn swap_outermost_lines<DATA, DS, IS, OS, const D: usize>(
+  csl: &mut Csl<DS, IS, OS, D>,
+  a: usize,
+  b: usize,
+) -> crate::Result<()>
+where
+  DS: AsMut<[DATA]>,
+  IS: AsMut<[usize]>,
+  OS: AsMut<[usize]>,
+{
+  let outermost_dim = csl.dims.first().copied().unwrap_or_default();
+  if let Some(&idx) = [a, b].iter().find(|&&idx| idx >= outermost_dim) {
+    return Err(CslError::IndcsGreaterThanEqualDimLength { dim_len: outermost_dim, idx }.into());
+  }
+  let (a, b) = if a <= b { (a, b) } else { (b, a) };
+  if a == b {
+    return Ok(());
+  }
+  let data = csl.data.as_mut();
+  let indcs = csl.indcs.as_mut();
+  if D == 1 {
+    match (indcs.binary_search(&a), indcs.binary_search(&b)) {
+      (Ok(a_idx), Ok(b_idx)) => data.swap(a_idx, b_idx),
+      (Ok(a_idx), Err(b_idx)) => {
+        if let Some(r) = indcs.get_mut(a_idx) {
+          *r = b;
+        }
+        data.get_mut(a_idx..b_idx).unwrap_or_default().rotate_left(1);
+        indcs.get_mut(a_idx..b_idx).unwrap_or_default().rotate_left(1);
+      }
+      (Err(a_idx), Ok(b_idx)) => {
+        if let Some(r) = indcs.get_mut(b_idx) {
+          *r = a;
+        }
+        data.get_mut(a_idx..=b_idx).unwrap_or_default().rotate_right(1);
+        indcs.get_mut(a_idx..=b_idx).unwrap_or_default().rotate_right(1);
+      }
+      (Err(_), Err(_)) => {}
+    }
+    return Ok(());
+  }
+  let outermost_stride = match csl.outermost_stride {
+    Some(r) if r != 0 => r,
+    Some(_) => return Ok(()),
+    None => return Err(crate::Error::UnknownError),
+  };
+  let offs = csl.offs.as_mut();
+  let first = offs.first().copied().unwrap_or_default();
+  let off = |line: usize| offs.get(line).map(|off| off - first).ok_or(crate::Error::UnknownError);
+  let a_values = off(a * outermost_stride)?..off((a + 1) * outermost_stride)?;
+  let b_values = off(b * outermost_stride)?..off((b + 1) * outermost_stride)?;
+  swap_segments(data, a_values.clone(), b_values.clone());
+  swap_segments(indcs, a_values, b_values);
+  let lines = a * outermost_stride..(b + 1) * outermost_stride + 1;
+  let b_lines = (b - a) * outermost_stride..(b - a + 1) * outermost_stride;
+  with_line_lens(offs.get_mut(lines).unwrap_or_default(), |lens| {
+    swap_segments(lens, 0..outermost_stride, b_lines)
+  });
+  Ok(())
+}
+
+// Swaps the non-overlapping segments `a` and `b`, where `a` comes before `b`, shifting all
+// elements between them.
+#[inline]
+fn swap_segments<T>(slice: &mut [T], a: Range<usize>, b: Range<usize>) {
+  let middle_len = b.start.saturating_sub(a.end);
+  if let Some(segment) = slice.get_mut(a.start..b.end) {
+    segment.reverse();
+    let (new_b, rest) = segment.split_at_mut(b.len());
+    let (middle, new_a) = rest.split_at_mut(middle_len);
+    new_b.reverse();
+    middle.reverse();
+    new_a.reverse();
+  }
+}
+
+#[inline]
+pub(crate) fn truncate<DS, IS, OS, const D: usize>(
+  csl: &mut Csl<DS, IS, OS, D>,
+  indcs: [usize; D],
+) -> crate::Result<()>
+where
+  DS: Truncate<Input = usize>,
+  IS: AsRef<[usize]> + Truncate<Input = usize>,
+  OS: AsMut<[usize]> + AsRef<[usize]>,
+{
+  if D == 0 {
+    return Ok(());
+  }
+  for (&dim_len, &idx) in csl.dims.iter().zip(indcs.iter()) {
+    if idx >= dim_len && (dim_len != 0 || idx != 0) {
+      return Err(CslError::IndcsGreaterThanEqualDimLength { dim_len, idx }.into());
+    }
+  }
+  let [offs_indcs, values] = line_offs(&csl.dims, csl.outermost_stride, &indcs, csl.offs.as_ref())
+    .ok_or(crate::Error::UnknownError)?;
+  let innermost = indcs.last().copied().unwrap_or_default();
+  let line_indcs = csl.indcs.as_ref().get(values.clone()).unwrap_or_default();
+  let cut = values.start + line_indcs.partition_point(|&idx| idx < innermost);
+  truncate_from_line(csl, offs_indcs.start, cut);
+  Ok(())
+}
+
+#[inline]
+pub(crate) fn truncate_outermost<DS, IS, OS, const D: usize>(
+  csl: &mut Csl<DS, IS, OS, D>,
+  len: usize,
+) where
+  DS: Truncate<Input = usize>,
+  IS: AsRef<[usize]> + Truncate<Input = usize>,
+  OS: AsMut<[usize]> + AsRef<[usize]> + Truncate<Input = usize>,
+{
+  let outermost_dim = if let Some(r) = csl.dims.first() { *r } else { return };
+  if len >= outermost_dim {
+    return;
+  }
+  if D == 1 {
+    let cut = csl.indcs.as_ref().partition_point(|&idx| idx < len);
+    truncate_from_line(csl, 0, cut);
+  } else {
+    let outermost_stride = if let Some(r) = csl.outermost_stride { r } else { return };
+    let line = outermost_stride.saturating_mul(len);
+    let offs = csl.offs.as_ref();
+    let first = offs.first().copied().unwrap_or_default();
+    let cut = offs.get(line).map_or(0, |off| off - first);
+    truncate_from_line(csl, line, cut);
+    // Zero-length dimensions still need the offsets of a single outermost block.
+    let _ = csl.offs.truncate(outermost_stride.saturating_mul(len.max(1)).saturating_add(1));
+  }
+  if let Some(r) = csl.dims.first_mut() {
+    *r = len;
+  }
+}
+
+// Removes all elements starting at the data index `cut`, which belongs to `line`. All subsequent
+// lines become empty.
+#[inline]
+fn truncate_from_line<DS, IS, OS, const D: usize>(
+  csl: &mut Csl<DS, IS, OS, D>,
+  line: usize,
+  cut: usize,
+) where
+  DS: Truncate<Input = usize>,
+  IS: Truncate<Input = usize>,
+  OS: AsMut<[usize]>,
+{
+  let _ = csl.data.truncate(cut);
+  let _ = csl.indcs.truncate(cut);
+  let offs = csl.offs.as_mut();
+  let first = offs.first().copied().unwrap_or_default();
+  offs.iter_mut().skip(line.saturating_add(1)).for_each(|off| *off = first + cut);
+}
+
+// Smallest dimensions that contain all stored elements. Zero-length outer dimensions, which
+// don't take part in the line numbering, stay zero.
+#[inline]
+pub(crate) fn effective_dims<const D: usize>(
+  dims: &[usize; D],
+  indcs: &[usize],
+  offs: &[usize],
+) -> [usize; D] {
+  let mut rslt = [0; D];
+  for (line, range) in line_ranges(offs).enumerate() {
+    let last =
+      if let Some(r) = indcs.get(range).unwrap_or_default().last() { *r } else { continue };
+    let line_indcs = outermost_indcs(dims, line);
+    let iter = rslt.iter_mut().zip(dims.iter().zip(line_indcs)).take(D.saturating_sub(1));
+    for (effective, (&dim, idx)) in iter {
+      if dim != 0 {
+        *effective = (*effective).max(idx + 1);
+      }
+    }
+    if let Some(r) = rslt.last_mut() {
+      *r = (*r).max(last + 1);
+    }
+  }
+  rslt
+}
+
+#[inline]
+pub(crate) fn trim_trailing_empty_lines<DS, IS, OS, const D: usize>(csl: &mut Csl<DS, IS, OS, D>)
+where
+  IS: AsRef<[usize]>,
+  OS: AsMut<[usize]> + AsRef<[usize]> + Truncate<Input = usize>,
+{
+  let outer_len = D.saturating_sub(1);
+  let effective = effective_dims(&csl.dims, csl.indcs.as_ref(), csl.offs.as_ref());
+  let mut new_dims = csl.dims;
+  for (dim, effective_dim) in new_dims.iter_mut().zip(effective).take(outer_len) {
+    *dim = effective_dim;
+  }
+  if new_dims == csl.dims {
+    return;
+  }
+  let new_offs_len = if let Ok(r) = correct_offs_len(&new_dims) { r } else { return };
+  let offs = csl.offs.as_mut();
+  // Kept lines preserve their order and every removed line is empty, therefore, only the
+  // offsets need to be rewritten. Reads always happen at or after the written position.
+  for new_line in 0..new_offs_len.saturating_sub(1) {
+    let line_indcs = outermost_indcs(&new_dims, new_line);
+    let mut old_line = 0;
+    let iter = csl.dims.iter().zip(line_indcs).take(outer_len).filter(|&(&dim, _)| dim != 0);
+    for (&dim, idx) in iter {
+      old_line = old_line * dim + idx;
+    }
+    let end = offs.get(old_line.saturating_add(1)).copied().unwrap_or_default();
+    if let Some(r) = offs.get_mut(new_line + 1) {
+      *r = end;
+    }
+  }
+  let _ = csl.offs.truncate(new_offs_len);
+  csl.dims = new_dims;
+  csl.outermost_stride = outermost_stride(&new_dims);
+}
+
+#[inline]
+pub(crate) fn reverse_outermost<DATA, DS, IS, OS, const D: usize>(csl: &mut Csl<DS, IS, OS, D>)
+where
+  DS: AsMut<[DATA]>,
+  IS: AsMut<[usize]>,
+  OS: AsMut<[usize]>,
+{
+  let outermost_dim = csl.dims.first().copied().unwrap_or_default();
+  if outermost_dim < 2 {
+    return;
+  }
+  let data = csl.data.as_mut();
+  let indcs = csl.indcs.as_mut();
+  data.reverse();
+  indcs.reverse();
+  if D == 1 {
+    indcs.iter_mut().for_each(|idx| *idx = outermost_dim - 1 - *idx);
+    return;
+  }
+  let outermost_stride = match csl.outermost_stride {
+    Some(r) if r != 0 => r,
+    _ => return,
+  };
+  let offs = csl.offs.as_mut();
+  with_line_lens(offs, |lens| {
+    lens.reverse();
+    lens.chunks_mut(outermost_stride).for_each(|chunk| chunk.reverse());
+  });
+  let first = offs.first().copied().unwrap_or_default();
+  for block in 0..outermost_dim {
+    let start = offs.get(block * outermost_stride).map_or(0, |off| off - first);
+    let end = offs.get((block + 1) * outermost_stride).map_or(0, |off| off - first);
+    if let (Some(data_block), Some(indcs_block)) =
+      (data.get_mut(start..end), indcs.get_mut(start..end))
+    {
+      data_block.reverse();
+      indcs_block.reverse();
+    }
+  }
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn select_outermost<DATA, DS, IS, OS, const D: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  indices: &[usize],
+) -> crate::Result<CslVec<DATA, D>>
+where
+  DATA: Clone,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  let outermost_dim = csl.dims.first().copied().unwrap_or_default();
+  if let Some(&idx) = indices.iter().find(|&&idx| idx >= outermost_dim) {
+    return Err(CslError::IndcsGreaterThanEqualDimLength { dim_len: outermost_dim, idx }.into());
+  }
+  let mut dims = csl.dims;
+  if let Some(r) = dims.first_mut() {
+    *r = indices.len();
+  }
+  let outermost_stride = outermost_stride(&dims);
+  let mut rslt =
+    CslVec { data: Vec::new(), dims, indcs: Vec::new(), offs: alloc::vec![0], outermost_stride };
+  if D == 1 {
+    let data = csl.data.as_ref();
+    let indcs = csl.indcs.as_ref();
+    for (new_idx, old_idx) in indices.iter().enumerate() {
+      if let Ok(data_idx) = indcs.binary_search(old_idx) {
+        rslt.data.push(data.get(data_idx).ok_or(crate::Error::UnknownError)?.clone());
+        rslt.indcs.push(new_idx);
+      }
+    }
+    rslt.offs.push(rslt.indcs.len());
+    return Ok(rslt);
+  }
+  extend_with_outermost_blocks(csl, indices, &mut rslt)?;
+  Ok(rslt)
+}
+
+// Temporarily converts `offs` into the lengths of each line so that `cb` can reorder lines.
+#[inline]
+fn with_line_lens(offs: &mut [usize], cb: impl FnOnce(&mut [usize])) {
+  let first = offs.first().copied().unwrap_or_default();
+  let mut prev = first;
+  for off in offs.iter_mut().skip(1) {
+    let curr = *off;
+    *off = curr - prev;
+    prev = curr;
+  }
+  cb(offs.get_mut(1..).unwrap_or_default());
+  let mut acc = first;
+  for off in offs.iter_mut().skip(1) {
+    acc += *off;
+    *off = acc;
+  }
+}
+
+// Appends the lines of each outermost index of `indices`, in order, to `rslt`.
+#[cfg(feature = "alloc")]
+#[inline]
+fn extend_with_outermost_blocks<DATA, DS, IS, OS, const D: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  indices: &[usize],
+  rslt: &mut CslVec<DATA, D>,
+) -> crate::Result<()>
+where
+  DATA: Clone,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  let data = csl.data.as_ref();
+  let indcs = csl.indcs.as_ref();
+  let offs = csl.offs.as_ref();
+  let first = offs.first().copied().unwrap_or_default();
+  let stride = csl.outermost_stride.ok_or(CslError::OffsLengthOverflow)?;
+  for old_idx in indices.iter().copied() {
+    let start_line = old_idx.saturating_mul(stride);
+    let block_offs =
+      offs.get(start_line..=start_line.saturating_add(stride)).ok_or(crate::Error::UnknownError)?;
+    let block_start = block_offs.first().copied().unwrap_or_default().saturating_sub(first);
+    let block_end = block_offs.last().copied().unwrap_or_default().saturating_sub(first);
+    let block_values = block_start..block_end;
+    rslt.data.extend_from_slice(data.get(block_values.clone()).ok_or(crate::Error::UnknownError)?);
+    rslt.indcs.extend_from_slice(indcs.get(block_values).ok_or(crate::Error::UnknownError)?);
+    for [a, b] in windows2(block_offs) {
+      let last = rslt.offs.last().copied().unwrap_or_default();
+      rslt.offs.push(last + (b - a));
+    }
+  }
+  Ok(())
+}
+
+// Folds every line in parallel. See `Csl::par_fold_lines`.
+#[cfg(feature = "with-rayon")]
+#[inline]
+pub(crate) fn par_fold_lines<DATA, DS, F, ID, IS, OS, R, T, const D: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  identity: ID,
+  fold: F,
+  reduce: R,
+) -> T
+where
+  DATA: Sync,
+  DS: AsRef<[DATA]>,
+  F: Fn(T, usize, CslRef<'_, DATA, 1>) -> T + Send + Sync,
+  ID: Fn() -> T + Send + Sync,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+  R: Fn(T, T) -> T + Send + Sync,
+  T: Send,
+{
+  use rayon::prelude::*;
+  let last_dim = if let Some(r) = csl.dims.last() { *r } else { return identity() };
+  let data = csl.data.as_ref();
+  let indcs = csl.indcs.as_ref();
+  let offs = csl.offs.as_ref();
+  let first_off = offs.first().copied().unwrap_or_default();
+  offs
+    .par_windows(2)
+    .enumerate()
+    .fold(&identity, |acc, (idx, line_offs)| {
+      let range = if let [a, b] = *line_offs {
+        a.saturating_sub(first_off)..b.saturating_sub(first_off)
+      } else {
+        0..0
+      };
+      let line = CslRef {
+        data: data.get(range.clone()).unwrap_or_default(),
+        dims: [last_dim],
+        indcs: indcs.get(range).unwrap_or_default(),
+        offs: line_offs,
+        outermost_stride: Some(1),
+      };
+      fold(acc, idx, line)
+    })
+    .reduce(&identity, reduce)
+}
+
+// Minimum number of elements that makes validation run in parallel.
+#[cfg(feature = "with-rayon")]
+pub(crate) const PARALLEL_VALIDATION_THRESHOLD: usize = 1 << 16;
+
+// Index of the first offset that is smaller than its predecessor.
+#[inline]
+pub(crate) fn unordered_off(offs: &[usize]) -> Option<usize> {
+  #[cfg(feature = "with-rayon")]
+  if offs.len() > PARALLEL_VALIDATION_THRESHOLD {
+    use rayon::prelude::*;
+    let rest = offs.get(1..).unwrap_or_default();
+    return offs.par_iter().zip(rest).position_first(|(a, b)| a > b).map(|idx| idx + 1);
+  }
+  windows2(offs).position(|[a, b]| a > b).map(|idx| idx + 1)
+}
+
+// First index that is greater or equal to `dim_len`.
+#[inline]
+pub(crate) fn out_of_bounds_idx(indcs: &[usize], dim_len: usize) -> Option<usize> {
+  #[cfg(feature = "with-rayon")]
+  if indcs.len() > PARALLEL_VALIDATION_THRESHOLD {
+    use rayon::prelude::*;
+    return indcs.par_iter().find_first(|&&idx| idx >= dim_len).copied();
+  }
+  indcs.iter().find(|&&idx| idx >= dim_len).copied()
+}
+
+// Error of the first line whose indices aren't in strictly ascending order.
+#[inline]
+pub(crate) fn invalid_line(indcs: &[usize], offs: &[usize]) -> Option<CslError> {
+  let first_off = offs.first().copied().unwrap_or_default();
+  let check = |line_idx, a: usize, b: usize| {
+    let line_indcs = indcs.get(a - first_off..b - first_off).unwrap_or_default();
+    for [prev, next] in windows2(line_indcs) {
+      if prev == next {
+        return Some(CslError::DuplicatedIndices { idx: *prev, line_idx });
+      }
+      if prev > next {
+        return Some(CslError::UnsortedIndices { line_idx });
+      }
+    }
+    None
+  };
+  #[cfg(feature = "with-rayon")]
+  if indcs.len() > PARALLEL_VALIDATION_THRESHOLD {
+    use rayon::prelude::*;
+    let rest = offs.get(1..).unwrap_or_default();
+    return offs
+      .par_iter()
+      .zip(rest)
+      .enumerate()
+      .find_map_first(|(line_idx, (&a, &b))| check(line_idx, a, b));
+  }
+  windows2(offs).enumerate().find_map(|(line_idx, [&a, &b])| check(line_idx, a, b))
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn line_nnz_histogram(offs: &[usize]) -> Vec<usize> {
+  let mut rslt = Vec::new();
+  for [a, b] in windows2(offs) {
+    let nnz = b - a;
+    if rslt.len() <= nnz {
+      rslt.resize(nnz + 1, 0);
+    }
+    if let Some(r) = rslt.get_mut(nnz) {
+      *r += 1;
+    }
+  }
+  rslt
+}
+
+// Each boundary is the outermost index whose offset is the closest to an equal share of the
+// total number of elements.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn partition_lines<const D: usize>(
+  dims: &[usize; D],
+  offs: &[usize],
+  num_parts: usize,
+) -> Vec<Range<usize>> {
+  let outermost = dims.first().copied().unwrap_or_default();
+  if num_parts == 0 || outermost == 0 {
+    return Vec::new();
+  }
+  let stride = if D > 1 { outermost_stride(dims).unwrap_or(usize::MAX) } else { 0 };
+  if stride == 0 {
+    return alloc::vec![0..outermost];
+  }
+  let block_off = |idx: usize| {
+    let off_idx = idx.saturating_mul(stride).min(offs.len().saturating_sub(1));
+    offs.get(off_idx).copied().unwrap_or_default()
+  };
+  let first = block_off(0);
+  let total = block_off(outermost) - first;
+  let mut rslt = Vec::with_capacity(num_parts);
+  let mut start = 0;
+  let mut end = 0;
+  for part in 1..num_parts {
+    let share = u128::try_from(total).unwrap_or_default()
+      * u128::try_from(part).unwrap_or_default()
+      / u128::try_from(num_parts).unwrap_or(1);
+    let target = first + usize::try_from(share).unwrap_or(total);
+    while end < outermost && block_off(end) < target {
+      end += 1;
+    }
+    if end > start + 1 && target - block_off(end - 1) < block_off(end) - target {
+      end -= 1;
+    }
+    if end > start {
+      rslt.push(start..end);
+      start = end;
+    }
+  }
+  if start < outermost {
+    rslt.push(start..outermost);
+  }
+  rslt
+}
+
+// Invariants whose verification doesn't depend on the number of lines or stored elements.
+#[inline]
+pub(crate) fn validate_lengths<const D: usize>(
+  dims: &[usize; D],
+  data_len: usize,
+  indcs_len: usize,
+  offs: &[usize],
+) -> crate::Result<()> {
+  if has_innermost_dims_zero(dims) {
+    return Err(CslError::InnermostDimsZero.into());
+  }
+
+  if data_len != indcs_len {
+    return Err(CslError::DiffDataIndcsLength { data_len, indcs_len }.into());
+  }
+
+  if data_len > crate::utils::max_nnz(dims) {
+    return Err(CslError::DataIndcsLengthGreaterThanDimsLength.into());
+  }
+
+  if !dims.is_empty() {
+    let expected = correct_offs_len(dims)?;
+    if offs.len() != expected {
+      return Err(CslError::InvalidOffsetsLength { expected, found: offs.len() }.into());
+    }
+  }
+
+  let max_nnz = crate::utils::checked_max_nnz(dims);
+  if max_nnz.and_then(|elem| usize::try_from(elem).ok()).is_none() {
+    return Err(CslError::DimsProductOverflow.into());
+  }
+
+  if let (Some(first), Some(last)) = (offs.first(), offs.last()) {
+    let last_off = if let Some(r) = last.checked_sub(*first) {
+      r
+    } else {
+      let offs_idx = unordered_off(offs).unwrap_or_default();
+      return Err(CslError::InvalidOffsetsOrder { offs_idx }.into());
+    };
+    if last_off != data_len {
+      return Err(CslError::LastOffsetDifferentNnz { last_off, nnz: data_len }.into());
+    }
+  }
+
+  Ok(())
+}
+
+// All invariants, including the ones that are verified by visiting every offset and every index.
+//
+// The order of the checks is part of the behavior of `Csl::new`, e.g., unordered offsets take
+// precedence over wrong lengths.
+#[inline]
+pub(crate) fn validate_all<const D: usize>(
+  dims: &[usize; D],
+  data_len: usize,
+  indcs: &[usize],
+  offs: &[usize],
+) -> crate::Result<()> {
+  if has_innermost_dims_zero(dims) {
+    return Err(CslError::InnermostDimsZero.into());
+  }
+
+  if data_len != indcs.len() {
+    return Err(CslError::DiffDataIndcsLength { data_len, indcs_len: indcs.len() }.into());
+  }
+
+  if let Some(offs_idx) = unordered_off(offs) {
+    return Err(CslError::InvalidOffsetsOrder { offs_idx }.into());
+  }
+
+  if data_len > crate::utils::max_nnz(dims) {
+    return Err(CslError::DataIndcsLengthGreaterThanDimsLength.into());
+  }
+
+  if let Some(&dim_len) = dims.last() {
+    if let Some(idx) = out_of_bounds_idx(indcs, dim_len) {
+      return Err(CslError::IndcsGreaterThanEqualDimLength { dim_len, idx }.into());
+    }
+    let expected = correct_offs_len(dims)?;
+    if offs.len() != expected {
+      return Err(CslError::InvalidOffsetsLength { expected, found: offs.len() }.into());
+    }
+  }
+
+  let max_nnz = crate::utils::checked_max_nnz(dims);
+  if max_nnz.and_then(|elem| usize::try_from(elem).ok()).is_none() {
+    return Err(CslError::DimsProductOverflow.into());
+  }
+
+  if let (Some(first), Some(last)) = (offs.first(), offs.last()) {
+    let last_off = last.saturating_sub(*first);
+    if last_off != data_len {
+      return Err(CslError::LastOffsetDifferentNnz { last_off, nnz: data_len }.into());
+    }
+  }
+
+  if let Some(err) = invalid_line(indcs, offs) {
+    return Err(err.into());
+  }
+
+  Ok(())
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn mode_n_product<DATA, DS, IS, OS, const D: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  dense_matrix: &[DATA],
+  rows: usize,
+  axis: usize,
+) -> crate::Result<CslVec<DATA, D>>
+where
+  DATA: Add<Output = DATA> + Clone + Default + Mul<Output = DATA>,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  let cols = *csl.dims.get(axis).ok_or(CslError::InvalidAxis { axis })?;
+  let expected = rows.checked_mul(cols).ok_or(CslError::DimsProductOverflow)?;
+  if dense_matrix.len() != expected {
+    return Err(CslError::InvalidDenseLength { expected, found: dense_matrix.len() }.into());
+  }
+  let mut dims = csl.dims;
+  if let Some(r) = dims.get_mut(axis) {
+    *r = rows;
+  }
+
+  let data = csl.data.as_ref();
+  let indcs = csl.indcs.as_ref();
+  let offs = csl.offs.as_ref();
+  let mut products = BTreeMap::new();
+  for (line, range) in line_ranges(offs).enumerate() {
+    let mut elem_indcs = outermost_indcs(&csl.dims, line);
+    let line_data = data.get(range.clone()).unwrap_or_default();
+    let line_indcs = indcs.get(range).unwrap_or_default();
+    for (value, &innermost_idx) in line_data.iter().zip(line_indcs) {
+      if let Some(r) = elem_indcs.last_mut() {
+        *r = innermost_idx;
+      }
+      let col = elem_indcs.get(axis).copied().unwrap_or_default();
+      for row in 0..rows {
+        let coefficient = dense_matrix.get(row * cols + col).cloned().unwrap_or_default();
+        let mut new_indcs = elem_indcs;
+        if let Some(r) = new_indcs.get_mut(axis) {
+          *r = row;
+        }
+        let sum = products.entry(new_indcs).or_insert_with(DATA::default);
+        *sum = mem::take(sum) + value.clone() * coefficient;
+      }
+    }
+  }
+
+  from_sorted_elements(dims, products.len(), products)
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn remap_indices<DATA, DS, F, IS, OS, const D: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  axis: usize,
+  mapping_len: usize,
+  mut mapping: F,
+) -> crate::Result<CslVec<DATA, D>>
+where
+  DATA: Clone,
+  DS: AsRef<[DATA]>,
+  F: FnMut(usize) -> Option<usize>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  let dim_len = *csl.dims.get(axis).ok_or(CslError::InvalidAxis { axis })?;
+  if mapping_len != dim_len {
+    return Err(CslError::InvalidMapping.into());
+  }
+  let new_len = (0..dim_len).filter_map(&mut mapping).max().map_or(0, |max| max + 1);
+  let mut is_used = alloc::vec![false; new_len];
+  for new_idx in (0..dim_len).filter_map(&mut mapping) {
+    match is_used.get_mut(new_idx) {
+      Some(r) if !*r => *r = true,
+      _ => return Err(CslError::InvalidMapping.into()),
+    }
+  }
+  let mut dims = csl.dims;
+  if let Some(r) = dims.get_mut(axis) {
+    *r = new_len;
+  }
+
+  let data = csl.data.as_ref();
+  let indcs = csl.indcs.as_ref();
+  let mut elements = Vec::with_capacity(data.len());
+  for (line, range) in line_ranges(csl.offs.as_ref()).enumerate() {
+    let mut elem_indcs = outermost_indcs(&csl.dims, line);
+    let line_data = data.get(range.clone()).unwrap_or_default();
+    let line_indcs = indcs.get(range).unwrap_or_default();
+    for (value, &innermost_idx) in line_data.iter().zip(line_indcs) {
+      if let Some(r) = elem_indcs.last_mut() {
+        *r = innermost_idx;
+      }
+      let mut new_indcs = elem_indcs;
+      if let Some(r) = new_indcs.get_mut(axis) {
+        match mapping(*r) {
+          Some(new_idx) => *r = new_idx,
+          None => continue,
+        }
+      }
+      elements.push((new_indcs, value.clone()));
+    }
+  }
+  elements.sort_unstable_by_key(|elem| elem.0);
+
+  from_sorted_elements(dims, elements.len(), elements)
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn pad<DATA, DS, IS, OS, const D: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  axis: usize,
+  before: usize,
+  after: usize,
+) -> crate::Result<CslVec<DATA, D>>
+where
+  DATA: Clone,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  let dim_len = *csl.dims.get(axis).ok_or(CslError::InvalidAxis { axis })?;
+  let new_len = dim_len
+    .checked_add(before)
+    .and_then(|len| len.checked_add(after))
+    .ok_or(CslError::DimsProductOverflow)?;
+  let mut dims = csl.dims;
+  if let Some(r) = dims.get_mut(axis) {
+    *r = new_len;
+  }
+  // A zero-length dimension has no positions, therefore, only padding remains
+  if dim_len == 0 && new_len != 0 {
+    unuse_dims_before_last_zero(&mut dims);
+    return from_sorted_elements(dims, 0, core::iter::empty());
+  }
+  // A constant shift along a single axis preserves the lexicographic order of the elements.
+  map_indcs(csl, dims, |indcs| {
+    let mut new_indcs = *indcs;
+    if let Some(r) = new_indcs.get_mut(axis) {
+      *r += before;
+    }
+    new_indcs
+  })
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn roll<DATA, DS, IS, OS, const D: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  axis: usize,
+  amount: isize,
+) -> crate::Result<CslVec<DATA, D>>
+where
+  DATA: Clone,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  let dim_len = *csl.dims.get(axis).ok_or(CslError::InvalidAxis { axis })?;
+
+  let data = csl.data.as_ref();
+  let indcs = csl.indcs.as_ref();
+  let mut elements = Vec::with_capacity(data.len());
+  for (line, range) in line_ranges(csl.offs.as_ref()).enumerate() {
+    let mut elem_indcs = outermost_indcs(&csl.dims, line);
+    let line_data = data.get(range.clone()).unwrap_or_default();
+    let line_indcs = indcs.get(range).unwrap_or_default();
+    for (value, &innermost_idx) in line_data.iter().zip(line_indcs) {
+      if let Some(r) = elem_indcs.last_mut() {
+        *r = innermost_idx;
+      }
+      let mut new_indcs = elem_indcs;
+      if let Some(r) = new_indcs.get_mut(axis) {
+        *r = roll_idx(*r, dim_len, amount);
+      }
+      elements.push((new_indcs, value.clone()));
+    }
+  }
+  elements.sort_unstable_by_key(|elem| elem.0);
+
+  from_sorted_elements(csl.dims, elements.len(), elements)
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn squeeze<DATA, DS, IS, OS, const D: usize, const TD: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  axis: usize,
+) -> crate::Result<CslVec<DATA, TD>>
+where
+  DATA: Clone,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  if TD.checked_add(1) != Some(D) {
+    return Err(CslError::InvalidRank { expected: D.saturating_sub(1), found: TD }.into());
+  }
+  let dim_len = *csl.dims.get(axis).ok_or(CslError::InvalidAxis { axis })?;
+  if dim_len != 1 {
+    return Err(CslError::NonUnitAxis { axis, dim_len }.into());
+  }
+  map_indcs(csl, remove_axis(&csl.dims, axis), |indcs| remove_axis(indcs, axis))
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn tile<DATA, DS, IS, OS, const D: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  reps: [usize; D],
+) -> crate::Result<CslVec<DATA, D>>
+where
+  DATA: Clone,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  let overflow = || crate::Error::from(CslError::DimsProductOverflow);
+  let mut dims = try_create_array(|axis| {
+    let dim = csl.dims.get(axis).copied().unwrap_or_default();
+    dim.checked_mul(reps.get(axis).copied().unwrap_or_default()).ok_or_else(overflow)
+  })?;
+  unuse_dims_before_last_zero(&mut dims);
+  // Unused dimensions are never replicated
+  let copies = if reps.contains(&0) {
+    0
+  } else {
+    let mut used_reps = reps.iter().zip(&csl.dims).filter(|&(_, &dim)| dim != 0);
+    used_reps.try_fold(1usize, |acc, (&rep, _)| acc.checked_mul(rep)).ok_or_else(overflow)?
+  };
+
+  let data = csl.data.as_ref();
+  let indcs = csl.indcs.as_ref();
+  let mut elements = Vec::with_capacity(data.len().checked_mul(copies).ok_or_else(overflow)?);
+  for (line, range) in line_ranges(csl.offs.as_ref()).enumerate() {
+    let mut elem_indcs = outermost_indcs(&csl.dims, line);
+    let line_data = data.get(range.clone()).unwrap_or_default();
+    let line_indcs = indcs.get(range).unwrap_or_default();
+    for (value, &innermost_idx) in line_data.iter().zip(line_indcs) {
+      if let Some(r) = elem_indcs.last_mut() {
+        *r = innermost_idx;
+      }
+      for copy in 0..copies {
+        let mut new_indcs = elem_indcs;
+        let mut remaining = copy;
+        let iter = new_indcs.iter_mut().zip(&csl.dims).zip(&reps).rev();
+        for ((idx, &dim), &rep) in iter.filter(|&((_, &dim), _)| dim != 0) {
+          *idx += remaining % rep * dim;
+          remaining /= rep;
+        }
+        elements.push((new_indcs, value.clone()));
+      }
+    }
+  }
+  elements.sort_unstable_by_key(|elem| elem.0);
+
+  from_sorted_elements(dims, elements.len(), elements)
+}
+
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn unsqueeze<DATA, DS, IS, OS, const D: usize, const TD: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  axis: usize,
+) -> crate::Result<CslVec<DATA, TD>>
+where
+  DATA: Clone,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  if D.checked_add(1) != Some(TD) {
+    return Err(CslError::InvalidRank { expected: D.saturating_add(1), found: TD }.into());
+  }
+  if axis > D {
+    return Err(CslError::InvalidAxis { axis }.into());
+  }
+  map_indcs(csl, insert_axis(&csl.dims, axis, 1), |indcs| insert_axis(indcs, axis, 0))
+}
+
+// Moves every element to the indices returned by `cb`, which must preserve the lexicographic
+// order of the elements.
+#[cfg(feature = "alloc")]
+#[inline]
+fn map_indcs<DATA, DS, F, IS, OS, const D: usize, const TD: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  dims: [usize; TD],
+  cb: F,
+) -> crate::Result<CslVec<DATA, TD>>
+where
+  DATA: Clone,
+  DS: AsRef<[DATA]>,
+  F: Fn(&[usize; D]) -> [usize; TD],
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  let data = csl.data.as_ref();
+  let indcs = csl.indcs.as_ref();
+  let cb = &cb;
+  let elements = line_ranges(csl.offs.as_ref()).enumerate().flat_map(|(line, range)| {
+    let outermost = outermost_indcs(&csl.dims, line);
+    let line_data = data.get(range.clone()).unwrap_or_default();
+    let line_indcs = indcs.get(range).unwrap_or_default();
+    line_data.iter().zip(line_indcs).map(move |(value, &innermost_idx)| {
+      let mut elem_indcs = outermost;
+      if let Some(r) = elem_indcs.last_mut() {
+        *r = innermost_idx;
+      }
+      (cb(&elem_indcs), value.clone())
+    })
+  });
+  from_sorted_elements(dims, data.len(), elements)
+}
+
+// Offsets shifted to start at zero. Offsets of lazily constructed instances aren't validated,
+// therefore, an offset that is smaller than the first one is reported instead of underflowing.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn rebased_offs(offs: &[usize]) -> crate::Result<Vec<usize>> {
+  let first = offs.first().copied().unwrap_or_default();
+  offs.iter().map(|off| off.checked_sub(first)).collect::<Option<_>>().ok_or_else(|| {
+    let offs_idx = windows2(offs).position(|[a, b]| b < a).map_or(0, |idx| idx + 1);
+    CslError::InvalidOffsetsOrder { offs_idx }.into()
+  })
+}
+
+// Only leading dimensions can have a zero length, therefore, all dimensions that precede the
+// last zero-length dimension become unused.
+#[cfg(feature = "alloc")]
+#[inline]
+fn unuse_dims_before_last_zero<const D: usize>(dims: &mut [usize; D]) {
+  if let Some(last_zero) = dims.iter().rposition(|&dim| dim == 0) {
+    dims.iter_mut().take(last_zero).for_each(|dim| *dim = 0);
+  }
+}
+
+// Builds a validated structure from elements that are sorted in lexicographic order of their
+// indices.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn from_sorted_elements<DATA, I, const D: usize>(
+  dims: [usize; D],
+  nnz: usize,
+  elements: I,
+) -> crate::Result<CslVec<DATA, D>>
+where
+  I: IntoIterator<Item = ([usize; D], DATA)>,
+{
+  let offs_len = correct_offs_len(&dims)?;
+  let mut rslt = CslVec::<DATA, D>::with_capacity(nnz, offs_len);
+  rslt.offs.push(0);
+  for (elem_indcs, value) in elements {
+    let line = line_idx(&dims, &elem_indcs).ok_or(crate::Error::UnknownError)?;
+    while rslt.offs.len() <= line {
+      rslt.offs.push(rslt.data.len());
+    }
+    rslt.data.push(value);
+    rslt.indcs.push(elem_indcs.last().copied().unwrap_or_default());
+  }
+  while rslt.offs.len() < offs_len {
+    rslt.offs.push(rslt.data.len());
+  }
+  CslVec::new(dims, rslt.data, rslt.indcs, rslt.offs)
+}
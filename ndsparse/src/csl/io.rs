@@ -0,0 +1,313 @@
+//! Interoperability with the NumPy archive (`.npz`) layout of `scipy.sparse.save_npz`.
+//!
+//! The archive contains the `data`, `indices`, `indptr`, `shape` and `format` arrays, where
+//! `format` is always `b"csr"`. Two-dimensional structures can be loaded in Python with
+//! `scipy.sparse.load_npz` and files written by `scipy.sparse.save_npz` with the `csr` format can
+//! be loaded with [`from_npz`]. Structures with more dimensions use the same layout, where
+//! `indptr` contains the offsets of every line and `shape` contains all dimensions.
+//!
+//! # Example
+//!
+//! ```rust
+//! # fn main() -> ndsparse::Result<()> {
+//! use ndsparse::csl::{io::{from_npz, to_npz}, CslVec};
+//! let csl = CslVec::new([2, 3], vec![1.0, 2.0, 3.0], vec![0, 2, 1], vec![0, 2, 3])?;
+//! let mut cursor = std::io::Cursor::new(Vec::new());
+//! to_npz(&csl, &mut cursor)?;
+//! let loaded: CslVec<f64, 2> = from_npz(&mut cursor)?;
+//! assert_eq!(loaded, csl);
+//! # Ok(()) }
+//! ```
+
+use crate::csl::{persist::PersistDtype, rebased_offs, Csl, CslError, CslVec};
+use alloc::{format, string::String, vec::Vec};
+use cl_traits::Storage;
+use core::{
+  convert::{TryFrom, TryInto},
+  fmt, mem,
+};
+use std::io::{Read, Seek, Write};
+use zip::{result::ZipError, write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+const DATA: &str = "data";
+const FORMAT: &str = "format";
+const INDICES: &str = "indices";
+const INDPTR: &str = "indptr";
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+const SHAPE: &str = "shape";
+
+/// Element type that can be stored in a NumPy array.
+pub trait NpyDtype: PersistDtype {
+  /// NumPy type descriptor of the little endian representation, e.g., `<f8`.
+  const DESCR: &'static str;
+}
+
+macro_rules! impl_npy_dtype {
+  ($($ty:ty => $descr:literal),*) => {
+    $(
+      impl NpyDtype for $ty {
+        const DESCR: &'static str = $descr;
+      }
+    )*
+  };
+}
+
+impl_npy_dtype!(
+  i8 => "|i1", i16 => "<i2", i32 => "<i4", i64 => "<i8", u8 => "|u1", u16 => "<u2",
+  u32 => "<u4", u64 => "<u8", f32 => "<f4", f64 => "<f8"
+);
+
+/// Any error related to NumPy archives
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum NpzError {
+  /// An array is stored in column-major (Fortran) order, which isn't supported
+  FortranOrder {
+    /// Name of the array
+    name: &'static str,
+  },
+  /// The input isn't a valid ZIP archive
+  InvalidArchive,
+  /// An array isn't a valid NumPy array or doesn't have the expected shape
+  InvalidArray {
+    /// Name of the array
+    name: &'static str,
+  },
+  /// A required array isn't present in the archive
+  MissingArray {
+    /// Name of the array
+    name: &'static str,
+  },
+  /// The type of an array isn't supported or is different than the requested type
+  UnsupportedDtype {
+    /// Name of the array
+    name: &'static str,
+  },
+  /// The stored sparse format isn't `csr`
+  UnsupportedFormat,
+}
+
+impl fmt::Display for NpzError {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match *self {
+      Self::FortranOrder { name } => write!(f, "FortranOrder ({})", name),
+      Self::InvalidArchive => write!(f, "InvalidArchive"),
+      Self::InvalidArray { name } => write!(f, "InvalidArray ({})", name),
+      Self::MissingArray { name } => write!(f, "MissingArray ({})", name),
+      Self::UnsupportedDtype { name } => write!(f, "UnsupportedDtype ({})", name),
+      Self::UnsupportedFormat => write!(f, "UnsupportedFormat"),
+    }
+  }
+}
+
+impl std::error::Error for NpzError {}
+
+#[cfg(feature = "with-defmt")]
+impl defmt::Format for NpzError {
+  #[inline]
+  fn format(&self, fmt: defmt::Formatter<'_>) {
+    defmt::write!(fmt, "{}", defmt::Display2Format(self))
+  }
+}
+
+/// Reads a structure from a NumPy archive written by [`to_npz`] or `scipy.sparse.save_npz`.
+///
+/// Indices and offsets can be stored as any 32 or 64 bits integer while the data type must match
+/// `DATA`. All invariants of [`Csl::new`] are verified.
+#[inline]
+pub fn from_npz<DATA, R, const D: usize>(reader: R) -> crate::Result<CslVec<DATA, D>>
+where
+  DATA: NpyDtype,
+  R: Read + Seek,
+{
+  let mut archive = ZipArchive::new(reader).map_err(zip_err)?;
+  let (descr, _, payload) = read_npy(&mut archive, FORMAT)?;
+  if !descr.starts_with("|S") || payload.split(|&byte| byte == 0).next() != Some(b"csr") {
+    return Err(NpzError::UnsupportedFormat.into());
+  }
+  let shape = read_usizes(&mut archive, SHAPE)?;
+  if shape.len() != D {
+    return Err(CslError::InvalidRank { expected: D, found: shape.len() }.into());
+  }
+  let dims = cl_traits::try_create_array(|idx| shape.get(idx).copied().ok_or(()))
+    .map_err(|_err| crate::Error::UnknownError)?;
+  let (descr, shape, payload) = read_npy(&mut archive, DATA)?;
+  if descr != DATA::DESCR {
+    return Err(NpzError::UnsupportedDtype { name: DATA }.into());
+  }
+  let size = mem::size_of::<DATA>();
+  if shape.len() != 1 || payload.len() != shape.iter().product::<usize>().saturating_mul(size) {
+    return Err(NpzError::InvalidArray { name: DATA }.into());
+  }
+  let data = payload
+    .chunks_exact(size)
+    .map(|bytes| DATA::from_le_slice(bytes).ok_or(crate::Error::UnknownError))
+    .collect::<crate::Result<Vec<_>>>()?;
+  let indcs = read_usizes(&mut archive, INDICES)?;
+  let offs = read_usizes(&mut archive, INDPTR)?;
+  CslVec::new(dims, data, indcs, offs)
+}
+
+/// Writes `csl` as a compressed NumPy archive using the layout of `scipy.sparse.save_npz`.
+/// Offsets are rebased to start at zero and all indices are stored as `i64`.
+#[inline]
+pub fn to_npz<DATA, DS, IS, OS, W, const D: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  writer: W,
+) -> crate::Result<()>
+where
+  DATA: NpyDtype,
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+  W: Seek + Write,
+{
+  let offs = rebased_offs(csl.offs.as_ref())?;
+  let mut zip = ZipWriter::new(writer);
+  let data = csl.data.as_ref();
+  let mut buffer = npy_header(DATA::DESCR, Some(data.len()));
+  for elem in data {
+    elem.extend_le(&mut buffer);
+  }
+  write_npy(&mut zip, DATA, &buffer)?;
+  let mut buffer = npy_header("|S3", None);
+  buffer.extend_from_slice(b"csr");
+  write_npy(&mut zip, FORMAT, &buffer)?;
+  write_npy(&mut zip, INDICES, &i64_npy(csl.indcs.as_ref().iter().copied())?)?;
+  write_npy(&mut zip, INDPTR, &i64_npy(offs.into_iter())?)?;
+  write_npy(&mut zip, SHAPE, &i64_npy(csl.dims.iter().copied())?)?;
+  let _writer = zip.finish().map_err(zip_err)?;
+  Ok(())
+}
+
+fn header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+  let start = header.find(key)? + key.len();
+  let rest = header.get(start..)?.trim_start().strip_prefix(':')?.trim_start();
+  let end = match rest.chars().next()? {
+    '(' => rest.find(')')? + 1,
+    '\'' => rest.get(1..)?.find('\'')? + 2,
+    _ => rest.find([',', '}'])?,
+  };
+  rest.get(..end)
+}
+
+fn i64_npy<I>(iter: I) -> crate::Result<Vec<u8>>
+where
+  I: ExactSizeIterator<Item = usize>,
+{
+  let mut buffer = npy_header("<i8", Some(iter.len()));
+  for elem in iter {
+    let value = i64::try_from(elem).map_err(|_err| CslError::IndexOverflow)?;
+    buffer.extend_from_slice(&value.to_le_bytes());
+  }
+  Ok(buffer)
+}
+
+fn npy_header(descr: &str, len: Option<usize>) -> Vec<u8> {
+  let shape = len.map(|elem| format!("({},)", elem)).unwrap_or_else(|| "()".into());
+  let dict = format!("{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}", descr, shape);
+  let unpadded = NPY_MAGIC.len() + 4 + dict.len() + 1;
+  let padding = (64 - unpadded % 64) % 64;
+  let header_len = u16::try_from(dict.len() + padding + 1).unwrap_or(u16::MAX);
+  let mut buffer = Vec::with_capacity(unpadded + padding);
+  buffer.extend_from_slice(NPY_MAGIC);
+  buffer.extend_from_slice(&[1, 0]);
+  buffer.extend_from_slice(&header_len.to_le_bytes());
+  buffer.extend_from_slice(dict.as_bytes());
+  buffer.extend(core::iter::repeat_n(b' ', padding));
+  buffer.push(b'\n');
+  buffer
+}
+
+fn read_npy<R>(
+  archive: &mut ZipArchive<R>,
+  name: &'static str,
+) -> crate::Result<(String, Vec<usize>, Vec<u8>)>
+where
+  R: Read + Seek,
+{
+  let invalid = || crate::Error::from(NpzError::InvalidArray { name });
+  let mut bytes = Vec::new();
+  let file_name = format!("{}.npy", name);
+  let mut file = archive.by_name(&file_name).map_err(|err| match err {
+    ZipError::FileNotFound => NpzError::MissingArray { name }.into(),
+    _ => zip_err(err),
+  })?;
+  let _ = file.read_to_end(&mut bytes)?;
+  let rest = bytes.strip_prefix(NPY_MAGIC).ok_or_else(invalid)?;
+  let (header_len, rest) = match *rest {
+    [1, _, a, b, ref rest @ ..] => (usize::from(u16::from_le_bytes([a, b])), rest),
+    [2..=3, _, a, b, c, d, ref rest @ ..] => {
+      (usize::try_from(u32::from_le_bytes([a, b, c, d])).map_err(|_err| invalid())?, rest)
+    }
+    _ => return Err(invalid()),
+  };
+  let header = rest.get(..header_len).ok_or_else(invalid)?;
+  let header = core::str::from_utf8(header).map_err(|_err| invalid())?;
+  let payload = rest.get(header_len..).ok_or_else(invalid)?.to_vec();
+  match header_value(header, "'fortran_order'") {
+    Some("False") => {}
+    Some("True") => return Err(NpzError::FortranOrder { name }.into()),
+    _ => return Err(invalid()),
+  }
+  let descr = header_value(header, "'descr'").ok_or_else(invalid)?.trim_matches('\'');
+  let shape = header_value(header, "'shape'")
+    .ok_or_else(invalid)?
+    .trim_matches(|c| c == '(' || c == ')')
+    .split(',')
+    .map(str::trim)
+    .filter(|elem| !elem.is_empty())
+    .map(|elem| elem.parse().map_err(|_err| invalid()))
+    .collect::<crate::Result<Vec<usize>>>()?;
+  Ok((descr.into(), shape, payload))
+}
+
+fn read_usizes<R>(archive: &mut ZipArchive<R>, name: &'static str) -> crate::Result<Vec<usize>>
+where
+  R: Read + Seek,
+{
+  let (descr, shape, payload) = read_npy(archive, name)?;
+  let len = shape.iter().product::<usize>();
+  let size = match descr.as_str() {
+    "<i4" | "<u4" => 4,
+    "<i8" | "<u8" => 8,
+    _ => return Err(NpzError::UnsupportedDtype { name }.into()),
+  };
+  if shape.len() > 1 || payload.len() != len.saturating_mul(size) {
+    return Err(NpzError::InvalidArray { name }.into());
+  }
+  let is_signed = descr.starts_with("<i");
+  payload
+    .chunks_exact(size)
+    .map(|bytes| -> crate::Result<usize> {
+      let value = match (size, is_signed) {
+        (4, true) => i64::from(i32::from_le_bytes(bytes.try_into().unwrap_or_default())),
+        (4, false) => i64::from(u32::from_le_bytes(bytes.try_into().unwrap_or_default())),
+        (_, true) => i64::from_le_bytes(bytes.try_into().unwrap_or_default()),
+        (_, false) => {
+          let value = u64::from_le_bytes(bytes.try_into().unwrap_or_default());
+          return usize::try_from(value).map_err(|_err| CslError::IndexOverflow.into());
+        }
+      };
+      usize::try_from(value).map_err(|_err| NpzError::InvalidArray { name }.into())
+    })
+    .collect()
+}
+
+fn write_npy<W>(zip: &mut ZipWriter<W>, name: &'static str, buffer: &[u8]) -> crate::Result<()>
+where
+  W: Seek + Write,
+{
+  let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+  zip.start_file(format!("{}.npy", name), options).map_err(zip_err)?;
+  zip.write_all(buffer)?;
+  Ok(())
+}
+
+fn zip_err(err: ZipError) -> crate::Error {
+  match err {
+    ZipError::Io(err) => err.into(),
+    _ => NpzError::InvalidArchive.into(),
+  }
+}
@@ -0,0 +1,494 @@
+//! Operations between structures of different shapes.
+
+use crate::csl::{
+  correct_offs_len, line_offs, line_ranges, merge_line, outermost_indcs, outermost_stride, Csl,
+  CslError, CslVec, Scalar,
+};
+use cl_traits::{create_array, try_create_array};
+
+/// Element-wise sum of `a` and `b`, whose dimensions are broadcast like
+/// [`broadcast_apply`]. Positions stored by only one of the operands keep their values.
+///
+/// # Arguments
+///
+/// * `a`: Left operand
+/// * `b`: Right operand
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::csl::{ops::add, CslVec};
+/// // [[1, 0, 2], [0, 3, 0]]
+/// let a = CslVec::new([2, 3], vec![1.0, 2.0, 3.0], vec![0, 2, 1], vec![0, 2, 3])?;
+/// // [[0, 4, 5], [0, 0, 0]]
+/// let b = CslVec::new([2, 3], vec![4.0, 5.0], vec![1, 2], vec![0, 2, 2])?;
+/// let expected = CslVec::new([2, 3], vec![1.0, 4.0, 7.0, 3.0], vec![0, 1, 2, 1], vec![0, 3, 4])?;
+/// assert_eq!(add(&a, &b)?, expected);
+/// # Ok(()) }
+/// ```
+#[inline]
+pub fn add<DATA, DSA, DSB, ISA, ISB, OSA, OSB, const D: usize>(
+  a: &Csl<DSA, ISA, OSA, D>,
+  b: &Csl<DSB, ISB, OSB, D>,
+) -> crate::Result<CslVec<DATA, D>>
+where
+  DATA: Scalar,
+  DSA: AsRef<[DATA]>,
+  DSB: AsRef<[DATA]>,
+  ISA: AsRef<[usize]>,
+  ISB: AsRef<[usize]>,
+  OSA: AsRef<[usize]>,
+  OSB: AsRef<[usize]>,
+{
+  broadcast_apply(a, b, |a, b| match (a, b) {
+    (Some(&a), Some(&b)) => Some(DATA::narrow(a.widen() + b.widen())),
+    (a, b) => a.or(b).copied(),
+  })
+}
+
+/// Concatenates 2D structures along the main diagonal, i.e., the elements of each matrix are
+/// shifted by the number of rows and columns of all preceding matrices.
+///
+/// # Arguments
+///
+/// * `iter`: Matrices in the order they appear along the diagonal
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::csl::{ops::block_diag, CslVec};
+/// // [1, 2]
+/// let a = CslVec::new([1, 2], vec![1, 2], vec![0, 1], vec![0, 2])?;
+/// // [_, 3]
+/// // [4, _]
+/// let b = CslVec::new([2, 2], vec![3, 4], vec![1, 0], vec![0, 1, 2])?;
+/// let diag = block_diag([&a, &b].iter().copied())?;
+/// assert_eq!(diag.dims(), &[3, 4]);
+/// assert_eq!(diag.data(), &[1, 2, 3, 4]);
+/// assert_eq!(diag.indcs(), &[0, 1, 3, 2]);
+/// assert_eq!(diag.offs(), &[0, 2, 3, 4]);
+/// # Ok(()) }
+/// ```
+#[inline]
+pub fn block_diag<'a, DATA, DS, I, IS, OS>(iter: I) -> crate::Result<CslVec<DATA, 2>>
+where
+  DATA: Clone + 'a,
+  DS: AsRef<[DATA]> + 'a,
+  I: IntoIterator<Item = &'a Csl<DS, IS, OS, 2>>,
+  IS: AsRef<[usize]> + 'a,
+  OS: AsRef<[usize]> + 'a,
+{
+  let mut rslt = CslVec::<DATA, 2> { offs: alloc::vec![0], ..Default::default() };
+  for csl in iter {
+    let [rows, cols] = csl.dims;
+    let [rslt_rows, rslt_cols] = rslt.dims;
+    let col_shift = rslt_cols;
+    rslt.dims = [
+      rslt_rows.checked_add(rows).ok_or(CslError::DimsProductOverflow)?,
+      rslt_cols.checked_add(cols).ok_or(CslError::DimsProductOverflow)?,
+    ];
+    rslt.outermost_stride = outermost_stride(&rslt.dims);
+    let data = csl.data.as_ref();
+    let indcs = csl.indcs.as_ref();
+    for range in line_ranges(csl.offs.as_ref()) {
+      rslt.data.extend(data.get(range.clone()).unwrap_or_default().iter().cloned());
+      rslt.indcs.extend(indcs.get(range).unwrap_or_default().iter().map(|idx| idx + col_shift));
+      rslt.offs.push(rslt.indcs.len());
+    }
+  }
+  Ok(rslt)
+}
+
+/// Combines `a` and `b` following the NumPy broadcasting rules, i.e., dimensions are aligned from
+/// the innermost one and a dimension of length one is expanded to match its counterpart. The
+/// structure with fewer dimensions behaves as if it had leading dimensions of length one.
+///
+/// `cb` is called for every position where at least one of the expanded operands has an element
+/// and the returned value, if any, is stored in the resulting structure of `DR` dimensions, i.e.,
+/// the greatest number of dimensions between `a` and `b`.
+///
+/// Expanding the innermost dimension of an operand fills the whole line with its single
+/// element, therefore, the result can be much denser than both operands.
+///
+/// # Arguments
+///
+/// * `a`: Left operand
+/// * `b`: Right operand
+/// * `cb`: Callback that receives the values of both positions, if any
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::csl::{ops::broadcast_apply, CslVec};
+/// // [[1, 0, 2], [0, 3, 0]]
+/// let matrix = CslVec::new([2, 3], vec![1, 2, 3], vec![0, 2, 1], vec![0, 2, 3])?;
+/// // [10, 0, 20]
+/// let row = CslVec::new([3], vec![10, 20], vec![0, 2], vec![0, 2])?;
+/// // [[5], [0]]
+/// let col = CslVec::new([2, 1], vec![5], vec![0], vec![0, 1, 1])?;
+/// let sum: CslVec<i32, 2> = broadcast_apply(&matrix, &row, |a, b| {
+///   Some(a.copied().unwrap_or_default() + b.copied().unwrap_or_default())
+/// })?;
+/// assert_eq!(sum, CslVec::new([2, 3], vec![11, 22, 10, 3, 20], vec![0, 2, 0, 1, 2], vec![0, 2, 5])?);
+/// let product: CslVec<i32, 2> = broadcast_apply(&matrix, &col, |a, b| Some(a? * b?))?;
+/// assert_eq!(product, CslVec::new([2, 3], vec![5, 10], vec![0, 2], vec![0, 2, 2])?);
+/// # Ok(()) }
+/// ```
+#[inline]
+pub fn broadcast_apply<
+  A,
+  B,
+  DSA,
+  DSB,
+  F,
+  ISA,
+  ISB,
+  OSA,
+  OSB,
+  T,
+  const DA: usize,
+  const DB: usize,
+  const DR: usize,
+>(
+  a: &Csl<DSA, ISA, OSA, DA>,
+  b: &Csl<DSB, ISB, OSB, DB>,
+  mut cb: F,
+) -> crate::Result<CslVec<T, DR>>
+where
+  DSA: AsRef<[A]>,
+  DSB: AsRef<[B]>,
+  F: FnMut(Option<&A>, Option<&B>) -> Option<T>,
+  ISA: AsRef<[usize]>,
+  ISB: AsRef<[usize]>,
+  OSA: AsRef<[usize]>,
+  OSB: AsRef<[usize]>,
+{
+  if DA.max(DB) != DR {
+    return Err(CslError::InvalidRank { expected: DA.max(DB), found: DR }.into());
+  }
+  let dims = broadcast_dims(&a.dims, &b.dims)?;
+  let offs_len = correct_offs_len(&dims)?;
+  let mut rslt = CslVec::<T, DR>::with_capacity(0, offs_len);
+  rslt.dims = dims;
+  rslt.outermost_stride = outermost_stride(&dims);
+  let innermost_len = if let Some(r) = dims.last() { *r } else { return Ok(rslt) };
+  rslt.offs.push(0);
+  for line in 0..offs_len.saturating_sub(1) {
+    let rslt_indcs = outermost_indcs(&dims, line);
+    let a_iter = broadcast_line(a, &rslt_indcs, innermost_len);
+    let b_iter = broadcast_line(b, &rslt_indcs, innermost_len);
+    merge_line(a_iter, b_iter, &mut cb, &mut rslt);
+    rslt.offs.push(rslt.indcs.len());
+  }
+  Ok(rslt)
+}
+
+/// Sparse-dense matrix product, i.e., `c = csl * b`. Each element of `c` is accumulated in
+/// [`Scalar::Acc`].
+///
+/// # Arguments
+///
+/// * `csl`: Matrix of `rows` rows and `cols` columns
+/// * `b`: Row-major dense matrix of `cols` rows and `b_cols` columns
+/// * `b_cols`: Number of columns of `b`
+/// * `c`: Row-major dense matrix of `rows` rows and `b_cols` columns whose previous values are
+///   overwritten
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::csl::{ops::spmm, CslVec};
+/// // [[1, 0, 2], [0, 3, 0]]
+/// let csl = CslVec::new([2, 3], vec![1.0, 2.0, 3.0], vec![0, 2, 1], vec![0, 2, 3])?;
+/// let mut c = [0.0; 4];
+/// spmm(&csl, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 2, &mut c)?;
+/// assert_eq!(c, [11.0, 14.0, 9.0, 12.0]);
+/// # Ok(()) }
+/// ```
+#[inline]
+pub fn spmm<DATA, DS, IS, OS>(
+  csl: &Csl<DS, IS, OS, 2>,
+  b: &[DATA],
+  b_cols: usize,
+  c: &mut [DATA],
+) -> crate::Result<()>
+where
+  DATA: Scalar,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  validate_spmm_lengths(csl.dims, b, b_cols, c)?;
+  spmm_lines(csl.data.as_ref(), csl.indcs.as_ref(), csl.offs.as_ref(), b, b_cols, c);
+  Ok(())
+}
+
+/// Same as [`spmm`] but groups the rows of `csl` into blocks of `block_rows` rows and multiplies
+/// each block whose density is at least `min_density` as a dense sub-matrix, which is faster
+/// for matrices that are only moderately sparse.
+///
+/// The density of a block is its number of stored elements divided by the area between its
+/// first and last stored columns. Dense blocks are handled by `ndarray`, i.e., by
+/// `matrixmultiply` for `f32` and `f64` or by BLAS when the `with-cblas` feature is active.
+/// Dense blocks aren't accumulated in [`Scalar::Acc`].
+///
+/// # Arguments
+///
+/// * `csl`: Matrix of `rows` rows and `cols` columns
+/// * `b`: Row-major dense matrix of `cols` rows and `b_cols` columns
+/// * `b_cols`: Number of columns of `b`
+/// * `c`: Row-major dense matrix of `rows` rows and `b_cols` columns whose previous values are
+///   overwritten
+/// * `block_rows`: Number of rows of each block
+/// * `min_density`: Blocks with a lower density use the sparse path
+///
+/// # Example
+#[cfg_attr(feature = "with-matrixmultiply", doc = "```rust")]
+#[cfg_attr(not(feature = "with-matrixmultiply"), doc = "```ignore")]
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::csl::{ops::spmm_with_dense_blocks, CslVec};
+/// // [[1, 0, 2], [0, 3, 0]]
+/// let csl = CslVec::new([2, 3], vec![1.0, 2.0, 3.0], vec![0, 2, 1], vec![0, 2, 3])?;
+/// let b = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+/// let mut c = [0.0; 4];
+/// spmm_with_dense_blocks(&csl, &b, 2, &mut c, 1, 0.5)?;
+/// assert_eq!(c, [11.0, 14.0, 9.0, 12.0]);
+/// spmm_with_dense_blocks(&csl, &b, 2, &mut c, 2, 0.0)?;
+/// assert_eq!(c, [11.0, 14.0, 9.0, 12.0]);
+/// # Ok(()) }
+/// ```
+#[cfg(feature = "with-matrixmultiply")]
+#[inline]
+pub fn spmm_with_dense_blocks<DATA, DS, IS, OS>(
+  csl: &Csl<DS, IS, OS, 2>,
+  b: &[DATA],
+  b_cols: usize,
+  c: &mut [DATA],
+  block_rows: usize,
+  min_density: f64,
+) -> crate::Result<()>
+where
+  DATA: ndarray::LinalgScalar + Scalar,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  use ndarray::{ArrayView2, ArrayViewMut2, Axis, Slice};
+
+  let [_, cols] = validate_spmm_lengths(csl.dims, b, b_cols, c)?;
+  if b_cols == 0 {
+    return Ok(());
+  }
+  let data = csl.data.as_ref();
+  let indcs = csl.indcs.as_ref();
+  let offs = csl.offs.as_ref();
+  let base = offs.first().copied().unwrap_or_default();
+  let b_matrix =
+    ArrayView2::from_shape((cols, b_cols), b).map_err(|_err| crate::Error::UnknownError)?;
+  let mut start = 0;
+  for c_block in c.chunks_mut(block_rows.max(1).saturating_mul(b_cols)) {
+    let block_len = c_block.len() / b_cols;
+    let end = start + block_len;
+    let block_offs = offs.get(start..=end).unwrap_or_default();
+    start = end;
+    let first_off = block_offs.first().copied().unwrap_or_default().saturating_sub(base);
+    let last_off = block_offs.last().copied().unwrap_or_default().saturating_sub(base);
+    let block_data = data.get(first_off..last_off).unwrap_or_default();
+    let block_indcs = indcs.get(first_off..last_off).unwrap_or_default();
+    let min_col = block_indcs.iter().copied().min().unwrap_or_default();
+    let max_col = block_indcs.iter().copied().max().unwrap_or_default();
+    let span = max_col - min_col + 1;
+    let area = block_len.saturating_mul(span);
+    let density = block_indcs.len() as f64 / area as f64;
+    if block_indcs.is_empty() || density < min_density {
+      spmm_lines(block_data, block_indcs, block_offs, b, b_cols, c_block);
+      continue;
+    }
+    let mut dense = alloc::vec![DATA::default(); area];
+    for (range, dense_row) in line_ranges(block_offs).zip(dense.chunks_exact_mut(span)) {
+      let line_data = block_data.get(range.clone()).unwrap_or_default();
+      for (value, &idx) in line_data.iter().zip(block_indcs.get(range).unwrap_or_default()) {
+        if let Some(elem) = dense_row.get_mut(idx - min_col) {
+          *elem = *value;
+        }
+      }
+    }
+    let a_block = ArrayView2::from_shape((block_len, span), &dense)
+      .map_err(|_err| crate::Error::UnknownError)?;
+    let mut c_matrix = ArrayViewMut2::from_shape((block_len, b_cols), c_block)
+      .map_err(|_err| crate::Error::UnknownError)?;
+    let b_block = b_matrix.slice_axis(Axis(0), Slice::from(min_col..=max_col));
+    c_matrix.assign(&a_block.dot(&b_block));
+  }
+  Ok(())
+}
+
+/// Sparse matrix-vector product, i.e., `y = csl * x`. Each row is accumulated in
+/// [`Scalar::Acc`], which means that half-precision matrices are multiplied in `f32`.
+///
+/// # Arguments
+///
+/// * `csl`: Matrix of `rows` rows and `cols` columns
+/// * `x`: Dense vector of `cols` elements
+/// * `y`: Dense vector of `rows` elements whose previous values are overwritten
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::csl::{ops::spmv, CslVec};
+/// // [[1, 0, 2], [0, 3, 0]]
+/// let csl = CslVec::new([2, 3], vec![1.0, 2.0, 3.0], vec![0, 2, 1], vec![0, 2, 3])?;
+/// let mut y = [0.0; 2];
+/// spmv(&csl, &[1.0, 2.0, 3.0], &mut y)?;
+/// assert_eq!(y, [7.0, 6.0]);
+/// # Ok(()) }
+/// ```
+///
+/// Complex matrices
+#[cfg_attr(feature = "with-num-complex", doc = "```rust")]
+#[cfg_attr(not(feature = "with-num-complex"), doc = "```ignore")]
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::csl::{ops::spmv, CslVec};
+/// use num_complex::Complex;
+/// // [[i, 0], [0, 2]]
+/// let data = vec![Complex::new(0.0, 1.0), Complex::new(2.0, 0.0)];
+/// let csl = CslVec::new([2, 2], data, vec![0, 1], vec![0, 1, 2])?;
+/// let mut y = [Complex::default(); 2];
+/// spmv(&csl, &[Complex::new(1.0, 1.0), Complex::new(0.0, 3.0)], &mut y)?;
+/// assert_eq!(y, [Complex::new(-1.0, 1.0), Complex::new(0.0, 6.0)]);
+/// # Ok(()) }
+/// ```
+#[inline]
+pub fn spmv<DATA, DS, IS, OS>(
+  csl: &Csl<DS, IS, OS, 2>,
+  x: &[DATA],
+  y: &mut [DATA],
+) -> crate::Result<()>
+where
+  DATA: Scalar,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  let [rows, cols] = csl.dims;
+  if x.len() != cols {
+    return Err(CslError::InvalidDenseLength { expected: cols, found: x.len() }.into());
+  }
+  if y.len() != rows {
+    return Err(CslError::InvalidDenseLength { expected: rows, found: y.len() }.into());
+  }
+  let data = csl.data.as_ref();
+  let indcs = csl.indcs.as_ref();
+  y.iter_mut().for_each(|elem| *elem = DATA::default());
+  for (range, elem) in line_ranges(csl.offs.as_ref()).zip(y.iter_mut()) {
+    let line_data = data.get(range.clone()).unwrap_or_default();
+    let line_indcs = indcs.get(range).unwrap_or_default();
+    let acc = line_data.iter().zip(line_indcs).fold(DATA::Acc::default(), |acc, (value, &idx)| {
+      acc + value.widen() * x.get(idx).copied().unwrap_or_default().widen()
+    });
+    *elem = DATA::narrow(acc);
+  }
+  Ok(())
+}
+
+fn broadcast_dims<const DA: usize, const DB: usize, const DR: usize>(
+  a: &[usize; DA],
+  b: &[usize; DB],
+) -> crate::Result<[usize; DR]> {
+  let aligned = |dims: &[usize], idx: usize| {
+    (idx + dims.len()).checked_sub(DR).and_then(|dims_idx| dims.get(dims_idx)).copied()
+  };
+  try_create_array(|axis| match (aligned(a, axis).unwrap_or(1), aligned(b, axis).unwrap_or(1)) {
+    (a_dim, b_dim) if a_dim == b_dim => Ok(a_dim),
+    (1, dim) | (dim, 1) => Ok(dim),
+    _ => Err(CslError::InvalidBroadcast { axis }.into()),
+  })
+}
+
+// Elements of the line of `csl` that corresponds to the line of the broadcast structure with the
+// outermost indices `rslt_indcs`. An innermost dimension of length one is repeated `innermost_len`
+// times.
+fn broadcast_line<'a, DATA, DS, IS, OS, const D: usize, const DR: usize>(
+  csl: &'a Csl<DS, IS, OS, D>,
+  rslt_indcs: &[usize; DR],
+  innermost_len: usize,
+) -> impl Iterator<Item = (usize, &'a DATA)>
+where
+  DATA: 'a,
+  DS: AsRef<[DATA]>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  let shift = DR.saturating_sub(D);
+  let indcs: [usize; D] = create_array(|idx| {
+    if csl.dims.get(idx) == Some(&1) {
+      0
+    } else {
+      rslt_indcs.get(idx + shift).copied().unwrap_or_default()
+    }
+  });
+  let range = line_offs(&csl.dims, csl.outermost_stride, &indcs, csl.offs.as_ref())
+    .map_or(0..0, |[_, range]| range);
+  let data = csl.data.as_ref().get(range.clone()).unwrap_or_default();
+  let line_indcs = csl.indcs.as_ref().get(range).unwrap_or_default();
+  let (repeated, data, line_indcs) = match csl.dims.last() {
+    Some(&1) if innermost_len != 1 => (data.first(), &[][..], &[][..]),
+    _ => (None, data, line_indcs),
+  };
+  let repeated_iter =
+    repeated.into_iter().flat_map(move |value| (0..innermost_len).map(move |idx| (idx, value)));
+  repeated_iter.chain(line_indcs.iter().copied().zip(data))
+}
+
+// Sparse path of the matrix products. `offs` can be any contiguous subset of the offsets.
+fn spmm_lines<DATA>(
+  data: &[DATA],
+  indcs: &[usize],
+  offs: &[usize],
+  b: &[DATA],
+  b_cols: usize,
+  c: &mut [DATA],
+) where
+  DATA: Scalar,
+{
+  if b_cols == 0 {
+    return;
+  }
+  let mut acc = alloc::vec![DATA::Acc::default(); b_cols];
+  for (range, c_row) in line_ranges(offs).zip(c.chunks_exact_mut(b_cols)) {
+    acc.iter_mut().for_each(|elem| *elem = DATA::Acc::default());
+    let line_data = data.get(range.clone()).unwrap_or_default();
+    for (value, &idx) in line_data.iter().zip(indcs.get(range).unwrap_or_default()) {
+      let b_row = b.get(idx * b_cols..(idx + 1) * b_cols).unwrap_or_default();
+      for (elem, &b_elem) in acc.iter_mut().zip(b_row) {
+        *elem = *elem + value.widen() * b_elem.widen();
+      }
+    }
+    for (c_elem, &elem) in c_row.iter_mut().zip(&acc) {
+      *c_elem = DATA::narrow(elem);
+    }
+  }
+}
+
+fn validate_spmm_lengths<DATA>(
+  dims: [usize; 2],
+  b: &[DATA],
+  b_cols: usize,
+  c: &[DATA],
+) -> crate::Result<[usize; 2]> {
+  let [rows, cols] = dims;
+  let b_len = cols.checked_mul(b_cols).ok_or(CslError::DimsProductOverflow)?;
+  if b.len() != b_len {
+    return Err(CslError::InvalidDenseLength { expected: b_len, found: b.len() }.into());
+  }
+  let c_len = rows.checked_mul(b_cols).ok_or(CslError::DimsProductOverflow)?;
+  if c.len() != c_len {
+    return Err(CslError::InvalidDenseLength { expected: c_len, found: c.len() }.into());
+  }
+  Ok(dims)
+}
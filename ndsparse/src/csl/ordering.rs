@@ -0,0 +1,90 @@
+//! Algorithms that compute reorderings of 2D CSL structures.
+//!
+//! All returned permutations can be directly applied with
+//! [`permute_outermost`](crate::csl::Csl#method.permute_outermost).
+
+use crate::csl::Csl;
+use alloc::{collections::VecDeque, vec::Vec};
+use cl_traits::Storage;
+
+/// Reverse Cuthill–McKee ordering of a 2D structure seen as an adjacency matrix.
+///
+/// Each line is a vertex and each stored innermost index is an edge. The pattern is expected to
+/// be symmetric and innermost indices greater or equal to the outermost dimension length are
+/// ignored. The returned permutation tends to reduce the bandwidth of the structure.
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::csl::{ordering::rcm, CslVec};
+/// // Path graph 0 - 2 - 3 - 1
+/// let csl = CslVec::new(
+///   [4, 4],
+///   vec![1; 10],
+///   vec![0, 2, 1, 3, 0, 2, 3, 1, 2, 3],
+///   vec![0, 2, 4, 7, 10],
+/// )?;
+/// let perm = rcm(&csl);
+/// assert_eq!(perm, [1, 3, 2, 0]);
+/// let permuted = csl.permute_outermost(&perm)?;
+/// assert_eq!(permuted.indcs(), &[1, 3, 1, 2, 3, 0, 2, 3, 0, 2]);
+/// # Ok(()) }
+/// ```
+#[inline]
+pub fn rcm<DATA, DS, IS, OS>(csl: &Csl<DS, IS, OS, 2>) -> Vec<usize>
+where
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  let len = csl.dims.first().copied().unwrap_or_default();
+  let mut adjacency: Vec<&[usize]> = Vec::with_capacity(len);
+  if let Ok(iter) = csl.outermost_line_iter() {
+    adjacency.extend(iter.map(|line| line.indcs));
+  }
+  adjacency.resize(len, &[]);
+
+  let degrees: Vec<usize> = adjacency
+    .iter()
+    .enumerate()
+    .map(|(vertex, neighbors)| neighbors.iter().filter(|&&n| n != vertex && n < len).count())
+    .collect();
+
+  let mut neighbors_buffer = Vec::new();
+  let mut queue = VecDeque::new();
+  let mut rslt = Vec::with_capacity(len);
+  let mut visited = alloc::vec![false; len];
+
+  while rslt.len() < len {
+    let start = (0..len)
+      .filter(|&vertex| !visited.get(vertex).copied().unwrap_or(true))
+      .min_by_key(|&vertex| degrees.get(vertex).copied().unwrap_or_default());
+    let start = if let Some(r) = start {
+      r
+    } else {
+      break;
+    };
+    if let Some(r) = visited.get_mut(start) {
+      *r = true;
+    }
+    queue.push_back(start);
+    while let Some(vertex) = queue.pop_front() {
+      rslt.push(vertex);
+      neighbors_buffer.clear();
+      for neighbor in adjacency.get(vertex).copied().unwrap_or_default().iter().copied() {
+        if let Some(is_visited) = visited.get_mut(neighbor) {
+          if !*is_visited {
+            *is_visited = true;
+            neighbors_buffer.push(neighbor);
+          }
+        }
+      }
+      neighbors_buffer.sort_by_key(|&neighbor| degrees.get(neighbor).copied().unwrap_or_default());
+      queue.extend(neighbors_buffer.iter().copied());
+    }
+  }
+
+  rslt.reverse();
+  rslt
+}
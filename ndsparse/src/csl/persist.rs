@@ -0,0 +1,342 @@
+//! Versioned binary persistence with integrity checks.
+//!
+//! Unlike plain `serde`, the format is self-describing and every section is protected by a
+//! CRC-32 checksum, which allows long-lived files to be validated and future versions of the
+//! format to be detected.
+//!
+//! | Field       | Type                                    |
+//! |-------------|-----------------------------------------|
+//! | Magic       | `b"NDSPARSE"`                           |
+//! | Version     | `u16`                                   |
+//! | Endianness  | `u8` (`0` for little endian)            |
+//! | Data type   | `u8` ([`PersistDtype::TAG`])            |
+//! | Rank        | `u32`                                   |
+//! | Dimensions  | `u64` for each dimension                |
+//! | Checksum    | `u32` of all preceding header bytes     |
+//! | Data        | Section of [`PersistDtype`] elements    |
+//! | Indices     | Section of `u64` elements               |
+//! | Offsets     | Section of `u64` elements               |
+//!
+//! Each section is composed by its length in bytes (`u64`), its content and the checksum (`u32`)
+//! of its content. Offsets are rebased to start at zero and all numbers are little endian.
+//!
+//! # Example
+//!
+//! ```rust
+//! # fn main() -> ndsparse::Result<()> {
+//! use ndsparse::{
+//!   csl::{persist::{load, save, PersistError}, CslVec},
+//!   fixtures::csl_vec_4,
+//! };
+//! let csl = csl_vec_4();
+//! let mut buffer = Vec::new();
+//! save(&csl, &mut buffer)?;
+//! let loaded: CslVec<i32, 4> = load(&mut &buffer[..])?;
+//! assert_eq!(loaded, csl);
+//! let last = buffer.len() - 5;
+//! buffer[last] ^= 1;
+//! let err = PersistError::ChecksumMismatch { section: "offsets" };
+//! assert_eq!(load::<i32, _, 4>(&mut &buffer[..]), Err(ndsparse::Error::Persist(err)));
+//! # Ok(()) }
+//! ```
+
+use crate::csl::{Csl, CslError, CslVec};
+use alloc::vec::Vec;
+use cl_traits::Storage;
+use core::{
+  convert::{TryFrom, TryInto},
+  fmt, mem,
+};
+use std::io::{Read, Write};
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+const LITTLE_ENDIAN: u8 = 0;
+const MAGIC: [u8; 8] = *b"NDSPARSE";
+const U64_SIZE: usize = mem::size_of::<u64>();
+
+/// Current version of the format.
+pub const VERSION: u16 = 1;
+
+/// Element type that can be persisted.
+pub trait PersistDtype: Sized {
+  /// Unique identifier of the type stored in the header.
+  const TAG: u8;
+
+  /// Decodes a little endian element, returning `None` if `bytes` doesn't have the size of the
+  /// type.
+  fn from_le_slice(bytes: &[u8]) -> Option<Self>;
+
+  /// Appends the little endian representation of the element.
+  fn extend_le(&self, buffer: &mut Vec<u8>);
+}
+
+macro_rules! impl_persist_dtype {
+  ($($ty:ty => $tag:literal),*) => {
+    $(
+      impl PersistDtype for $ty {
+        const TAG: u8 = $tag;
+
+        #[inline]
+        fn from_le_slice(bytes: &[u8]) -> Option<Self> {
+          Some(<$ty>::from_le_bytes(bytes.try_into().ok()?))
+        }
+
+        #[inline]
+        fn extend_le(&self, buffer: &mut Vec<u8>) {
+          buffer.extend_from_slice(&self.to_le_bytes());
+        }
+      }
+    )*
+  };
+}
+
+impl_persist_dtype!(
+  i8 => 0, i16 => 1, i32 => 2, i64 => 3, u8 => 4, u16 => 5, u32 => 6, u64 => 7, f32 => 8,
+  f64 => 9
+);
+
+/// Any error related to the persistence format
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum PersistError {
+  /// The stored checksum of a section doesn't match its content
+  ChecksumMismatch {
+    /// Name of the section
+    section: &'static str,
+  },
+  /// The stored data type is different than the requested data type
+  DtypeMismatch {
+    /// Requested tag
+    expected: u8,
+    /// Stored tag
+    found: u8,
+  },
+  /// The input doesn't start with the magic bytes of the format
+  InvalidMagic,
+  /// The length of a section isn't a multiple of the size of its elements
+  InvalidSectionLength {
+    /// Name of the section
+    section: &'static str,
+  },
+  /// Reading or writing failed
+  Io(std::io::ErrorKind),
+  /// The stored number of dimensions is different than the requested number of dimensions
+  RankMismatch {
+    /// Requested number of dimensions
+    expected: usize,
+    /// Stored number of dimensions
+    found: usize,
+  },
+  /// The stored endianness isn't supported by this version
+  UnsupportedEndianness(u8),
+  /// The stored format version isn't supported by this version
+  UnsupportedVersion(u16),
+}
+
+impl fmt::Display for PersistError {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match *self {
+      Self::ChecksumMismatch { section } => write!(f, "ChecksumMismatch ({})", section),
+      Self::DtypeMismatch { expected, found } => {
+        write!(f, "DtypeMismatch (expected: {}, found: {})", expected, found)
+      }
+      Self::InvalidMagic => write!(f, "InvalidMagic"),
+      Self::InvalidSectionLength { section } => write!(f, "InvalidSectionLength ({})", section),
+      Self::Io(kind) => write!(f, "Io ({:?})", kind),
+      Self::RankMismatch { expected, found } => {
+        write!(f, "RankMismatch (expected: {}, found: {})", expected, found)
+      }
+      Self::UnsupportedEndianness(x) => write!(f, "UnsupportedEndianness ({})", x),
+      Self::UnsupportedVersion(x) => write!(f, "UnsupportedVersion ({})", x),
+    }
+  }
+}
+
+impl std::error::Error for PersistError {}
+
+#[cfg(feature = "with-defmt")]
+impl defmt::Format for PersistError {
+  #[inline]
+  fn format(&self, fmt: defmt::Formatter<'_>) {
+    defmt::write!(fmt, "{}", defmt::Display2Format(self))
+  }
+}
+
+impl From<std::io::Error> for crate::Error {
+  #[inline]
+  fn from(from: std::io::Error) -> Self {
+    Self::Persist(PersistError::Io(from.kind()))
+  }
+}
+
+/// Reads a structure previously written by [`save`], verifying the header, all checksums and
+/// all invariants of [`Csl::new`].
+#[inline]
+pub fn load<DATA, R, const D: usize>(reader: &mut R) -> crate::Result<CslVec<DATA, D>>
+where
+  DATA: PersistDtype,
+  R: Read,
+{
+  let mut header = [0; 16];
+  reader.read_exact(&mut header)?;
+  let (magic, rest) = header.split_at(MAGIC.len());
+  if magic != MAGIC {
+    return Err(PersistError::InvalidMagic.into());
+  }
+  let rest: [u8; 8] = rest.try_into().map_err(|_err| crate::Error::UnknownError)?;
+  let [v0, v1, endianness, dtype, r0, r1, r2, r3] = rest;
+  let version = u16::from_le_bytes([v0, v1]);
+  if version != VERSION {
+    return Err(PersistError::UnsupportedVersion(version).into());
+  }
+  if endianness != LITTLE_ENDIAN {
+    return Err(PersistError::UnsupportedEndianness(endianness).into());
+  }
+  if dtype != DATA::TAG {
+    return Err(PersistError::DtypeMismatch { expected: DATA::TAG, found: dtype }.into());
+  }
+  let rank = usize::try_from(u32::from_le_bytes([r0, r1, r2, r3])).unwrap_or(usize::MAX);
+  if rank != D {
+    return Err(PersistError::RankMismatch { expected: D, found: rank }.into());
+  }
+  let mut dims_bytes = [[0; 8]; D];
+  let mut crc = crc32_update(!0, &header);
+  for dim_bytes in dims_bytes.iter_mut() {
+    reader.read_exact(dim_bytes)?;
+    crc = crc32_update(crc, dim_bytes);
+  }
+  if !crc != read_u32(reader)? {
+    return Err(PersistError::ChecksumMismatch { section: "header" }.into());
+  }
+  let mut dims = [0; D];
+  for (dim, dim_bytes) in dims.iter_mut().zip(dims_bytes.iter()) {
+    *dim = usize_from_le_slice(dim_bytes)?;
+  }
+  let data = read_section(reader, "data", mem::size_of::<DATA>(), |bytes| {
+    DATA::from_le_slice(bytes).ok_or(crate::Error::UnknownError)
+  })?;
+  let indcs = read_section(reader, "indices", U64_SIZE, usize_from_le_slice)?;
+  let offs = read_section(reader, "offsets", U64_SIZE, usize_from_le_slice)?;
+  CslVec::new(dims, data, indcs, offs)
+}
+
+/// Writes `csl` using the format described in the [module](self) documentation.
+#[inline]
+pub fn save<DATA, DS, IS, OS, W, const D: usize>(
+  csl: &Csl<DS, IS, OS, D>,
+  writer: &mut W,
+) -> crate::Result<()>
+where
+  DATA: PersistDtype,
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+  W: Write,
+{
+  let rank = u32::try_from(D).map_err(|_err| CslError::IndexOverflow)?;
+  let mut header = Vec::with_capacity(MAGIC.len() + 8 + D * U64_SIZE);
+  header.extend_from_slice(&MAGIC);
+  header.extend_from_slice(&VERSION.to_le_bytes());
+  header.extend_from_slice(&[LITTLE_ENDIAN, DATA::TAG]);
+  header.extend_from_slice(&rank.to_le_bytes());
+  for &dim in csl.dims.iter() {
+    header.extend_from_slice(&(dim as u64).to_le_bytes());
+  }
+  writer.write_all(&header)?;
+  writer.write_all(&crc32(&header).to_le_bytes())?;
+  let first_off = csl.offs.as_ref().first().copied().unwrap_or_default();
+  write_section(writer, csl.data.as_ref().iter(), |elem, buffer| elem.extend_le(buffer))?;
+  write_section(writer, csl.indcs.as_ref().iter(), |&elem, buffer| {
+    buffer.extend_from_slice(&(elem as u64).to_le_bytes())
+  })?;
+  write_section(writer, csl.offs.as_ref().iter(), |&elem, buffer| {
+    buffer.extend_from_slice(&(elem.saturating_sub(first_off) as u64).to_le_bytes())
+  })?;
+  Ok(())
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+  !crc32_update(!0, bytes)
+}
+
+const fn crc32_table() -> [u32; 256] {
+  let mut table = [0; 256];
+  let mut idx = 0;
+  while idx < 256 {
+    let mut crc = idx as u32;
+    let mut bit = 0;
+    while bit < 8 {
+      crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+      bit += 1;
+    }
+    table[idx] = crc;
+    idx += 1;
+  }
+  table
+}
+
+fn crc32_update(crc: u32, bytes: &[u8]) -> u32 {
+  bytes.iter().fold(crc, |acc, &byte| {
+    let idx = usize::from((acc as u8) ^ byte);
+    CRC32_TABLE.get(idx).copied().unwrap_or_default() ^ (acc >> 8)
+  })
+}
+
+fn read_section<F, R, T>(
+  reader: &mut R,
+  section: &'static str,
+  size: usize,
+  cb: F,
+) -> crate::Result<Vec<T>>
+where
+  F: FnMut(&[u8]) -> crate::Result<T>,
+  R: Read,
+{
+  let mut len_bytes = [0; 8];
+  reader.read_exact(&mut len_bytes)?;
+  let len = u64::from_le_bytes(len_bytes);
+  let mut buffer = Vec::new();
+  let _ = reader.take(len).read_to_end(&mut buffer)?;
+  if buffer.len() as u64 != len {
+    return Err(crate::Error::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof)));
+  }
+  if crc32(&buffer) != read_u32(reader)? {
+    return Err(PersistError::ChecksumMismatch { section }.into());
+  }
+  if size == 0 || buffer.len() % size != 0 {
+    return Err(PersistError::InvalidSectionLength { section }.into());
+  }
+  buffer.chunks_exact(size).map(cb).collect()
+}
+
+fn read_u32<R>(reader: &mut R) -> crate::Result<u32>
+where
+  R: Read,
+{
+  let mut bytes = [0; 4];
+  reader.read_exact(&mut bytes)?;
+  Ok(u32::from_le_bytes(bytes))
+}
+
+fn usize_from_le_slice(bytes: &[u8]) -> crate::Result<usize> {
+  let value = u64::from_le_slice(bytes).ok_or(crate::Error::UnknownError)?;
+  usize::try_from(value).map_err(|_err| CslError::IndexOverflow.into())
+}
+
+fn write_section<'a, F, I, T, W>(writer: &mut W, iter: I, mut cb: F) -> crate::Result<()>
+where
+  F: FnMut(&'a T, &mut Vec<u8>),
+  I: ExactSizeIterator<Item = &'a T>,
+  T: 'a,
+  W: Write,
+{
+  let mut buffer = Vec::with_capacity(iter.len().saturating_mul(U64_SIZE));
+  for elem in iter {
+    cb(elem, &mut buffer);
+  }
+  writer.write_all(&(buffer.len() as u64).to_le_bytes())?;
+  writer.write_all(&buffer)?;
+  writer.write_all(&crc32(&buffer).to_le_bytes())?;
+  Ok(())
+}
@@ -0,0 +1,75 @@
+//! Offsets arithmetic over raw slices.
+//!
+//! The same computations used internally by [`Csl`](crate::csl::Csl), exposed for custom
+//! kernels that operate directly on the dimensions, indices and offsets of a structure. None of
+//! the functions validate their inputs, which should follow the invariants verified by
+//! [`Csl::new`](crate::csl::Csl#method.new).
+//!
+//! Returned data ranges are relative to the first offset, i.e., they can be used to index the
+//! data and indices slices of a sub-structure whose offsets don't start at zero.
+
+use core::ops::Range;
+
+/// Offsets and data ranges of the line that contains `indcs`. The innermost index is ignored.
+///
+/// The first range covers the two offsets that delimit the line and the second range covers the
+/// line elements. Returns `None` if `indcs` is out of bounds or if `offs` is too short.
+///
+/// # Example
+///
+/// ```rust
+/// use ndsparse::{csl::raw::line_offs, fixtures::csl_array_4};
+/// let csl = csl_array_4();
+/// let [offs, values] = line_offs(csl.dims(), &[0, 0, 3, 0], csl.offs()).unwrap();
+/// assert_eq!((offs, values.clone()), (3..5, 3..5));
+/// assert_eq!(&csl.data()[values], &[4, 5]);
+/// assert_eq!(line_offs(csl.dims(), &[0, 7, 0, 0], csl.offs()), None);
+/// ```
+#[inline]
+pub fn line_offs<const D: usize>(
+  dims: &[usize; D],
+  indcs: &[usize; D],
+  offs: &[usize],
+) -> Option<[Range<usize>; 2]> {
+  crate::csl::line_offs(dims, crate::csl::outermost_stride(dims), indcs, offs)
+}
+
+/// Maximum number of elements that a structure with dimensions `dims` can store. Saturates at
+/// `usize::MAX`.
+///
+/// # Example
+///
+/// ```rust
+/// use ndsparse::csl::raw::max_nnz;
+/// assert_eq!(max_nnz(&[2, 3, 4, 5]), 120);
+/// assert_eq!(max_nnz(&[0, 0]), 0);
+/// ```
+#[inline]
+pub fn max_nnz<const D: usize>(dims: &[usize; D]) -> usize {
+  crate::utils::max_nnz(dims)
+}
+
+/// Offsets and data ranges of all lines of the outermost indices of `range`.
+///
+/// The first range covers the offsets that delimit the lines, which includes the offset of the
+/// end of the last line, and the second range covers the elements of all lines. Returns `None`
+/// if `range` isn't contained by `offs` or if the offsets computation overflows.
+///
+/// # Example
+///
+/// ```rust
+/// use ndsparse::{csl::raw::outermost_offs, fixtures::csl_array_4};
+/// let csl = csl_array_4();
+/// let [offs, values] = outermost_offs(csl.dims(), csl.offs(), 1..2).unwrap();
+/// assert_eq!((offs, values.clone()), (12..25, 8..9));
+/// assert_eq!(&csl.data()[values], &[9]);
+/// assert_eq!(outermost_offs(csl.dims(), csl.offs(), 1..3), None);
+/// ```
+#[inline]
+pub fn outermost_offs<const D: usize>(
+  dims: &[usize; D],
+  offs: &[usize],
+  range: Range<usize>,
+) -> Option<[Range<usize>; 2]> {
+  crate::csl::outermost_offs(crate::csl::outermost_stride(dims), offs, range)
+}
@@ -0,0 +1,327 @@
+//! Experimental decompositions of sparse tensors.
+//!
+//! [`Tensor::mttkrp`] computes the Matricized Tensor Times Khatri-Rao Product, the kernel that
+//! dominates most CP algorithms, and [`cp_als`] is a basic CANDECOMP/PARAFAC driver based on
+//! Alternating Least Squares.
+//!
+//! Factor matrices are dense, row-major and have one row for each index of their dimension and
+//! one column for each component, i.e., the factor of axis `n` has `dims[n] * rank` elements.
+//!
+//! ```rust
+//! # fn main() -> ndsparse::Result<()> {
+//! use ndsparse::{coo::CooVec, decomp::cp_als};
+//! // Outer product of [1, 2] and [3, 0, 1]
+//! let coo = CooVec::new([2, 3], vec![([0, 0], 3.0), ([0, 2], 1.0), ([1, 0], 6.0), ([1, 2], 2.0)])?;
+//! let kruskal = cp_als(&coo, 1, 10)?;
+//! assert!((kruskal.value([1, 0]) - 6.0).abs() < 1e-9);
+//! assert!(kruskal.value([1, 1]).abs() < 1e-9);
+//! # Ok(()) }
+//! ```
+
+mod decomp_error;
+
+use crate::{coo::Coo, csl::Csl};
+use alloc::vec::Vec;
+use cl_traits::Storage;
+pub use decomp_error::*;
+
+/// Sparse tensors that can be decomposed.
+pub trait Tensor<const D: usize> {
+  /// Length of each dimension.
+  fn tensor_dims(&self) -> [usize; D];
+
+  /// Calls `cb` with the indices and the value of every stored element.
+  fn for_each_entry<F>(&self, cb: F)
+  where
+    F: FnMut([usize; D], f64);
+
+  /// Matricized tensor times the Khatri-Rao product of all factors except the one of `axis`.
+  ///
+  /// The returned matrix is dense, row-major and has `dims[axis]` rows and `rank` columns. The
+  /// factor of `axis` only participates in the inference of the rank.
+  ///
+  /// # Arguments
+  ///
+  /// * `factors`: Factor matrix of each dimension
+  /// * `axis`: Dimension that isn't multiplied
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{csl::CslVec, decomp::Tensor};
+  /// // [[1, 0, 2], [0, 3, 0]]
+  /// let csl = CslVec::new([2, 3], vec![1, 2, 3], vec![0, 2, 1], vec![0, 2, 3])?;
+  /// let rslt = csl.mttkrp(&[&[0.0; 4], &[1.0, 1.0, 0.0, 1.0, 1.0, 0.0]], 0)?;
+  /// assert_eq!(rslt, [3.0, 1.0, 0.0, 3.0]);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  fn mttkrp(&self, factors: &[&[f64]; D], axis: usize) -> crate::Result<Vec<f64>> {
+    let dims = self.tensor_dims();
+    let rows = *dims.get(axis).ok_or(DecompError::InvalidAxis { axis })?;
+    let rank = factors_rank(&dims, factors)?;
+    let mut rslt = alloc::vec![0.0; rows * rank];
+    let mut product = alloc::vec![0.0; rank];
+    self.for_each_entry(|indcs, value| {
+      product.iter_mut().for_each(|elem| *elem = value);
+      for (dim, (factor, &idx)) in factors.iter().zip(indcs.iter()).enumerate() {
+        if dim == axis {
+          continue;
+        }
+        let row = factor.get(idx * rank..(idx + 1) * rank).unwrap_or_default();
+        product.iter_mut().zip(row).for_each(|(a, b)| *a *= b);
+      }
+      if let Some(idx) = indcs.get(axis) {
+        let row = rslt.get_mut(idx * rank..(idx + 1) * rank).unwrap_or_default();
+        row.iter_mut().zip(&product).for_each(|(a, b)| *a += b);
+      }
+    });
+    Ok(rslt)
+  }
+}
+
+impl<DATA, DS, const D: usize> Tensor<D> for Coo<DS, D>
+where
+  DATA: Copy + Into<f64>,
+  DS: AsRef<[<DS as Storage>::Item]> + Storage<Item = ([usize; D], DATA)>,
+{
+  #[inline]
+  fn tensor_dims(&self) -> [usize; D] {
+    self.dims
+  }
+
+  #[inline]
+  fn for_each_entry<F>(&self, mut cb: F)
+  where
+    F: FnMut([usize; D], f64),
+  {
+    for &(indcs, value) in self.data.as_ref() {
+      cb(indcs, value.into());
+    }
+  }
+}
+
+impl<DATA, DS, IS, OS, const D: usize> Tensor<D> for Csl<DS, IS, OS, D>
+where
+  DATA: Copy + Into<f64>,
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  #[inline]
+  fn tensor_dims(&self) -> [usize; D] {
+    self.dims
+  }
+
+  #[inline]
+  fn for_each_entry<F>(&self, mut cb: F)
+  where
+    F: FnMut([usize; D], f64),
+  {
+    let data = self.data.as_ref();
+    let indcs = self.indcs.as_ref();
+    for (line, range) in crate::csl::line_ranges(self.offs.as_ref()).enumerate() {
+      let mut elem_indcs = crate::csl::outermost_indcs(&self.dims, line);
+      let line_data = data.get(range.clone()).unwrap_or_default();
+      let line_indcs = indcs.get(range).unwrap_or_default();
+      for (&value, &innermost_idx) in line_data.iter().zip(line_indcs) {
+        if let Some(r) = elem_indcs.last_mut() {
+          *r = innermost_idx;
+        }
+        cb(elem_indcs, value.into());
+      }
+    }
+  }
+}
+
+/// Sum of `rank` rank-one tensors, each one the outer product of a column of every factor scaled
+/// by its weight.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Kruskal<const D: usize> {
+  factors: [Vec<f64>; D],
+  rank: usize,
+  weights: Vec<f64>,
+}
+
+impl<const D: usize> Kruskal<D> {
+  /// Factor matrices with normalized columns.
+  #[inline]
+  pub fn factors(&self) -> &[Vec<f64>; D] {
+    &self.factors
+  }
+
+  /// Number of components.
+  #[inline]
+  pub fn rank(&self) -> usize {
+    self.rank
+  }
+
+  /// Reconstructed value of the element determined by `indcs`.
+  #[inline]
+  pub fn value(&self, indcs: [usize; D]) -> f64 {
+    let mut rslt = 0.0;
+    for (component, weight) in self.weights.iter().enumerate() {
+      let mut product = *weight;
+      for (factor, idx) in self.factors.iter().zip(indcs.iter()) {
+        product *= factor.get(idx * self.rank + component).copied().unwrap_or_default();
+      }
+      rslt += product;
+    }
+    rslt
+  }
+
+  /// Scale of each component.
+  #[inline]
+  pub fn weights(&self) -> &[f64] {
+    &self.weights
+  }
+}
+
+/// CANDECOMP/PARAFAC decomposition through Alternating Least Squares.
+///
+/// Factors are deterministically initialized and every iteration updates all of them once.
+///
+/// # Arguments
+///
+/// * `tensor`: Sparse tensor
+/// * `rank`: Number of components
+/// * `iterations`: Number of ALS sweeps
+#[inline]
+pub fn cp_als<T, const D: usize>(
+  tensor: &T,
+  rank: usize,
+  iterations: usize,
+) -> crate::Result<Kruskal<D>>
+where
+  T: Tensor<D>,
+{
+  let dims = tensor.tensor_dims();
+  if rank == 0 {
+    let factors = cl_traits::create_array(|_| Vec::new());
+    return Ok(Kruskal { factors, rank, weights: Vec::new() });
+  }
+  let mut factors: [Vec<f64>; D] = cl_traits::create_array(|axis| {
+    let len = dims.get(axis).copied().unwrap_or_default() * rank;
+    (0..len).map(|idx| 1.0 + ((idx + axis + 1) as f64 * 0.618_033_988_749_895).fract()).collect()
+  });
+  let mut weights = alloc::vec![1.0; rank];
+  for _ in 0..iterations {
+    for axis in 0..D {
+      let mut gram = alloc::vec![1.0; rank * rank];
+      for (dim, factor) in factors.iter().enumerate() {
+        if dim != axis {
+          gram.iter_mut().zip(factor_gram(factor, rank)).for_each(|(a, b)| *a *= b);
+        }
+      }
+      let factors_ref: [&[f64]; D] = cl_traits::create_array(|idx| {
+        factors.get(idx).map(|elem| elem.as_slice()).unwrap_or_default()
+      });
+      let mut new_factor = tensor.mttkrp(&factors_ref, axis)?;
+      for row in new_factor.chunks_exact_mut(rank) {
+        solve_symmetric(&gram, rank, row)?;
+      }
+      weights = normalize_columns(&mut new_factor, rank);
+      if let Some(r) = factors.get_mut(axis) {
+        *r = new_factor;
+      }
+    }
+  }
+  Ok(Kruskal { factors, rank, weights })
+}
+
+// Rank shared by all factors.
+#[inline]
+fn factors_rank<const D: usize>(dims: &[usize; D], factors: &[&[f64]; D]) -> crate::Result<usize> {
+  let rank = dims
+    .iter()
+    .zip(factors.iter())
+    .find(|(&dim, _)| dim != 0)
+    .map(|(dim, factor)| factor.len() / dim)
+    .unwrap_or_default();
+  for (axis, (&dim, factor)) in dims.iter().zip(factors.iter()).enumerate() {
+    let expected = dim * rank;
+    if factor.len() != expected {
+      return Err(DecompError::DiffFactorLength { axis, expected, found: factor.len() }.into());
+    }
+  }
+  Ok(rank)
+}
+
+// `Aᵀ * A` of a row-major matrix with `rank` columns.
+#[inline]
+fn factor_gram(factor: &[f64], rank: usize) -> Vec<f64> {
+  let mut rslt = alloc::vec![0.0; rank * rank];
+  for row in factor.chunks_exact(rank) {
+    for (gram_row, a) in rslt.chunks_exact_mut(rank).zip(row) {
+      gram_row.iter_mut().zip(row).for_each(|(elem, b)| *elem += a * b);
+    }
+  }
+  rslt
+}
+
+// Divides each column by its euclidean norm and returns the norms. Zeroed columns are untouched.
+#[inline]
+fn normalize_columns(matrix: &mut [f64], rank: usize) -> Vec<f64> {
+  let mut norms = alloc::vec![0.0; rank];
+  for row in matrix.chunks_exact(rank) {
+    norms.iter_mut().zip(row).for_each(|(norm, elem)| *norm += elem * elem);
+  }
+  norms.iter_mut().for_each(|norm| *norm = norm.sqrt());
+  for row in matrix.chunks_exact_mut(rank) {
+    for (elem, &norm) in row.iter_mut().zip(&norms) {
+      if norm != 0.0 {
+        *elem /= norm;
+      }
+    }
+  }
+  norms
+}
+
+// Overwrites `rhs` with the solution of `matrix * x = rhs` through Gaussian elimination with
+// partial pivoting.
+#[inline]
+fn solve_symmetric(matrix: &[f64], rank: usize, rhs: &mut [f64]) -> crate::Result<()> {
+  let mut lhs = matrix.to_vec();
+  for col in 0..rank {
+    let pivot = (col..rank)
+      .max_by(|&a, &b| {
+        let [a_elem, b_elem] = [lhs.get(a * rank + col), lhs.get(b * rank + col)];
+        let [a_abs, b_abs] = [a_elem.map(|e| e.abs()), b_elem.map(|e| e.abs())];
+        a_abs.partial_cmp(&b_abs).unwrap_or(core::cmp::Ordering::Equal)
+      })
+      .unwrap_or(col);
+    let pivot_elem = lhs.get(pivot * rank + col).copied().unwrap_or_default();
+    if pivot_elem.abs() <= f64::EPSILON {
+      return Err(DecompError::SingularMatrix.into());
+    }
+    for idx in 0..rank {
+      lhs.swap(col * rank + idx, pivot * rank + idx);
+    }
+    rhs.swap(col, pivot);
+    for row in col + 1..rank {
+      let factor = lhs.get(row * rank + col).copied().unwrap_or_default() / pivot_elem;
+      for idx in col..rank {
+        let elem = lhs.get(col * rank + idx).copied().unwrap_or_default();
+        if let Some(r) = lhs.get_mut(row * rank + idx) {
+          *r -= factor * elem;
+        }
+      }
+      let elem = rhs.get(col).copied().unwrap_or_default();
+      if let Some(r) = rhs.get_mut(row) {
+        *r -= factor * elem;
+      }
+    }
+  }
+  for col in (0..rank).rev() {
+    let mut sum = rhs.get(col).copied().unwrap_or_default();
+    for idx in col + 1..rank {
+      let coefficient = lhs.get(col * rank + idx).copied().unwrap_or_default();
+      sum -= coefficient * rhs.get(idx).copied().unwrap_or_default();
+    }
+    if let Some(r) = rhs.get_mut(col) {
+      *r = sum / lhs.get(col * rank + col).copied().unwrap_or(1.0);
+    }
+  }
+  Ok(())
+}
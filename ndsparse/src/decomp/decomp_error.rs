@@ -0,0 +1,58 @@
+use core::fmt;
+
+/// Any error related to tensor decompositions
+#[cfg_attr(feature = "with-defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum DecompError {
+  /// A factor matrix doesn't have `dims[axis] * rank` elements
+  ///
+  /// ```rust
+  /// use ndsparse::{decomp::{DecompError, Tensor}, fixtures::csl_array_4};
+  /// let csl = csl_array_4();
+  /// let rslt = csl.mttkrp(&[&[1.0; 2], &[1.0; 3], &[1.0; 4], &[1.0; 4]], 0);
+  /// let err = DecompError::DiffFactorLength { axis: 3, expected: 5, found: 4 };
+  /// assert_eq!(rslt, Err(ndsparse::Error::Decomp(err)));
+  /// ```
+  DiffFactorLength {
+    /// Dimension of the factor matrix
+    axis: usize,
+    /// Number of elements required by the dimension length and the rank
+    expected: usize,
+    /// Number of provided elements
+    found: usize,
+  },
+
+  /// Axis isn't one of the dimensions of the tensor
+  ///
+  /// ```rust
+  /// use ndsparse::{decomp::{DecompError, Tensor}, fixtures::coo_array_5};
+  /// let coo = coo_array_5();
+  /// let rslt = coo.mttkrp(&[&[1.0; 2], &[1.0; 3], &[1.0; 4], &[1.0; 3], &[1.0; 3]], 5);
+  /// assert_eq!(rslt, Err(ndsparse::Error::Decomp(DecompError::InvalidAxis { axis: 5 })));
+  /// ```
+  InvalidAxis {
+    /// The offending axis
+    axis: usize,
+  },
+
+  /// The Gram matrix of the factors can't be inverted, e.g., the requested rank is greater than
+  /// what the tensor can support
+  SingularMatrix,
+}
+
+impl fmt::Display for DecompError {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match *self {
+      Self::DiffFactorLength { axis, expected, found } => {
+        write!(f, "DiffFactorLength (axis {}, expected: {}, found: {})", axis, expected, found)
+      }
+      Self::InvalidAxis { axis } => write!(f, "InvalidAxis ({})", axis),
+      Self::SingularMatrix => write!(f, "SingularMatrix"),
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecompError {}
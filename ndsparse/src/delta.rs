@@ -0,0 +1,209 @@
+//! Delta-compressed offsets for CSL structures with a large number of lines.
+//!
+//! [`DeltaCsl`] replaces the absolute `usize` offsets of a [`Csl`] by the number of elements of
+//! each line stored in a narrow integer type like `u16` or `u32`. Absolute offsets are restored
+//! with prefix sums that start from a checkpoint saved every [`CHECKPOINT_INTERVAL`] lines, which
+//! bounds the cost of every lookup while cutting the memory of the offsets by up to 8 times.
+
+use crate::{
+  csl::{line_idx, Csl, CslError, CslVec},
+  utils::windows2,
+};
+use alloc::vec::Vec;
+use cl_traits::Storage;
+use core::{convert::TryFrom, ops::Range};
+
+/// Number of lines between two saved absolute offsets.
+pub const CHECKPOINT_INTERVAL: usize = 64;
+
+/// Unsigned integer that stores the number of elements of a line.
+pub trait LineCount: Copy {
+  /// Converts the number of elements of a line, returning `None` if it doesn't fit.
+  fn from_usize(value: usize) -> Option<Self>;
+
+  /// Converts back to `usize`.
+  fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_line_count {
+  ($($ty:ty),*) => {
+    $(
+      impl LineCount for $ty {
+        #[inline]
+        fn from_usize(value: usize) -> Option<Self> {
+          <$ty>::try_from(value).ok()
+        }
+
+        #[inline]
+        fn to_usize(self) -> usize {
+          self as usize
+        }
+      }
+    )*
+  };
+}
+
+impl_line_count!(u8, u16, u32);
+
+/// CSL with delta-compressed offsets. See the [module](self) documentation for more information.
+///
+/// # Types
+///
+/// * `C`: Type of the number of elements of each line
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeltaCsl<DATA, C, const D: usize> {
+  checkpoints: Vec<usize>,
+  counts: Vec<C>,
+  data: Vec<DATA>,
+  dims: [usize; D],
+  indcs: Vec<usize>,
+}
+
+impl<DATA, C, const D: usize> DeltaCsl<DATA, C, D>
+where
+  C: LineCount,
+{
+  /// Compresses the offsets of `csl`, which are rebased to start at zero.
+  ///
+  /// Returns [`CslError::IndexOverflow`] if the number of elements of any line doesn't fit in
+  /// `C`.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{csl::CslError, delta::DeltaCsl, fixtures::csl_vec_4};
+  /// let csl = csl_vec_4();
+  /// let delta = DeltaCsl::<_, u8, 4>::pack(&csl)?;
+  /// assert_eq!(delta.counts(), &[2, 1, 0, 2, 1, 0, 0, 0, 1, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+  /// assert_eq!(delta.unpack()?, csl);
+  /// let dense = ndsparse::fixtures::fixture_csl([1, 300], ndsparse::fixtures::Pattern::Dense)?;
+  /// assert_eq!(DeltaCsl::<_, u8, 2>::pack(&dense), Err(ndsparse::Error::Csl(CslError::IndexOverflow)));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn pack<DS, IS, OS>(csl: &Csl<DS, IS, OS, D>) -> crate::Result<Self>
+  where
+    DATA: Clone,
+    DS: AsRef<[DATA]> + Storage<Item = DATA>,
+    IS: AsRef<[usize]>,
+    OS: AsRef<[usize]>,
+  {
+    let offs = csl.offs();
+    let first = offs.first().copied().unwrap_or_default();
+    let checkpoints =
+      offs.iter().step_by(CHECKPOINT_INTERVAL).map(|off| off.saturating_sub(first)).collect();
+    let mut counts = Vec::with_capacity(offs.len().saturating_sub(1));
+    for [start, end] in windows2(offs) {
+      counts.push(C::from_usize(end - start).ok_or(CslError::IndexOverflow)?);
+    }
+    Ok(Self {
+      checkpoints,
+      counts,
+      data: csl.data().to_vec(),
+      dims: *csl.dims(),
+      indcs: csl.indcs().to_vec(),
+    })
+  }
+
+  /// Number of elements of each line.
+  #[inline]
+  pub fn counts(&self) -> &[C] {
+    &self.counts
+  }
+
+  /// The data that is being stored.
+  #[inline]
+  pub fn data(&self) -> &[DATA] {
+    &self.data
+  }
+
+  /// The definitions of all dimensions.
+  #[inline]
+  pub fn dims(&self) -> &[usize; D] {
+    &self.dims
+  }
+
+  /// Innermost indices of all stored elements.
+  #[inline]
+  pub fn indcs(&self) -> &[usize] {
+    &self.indcs
+  }
+
+  /// Indices and data of the line that contains `indcs`. The innermost index is ignored.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{delta::DeltaCsl, fixtures::csl_array_4};
+  /// let delta = DeltaCsl::<_, u16, 4>::pack(&csl_array_4())?;
+  /// assert_eq!(delta.line([0, 0, 3, 0]), Some((&[3, 4][..], &[4, 5][..])));
+  /// assert_eq!(delta.line([0, 7, 0, 0]), None);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn line(&self, indcs: [usize; D]) -> Option<(&[usize], &[DATA])> {
+    let range = self.line_range(line_idx(&self.dims, &indcs)?)?;
+    Some((self.indcs.get(range.clone())?, self.data.get(range)?))
+  }
+
+  /// Range of the elements of the line `line` in [`data`](#method.data) and
+  /// [`indcs`](#method.indcs). At most [`CHECKPOINT_INTERVAL`] counts are summed.
+  #[inline]
+  pub fn line_range(&self, line: usize) -> Option<Range<usize>> {
+    let count = self.counts.get(line)?.to_usize();
+    let checkpoint_idx = line / CHECKPOINT_INTERVAL;
+    let checkpoint = *self.checkpoints.get(checkpoint_idx)?;
+    let counts = self.counts.get(checkpoint_idx * CHECKPOINT_INTERVAL..line)?;
+    let start = counts.iter().fold(checkpoint, |acc, elem| acc + elem.to_usize());
+    Some(start..start + count)
+  }
+
+  /// Number of Non-Zero elements.
+  #[inline]
+  pub fn nnz(&self) -> usize {
+    self.data.len()
+  }
+
+  /// Absolute offsets restored through a running prefix sum.
+  #[inline]
+  pub fn offs_iter(&self) -> impl Iterator<Item = usize> + '_ {
+    let first = self.checkpoints.first().copied();
+    let sums = self.counts.iter().scan(0, |acc, elem| {
+      *acc += elem.to_usize();
+      Some(*acc)
+    });
+    first.into_iter().chain(sums)
+  }
+
+  /// Restores the absolute offsets and converts the buffers back into a validated structure.
+  #[inline]
+  pub fn unpack(&self) -> crate::Result<CslVec<DATA, D>>
+  where
+    DATA: Clone,
+  {
+    let offs = self.offs_iter().collect();
+    CslVec::new(self.dims, self.data.clone(), self.indcs.clone(), offs)
+  }
+
+  /// Retrieves an immutable reference of a single data value.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{delta::DeltaCsl, fixtures::csl_array_4};
+  /// let delta = DeltaCsl::<_, u16, 4>::pack(&csl_array_4())?;
+  /// assert_eq!(delta.value([1, 0, 2, 2]), Some(&9));
+  /// assert_eq!(delta.value([1, 0, 2, 3]), None);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn value(&self, indcs: [usize; D]) -> Option<&DATA> {
+    let innermost_idx = *indcs.last()?;
+    let (line_indcs, line_data) = self.line(indcs)?;
+    let idx = line_indcs.binary_search(&innermost_idx).ok()?;
+    line_data.get(idx)
+  }
+}
@@ -1,3 +1,11 @@
+#[cfg(feature = "with-npz")]
+use crate::csl::io::NpzError;
+#[cfg(feature = "std")]
+use crate::csl::persist::PersistError;
+#[cfg(feature = "decomp")]
+use crate::decomp::DecompError;
+#[cfg(feature = "alloc")]
+use crate::labels::LabelsError;
 use crate::{
   coo::CooError,
   csl::{CslError, CslLineConstructorError},
@@ -5,6 +13,19 @@ use crate::{
 use core::fmt;
 
 /// Contains all errors related to ndsparse
+///
+/// Variants that wrap more specific errors expose them through `std::error::Error::source`.
+///
+#[cfg_attr(feature = "std", doc = "```rust")]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+/// use ndsparse::csl::{CslArray, CslError};
+/// use std::error::Error;
+/// let err = CslArray::new([10], [8, 9], [0, 5], [2, 0]).unwrap_err();
+/// assert_eq!(err.to_string(), "Csl(InvalidOffsetsOrder (offset 1))");
+/// let source = err.source().and_then(|e| e.downcast_ref::<CslError>());
+/// assert_eq!(source, Some(&CslError::InvalidOffsetsOrder { offs_idx: 1 }));
+/// ```
+#[cfg_attr(feature = "with-defmt", derive(defmt::Format))]
 #[derive(Debug, PartialEq)]
 #[non_exhaustive]
 pub enum Error {
@@ -14,8 +35,23 @@ pub enum Error {
   Csl(CslError),
   /// CslLineConstructorError
   CslLineConstructor(CslLineConstructorError),
+  /// DecompError
+  #[cfg(feature = "decomp")]
+  Decomp(DecompError),
   /// The internal buffer can't store all necessary data
   InsufficientCapacity,
+  /// Protocol Buffers message is malformed or doesn't match the requested structure
+  #[cfg(feature = "with-prost")]
+  InvalidProtoMessage,
+  /// LabelsError
+  #[cfg(feature = "alloc")]
+  Labels(LabelsError),
+  /// NpzError
+  #[cfg(feature = "with-npz")]
+  Npz(NpzError),
+  /// PersistError
+  #[cfg(feature = "std")]
+  Persist(PersistError),
   /// An Unknown that probably shouldn't have happened
   UnknownError,
 }
@@ -27,14 +63,40 @@ impl fmt::Display for Error {
       Self::Coo(ref x) => write!(f, "Coo({})", x),
       Self::Csl(ref x) => write!(f, "Csl({})", x),
       Self::CslLineConstructor(ref x) => write!(f, "CslLineConstructor({})", x),
+      #[cfg(feature = "decomp")]
+      Self::Decomp(ref x) => write!(f, "Decomp({})", x),
       Self::InsufficientCapacity => write!(f, "Inefficient Capacity"),
+      #[cfg(feature = "with-prost")]
+      Self::InvalidProtoMessage => write!(f, "InvalidProtoMessage"),
+      #[cfg(feature = "alloc")]
+      Self::Labels(ref x) => write!(f, "Labels({})", x),
+      #[cfg(feature = "with-npz")]
+      Self::Npz(ref x) => write!(f, "Npz({})", x),
+      #[cfg(feature = "std")]
+      Self::Persist(ref x) => write!(f, "Persist({})", x),
       Self::UnknownError => write!(f, "UnknownError"),
     }
   }
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+  #[inline]
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match *self {
+      Self::Coo(ref x) => Some(x),
+      Self::Csl(ref x) => Some(x),
+      Self::CslLineConstructor(ref x) => Some(x),
+      #[cfg(feature = "decomp")]
+      Self::Decomp(ref x) => Some(x),
+      Self::Labels(ref x) => Some(x),
+      #[cfg(feature = "with-npz")]
+      Self::Npz(ref x) => Some(x),
+      Self::Persist(ref x) => Some(x),
+      _ => None,
+    }
+  }
+}
 
 impl From<CooError> for Error {
   #[inline]
@@ -56,3 +118,35 @@ impl From<CslLineConstructorError> for Error {
     Self::CslLineConstructor(f)
   }
 }
+
+#[cfg(feature = "decomp")]
+impl From<DecompError> for Error {
+  #[inline]
+  fn from(f: DecompError) -> Self {
+    Self::Decomp(f)
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl From<LabelsError> for Error {
+  #[inline]
+  fn from(f: LabelsError) -> Self {
+    Self::Labels(f)
+  }
+}
+
+#[cfg(feature = "with-npz")]
+impl From<NpzError> for Error {
+  #[inline]
+  fn from(f: NpzError) -> Self {
+    Self::Npz(f)
+  }
+}
+
+#[cfg(feature = "std")]
+impl From<PersistError> for Error {
+  #[inline]
+  fn from(f: PersistError) -> Self {
+    Self::Persist(f)
+  }
+}
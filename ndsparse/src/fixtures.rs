@@ -1,8 +1,110 @@
-//! Instances for documentation tests or tests.
+//! Well-known instances for documentation tests or tests.
+//!
+//! Besides the hand-written [`coo_array_5`] and [`csl_array_4`] structures, [`fixture_coo`] and
+//! [`fixture_csl`] generate instances of any dimension following a [`Pattern`]. Downstream
+//! crates can use them in their own tests instead of copying constants.
 
 use crate::{coo::CooArray, csl::CslArray};
 #[cfg(feature = "alloc")]
 use crate::{coo::CooVec, csl::CslVec};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Sparsity patterns used by [`fixture_coo`] and [`fixture_csl`]. Each pattern is applied to
+/// every innermost line, where `row` is the index of the second innermost dimension (zero for
+/// 1D structures) and `col` is the innermost index.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Pattern {
+  /// Every element is stored
+  Dense,
+  /// Only elements where `col == row` are stored
+  Diagonal,
+  /// No element is stored
+  Empty,
+  /// Only elements where `col` is at most one position away from `row` are stored
+  Tridiagonal,
+}
+
+#[cfg(feature = "alloc")]
+impl Pattern {
+  #[inline]
+  fn contains(self, row: usize, col: usize) -> bool {
+    match self {
+      Self::Dense => true,
+      Self::Diagonal => col == row,
+      Self::Empty => false,
+      Self::Tridiagonal => col.saturating_add(1) >= row && col <= row.saturating_add(1),
+    }
+  }
+}
+
+/// COO structure with dimensions `dims` whose elements follow `pattern`. Stored values are
+/// `1, 2, 3, ...` in lexicographic order of their indices.
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::fixtures::{fixture_coo, Pattern};
+/// let coo = fixture_coo([2, 3], Pattern::Diagonal)?;
+/// assert_eq!(coo.data(), &[([0, 0], 1), ([1, 1], 2)]);
+/// # Ok(()) }
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn fixture_coo<const D: usize>(
+  dims: [usize; D],
+  pattern: Pattern,
+) -> crate::Result<CooVec<i32, D>> {
+  let mut data = Vec::new();
+  let mut value = 0;
+  if crate::utils::max_nnz(&dims) > 0 {
+    let mut indcs = [0; D];
+    loop {
+      let row = if D > 1 { indcs.get(D - 2).copied().unwrap_or_default() } else { 0 };
+      let col = indcs.last().copied().unwrap_or_default();
+      if pattern.contains(row, col) {
+        value += 1;
+        data.push((indcs, value));
+      }
+      let mut has_overflowed = true;
+      for (idx, dim) in indcs.iter_mut().zip(&dims).rev() {
+        *idx += 1;
+        if *idx < *dim {
+          has_overflowed = false;
+          break;
+        }
+        *idx = 0;
+      }
+      if has_overflowed {
+        break;
+      }
+    }
+  }
+  CooVec::new(dims, data)
+}
+
+/// CSL version of [`fixture_coo`].
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> ndsparse::Result<()> {
+/// use ndsparse::fixtures::{fixture_csl, Pattern};
+/// let csl = fixture_csl([2, 3, 3], Pattern::Tridiagonal)?;
+/// assert_eq!(csl.data(), &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
+/// assert_eq!(csl.indcs(), &[0, 1, 0, 1, 2, 1, 2, 0, 1, 0, 1, 2, 1, 2]);
+/// assert_eq!(csl.offs(), &[0, 2, 5, 7, 9, 12, 14]);
+/// # Ok(()) }
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn fixture_csl<const D: usize>(
+  dims: [usize; D],
+  pattern: Pattern,
+) -> crate::Result<CslVec<i32, D>> {
+  fixture_coo(dims, pattern)?.to_csl()
+}
 
 /// As odd as it may seem, this illustration is just a guide to get a grasp of
 /// a 5D structure.
@@ -108,6 +210,7 @@ pub fn csl_array_4() -> CslArray<i32, 4, 9, 25> {
     dims: [2, 3, 4, 5],
     indcs: [0, 3, 1, 3, 4, 2, 2, 4, 2],
     offs: [0, 2, 3, 3, 5, 6, 6, 6, 6, 7, 8, 8, 8, 8, 8, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9],
+    outermost_stride: Some(12),
   }
 }
 
@@ -121,5 +224,6 @@ pub fn csl_vec_4() -> CslVec<i32, 4> {
     dims: csl.dims,
     indcs: csl.indcs.to_vec(),
     offs: csl.offs.to_vec(),
+    outermost_stride: csl.outermost_stride,
   }
 }
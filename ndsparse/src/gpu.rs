@@ -0,0 +1,207 @@
+//! GPU-friendly layout of 2D CSL structures.
+//!
+//! [`GpuCsl`] stores offsets and indices as `u32` and pads every buffer to a multiple of
+//! [`ALIGNMENT`] elements, which satisfies the size and alignment rules of storage buffers in
+//! APIs like `wgpu`/WebGPU. The buffers can be uploaded as-is with their `*_bytes` methods and
+//! bound to a compute shader alongside the uniform returned by [`GpuCsl::meta_bytes`].
+//!
+//! ```wgsl
+//! struct Meta { rows: u32, cols: u32, nnz: u32, data_stride: u32 }
+//! @group(0) @binding(0) var<uniform> meta: Meta;
+//! @group(0) @binding(1) var<storage, read> data: array<f32>;
+//! @group(0) @binding(2) var<storage, read> indcs: array<u32>;
+//! @group(0) @binding(3) var<storage, read> offs: array<u32>;
+//! ```
+
+use crate::csl::{rebased_offs, Csl, CslError, CslVec};
+use alloc::vec::Vec;
+use cl_traits::Storage;
+use core::convert::TryFrom;
+
+/// Number of elements that every buffer length is rounded up to.
+pub const ALIGNMENT: usize = 4;
+
+/// Scalar types that have a direct 4-byte representation in shaders.
+pub trait GpuScalar: Copy + Default {
+  /// Little-endian bytes of the value.
+  fn to_le_bytes(self) -> [u8; 4];
+}
+
+macro_rules! impl_gpu_scalar {
+  ($($ty:ty),*) => {
+    $(
+      impl GpuScalar for $ty {
+        #[inline]
+        fn to_le_bytes(self) -> [u8; 4] {
+          <$ty>::to_le_bytes(self)
+        }
+      }
+    )*
+  };
+}
+
+impl_gpu_scalar!(f32, i32, u32);
+
+/// Metadata of a packed structure, usually bound as a uniform buffer.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[repr(C)]
+pub struct GpuMeta {
+  /// Number of rows, i.e., the outermost dimension length
+  pub rows: u32,
+  /// Number of columns, i.e., the innermost dimension length
+  pub cols: u32,
+  /// Number of stored elements without padding
+  pub nnz: u32,
+  /// Size in bytes of each data element
+  pub data_stride: u32,
+}
+
+/// 2D CSL packed into padded buffers. See the [module](self) documentation for more information.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GpuCsl<T> {
+  data: Vec<T>,
+  indcs: Vec<u32>,
+  meta: GpuMeta,
+  offs: Vec<u32>,
+  offs_len: usize,
+}
+
+impl<T> GpuCsl<T>
+where
+  T: GpuScalar,
+{
+  /// Packs a 2D structure. Offsets are rebased to start at zero.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{csl::CslVec, gpu::GpuCsl};
+  /// let csl = CslVec::new([2, 3], vec![1.0f32, 2.0, 3.0], vec![0, 2, 1], vec![0, 2, 3])?;
+  /// let gpu = GpuCsl::pack(&csl)?;
+  /// assert_eq!(gpu.data(), &[1.0, 2.0, 3.0, 0.0]);
+  /// assert_eq!(gpu.indcs(), &[0, 2, 1, 0]);
+  /// assert_eq!(gpu.offs(), &[0, 2, 3, 3]);
+  /// assert_eq!(gpu.meta().nnz, 3);
+  /// assert_eq!(gpu.unpack()?, csl);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn pack<DS, IS, OS>(csl: &Csl<DS, IS, OS, 2>) -> crate::Result<Self>
+  where
+    DS: AsRef<[T]> + Storage<Item = T>,
+    IS: AsRef<[usize]>,
+    OS: AsRef<[usize]>,
+  {
+    let to_u32 = |value: usize| u32::try_from(value).map_err(|_err| CslError::IndexOverflow);
+    let [rows, cols] = *csl.dims();
+    let data_ref = csl.data();
+    let offs_ref = csl.offs();
+    let mut data = Vec::with_capacity(padded_len(data_ref.len()));
+    data.extend_from_slice(data_ref);
+    data.resize(padded_len(data_ref.len()), T::default());
+    let mut indcs = Vec::with_capacity(data.len());
+    for idx in csl.indcs().iter().copied() {
+      indcs.push(to_u32(idx)?);
+    }
+    indcs.resize(data.len(), 0);
+    let mut offs = Vec::with_capacity(padded_len(offs_ref.len()));
+    for off in rebased_offs(offs_ref)? {
+      offs.push(to_u32(off)?);
+    }
+    let last = offs.last().copied().unwrap_or_default();
+    offs.resize(padded_len(offs_ref.len()), last);
+    let meta = GpuMeta {
+      rows: to_u32(rows)?,
+      cols: to_u32(cols)?,
+      nnz: to_u32(data_ref.len())?,
+      data_stride: 4,
+    };
+    Ok(Self { data, indcs, meta, offs, offs_len: offs_ref.len() })
+  }
+
+  /// Padded data.
+  #[inline]
+  pub fn data(&self) -> &[T] {
+    &self.data
+  }
+
+  /// Little-endian bytes of [`data`](#method.data).
+  #[inline]
+  pub fn data_bytes(&self) -> Vec<u8> {
+    self.data.iter().flat_map(|elem| elem.to_le_bytes()).collect()
+  }
+
+  /// Padded indices.
+  #[inline]
+  pub fn indcs(&self) -> &[u32] {
+    &self.indcs
+  }
+
+  /// Little-endian bytes of [`indcs`](#method.indcs).
+  #[inline]
+  pub fn indcs_bytes(&self) -> Vec<u8> {
+    self.indcs.iter().flat_map(|elem| elem.to_le_bytes()).collect()
+  }
+
+  /// Metadata of the packed structure.
+  #[inline]
+  pub fn meta(&self) -> &GpuMeta {
+    &self.meta
+  }
+
+  /// Little-endian bytes of [`meta`](#method.meta) in declaration order.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{csl::CslVec, gpu::GpuCsl};
+  /// let csl = CslVec::new([2, 3], vec![1i32], vec![2], vec![0, 1, 1])?;
+  /// let bytes = GpuCsl::pack(&csl)?.meta_bytes();
+  /// assert_eq!(bytes, [2, 0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0]);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn meta_bytes(&self) -> [u8; 16] {
+    let mut rslt = [0; 16];
+    let GpuMeta { rows, cols, nnz, data_stride } = self.meta;
+    for (chunk, elem) in rslt.chunks_exact_mut(4).zip([rows, cols, nnz, data_stride].iter()) {
+      chunk.copy_from_slice(&elem.to_le_bytes());
+    }
+    rslt
+  }
+
+  /// Padded offsets. Padding repeats the last offset.
+  #[inline]
+  pub fn offs(&self) -> &[u32] {
+    &self.offs
+  }
+
+  /// Little-endian bytes of [`offs`](#method.offs).
+  #[inline]
+  pub fn offs_bytes(&self) -> Vec<u8> {
+    self.offs.iter().flat_map(|elem| elem.to_le_bytes()).collect()
+  }
+
+  /// Removes all padding and converts the buffers back into a validated structure.
+  #[inline]
+  pub fn unpack(&self) -> crate::Result<CslVec<T, 2>> {
+    let GpuMeta { rows, cols, nnz, .. } = self.meta;
+    let [rows, cols, nnz] = [rows as usize, cols as usize, nnz as usize];
+    let data = self.data.get(..nnz).unwrap_or_default().to_vec();
+    let indcs = self.indcs.iter().take(nnz).map(|&idx| idx as usize).collect();
+    let offs = self.offs.iter().take(self.offs_len).map(|&off| off as usize).collect();
+    CslVec::new([rows, cols], data, indcs, offs)
+  }
+}
+
+#[inline]
+fn padded_len(len: usize) -> usize {
+  let rem = len % ALIGNMENT;
+  if rem == 0 {
+    len
+  } else {
+    len + (ALIGNMENT - rem)
+  }
+}
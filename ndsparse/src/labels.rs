@@ -0,0 +1,243 @@
+//! Labels for the indices of each dimension.
+//!
+//! [`Labeled`] attaches a label to every index of every dimension of an inner structure, which
+//! allows elements to be retrieved by their labels instead of their numeric indices.
+//!
+//! ```rust
+//! # fn main() -> ndsparse::Result<()> {
+//! use ndsparse::{csl::CslVec, labels::Labeled};
+//! let csl = CslVec::new([2, 3], vec![1, 2, 3], vec![0, 2, 1], vec![0, 2, 3])?;
+//! let users = vec!["user1".to_string(), "user42".to_string()];
+//! let items = vec!["item0".to_string(), "item7".to_string(), "item9".to_string()];
+//! let labeled = Labeled::from_csl(csl, [users, items])?;
+//! assert_eq!(labeled.value_by_labels(["user42", "item7"]), Some(&3));
+//! assert_eq!(labeled.value_by_labels(["user1", "item7"]), None);
+//! // The inner structure is still reachable
+//! assert_eq!(labeled.value([0, 2]), Some(&2));
+//! # Ok(()) }
+//! ```
+
+mod labels_error;
+
+use crate::{coo::Coo, csl::Csl};
+use alloc::vec::Vec;
+use cl_traits::Storage;
+use core::{borrow::Borrow, ops::Deref};
+pub use labels_error::*;
+
+/// Labels of all indices of all dimensions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Labels<L, const D: usize> {
+  labels: [Vec<L>; D],
+  sorted: [Vec<usize>; D],
+}
+
+impl<L, const D: usize> Labels<L, D>
+where
+  L: Ord,
+{
+  /// Creates a new instance from the labels of each dimension.
+  ///
+  /// # Arguments
+  ///
+  /// * `dims`: Length of each dimension
+  /// * `labels`: Labels of each dimension, the label of index `idx` is stored at `idx`
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::labels::Labels;
+  /// let labels = Labels::new(&[2, 1], [vec!['b', 'a'], vec!['c']])?;
+  /// assert_eq!(labels.indcs_by_labels([&'a', &'c']), Some([1, 0]));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn new(dims: &[usize; D], labels: [Vec<L>; D]) -> crate::Result<Self> {
+    for (dim, (dim_labels, &expected)) in labels.iter().zip(dims).enumerate() {
+      if dim_labels.len() != expected {
+        let found = dim_labels.len();
+        return Err(LabelsError::DiffLength { dim, expected, found }.into());
+      }
+    }
+    let mut sorted: [Vec<usize>; D] = cl_traits::create_array(|_| Vec::new());
+    for (dim, (dim_sorted, dim_labels)) in sorted.iter_mut().zip(&labels).enumerate() {
+      dim_sorted.extend(0..dim_labels.len());
+      dim_sorted.sort_unstable_by(|&a, &b| dim_labels.get(a).cmp(&dim_labels.get(b)));
+      let has_duplicates = crate::utils::windows2(dim_sorted.as_slice())
+        .any(|[&a, &b]| dim_labels.get(a) == dim_labels.get(b));
+      if has_duplicates {
+        return Err(LabelsError::DuplicatedLabel { dim }.into());
+      }
+    }
+    Ok(Self { labels, sorted })
+  }
+
+  /// Index of `label` in dimension `dim`.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::labels::Labels;
+  /// let labels = Labels::new(&[3], [vec!["x", "y", "z"]])?;
+  /// assert_eq!(labels.idx_by_label(0, "z"), Some(2));
+  /// assert_eq!(labels.idx_by_label(0, "w"), None);
+  /// assert_eq!(labels.idx_by_label(1, "z"), None);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn idx_by_label<Q>(&self, dim: usize, label: &Q) -> Option<usize>
+  where
+    L: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    let dim_labels = self.labels.get(dim)?;
+    let dim_sorted = self.sorted.get(dim)?;
+    let rslt = dim_sorted.binary_search_by(|&idx| match dim_labels.get(idx) {
+      Some(elem) => elem.borrow().cmp(label),
+      None => core::cmp::Ordering::Less,
+    });
+    dim_sorted.get(rslt.ok()?).copied()
+  }
+
+  /// Indices of all dimensions determined by `labels`.
+  #[inline]
+  pub fn indcs_by_labels<Q>(&self, labels: [&Q; D]) -> Option<[usize; D]>
+  where
+    L: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    let mut rslt = [0; D];
+    for (dim, (idx, label)) in rslt.iter_mut().zip(labels.iter()).enumerate() {
+      *idx = self.idx_by_label(dim, *label)?;
+    }
+    Some(rslt)
+  }
+}
+
+impl<L, const D: usize> Labels<L, D> {
+  /// Labels of dimension `dim`.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::labels::Labels;
+  /// let labels = Labels::new(&[2], [vec!["x", "y"]])?;
+  /// assert_eq!(labels.labels(0), Some(&["x", "y"][..]));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn labels(&self, dim: usize) -> Option<&[L]> {
+    self.labels.get(dim).map(|elem| elem.as_slice())
+  }
+}
+
+/// Structure whose indices are also identified by [`Labels`]. All methods of the inner structure
+/// are available through `Deref`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Labeled<L, S, const D: usize> {
+  labels: Labels<L, D>,
+  structure: S,
+}
+
+impl<L, S, const D: usize> Labeled<L, S, D> {
+  /// Splits the instance into its labels and its inner structure.
+  #[inline]
+  pub fn into_parts(self) -> (Labels<L, D>, S) {
+    (self.labels, self.structure)
+  }
+
+  /// The labels of all dimensions.
+  #[inline]
+  pub fn labels(&self) -> &Labels<L, D> {
+    &self.labels
+  }
+}
+
+impl<DS, L, const D: usize> Labeled<L, Coo<DS, D>, D>
+where
+  L: Ord,
+{
+  /// Attaches `labels` to a COO structure.
+  ///
+  /// # Arguments
+  ///
+  /// * `coo`: COO structure
+  /// * `labels`: Labels of each dimension
+  #[inline]
+  pub fn from_coo(coo: Coo<DS, D>, labels: [Vec<L>; D]) -> crate::Result<Self> {
+    Ok(Self { labels: Labels::new(coo.dims(), labels)?, structure: coo })
+  }
+}
+
+impl<DATA, DS, L, const D: usize> Labeled<L, Coo<DS, D>, D>
+where
+  DS: AsRef<[<DS as Storage>::Item]> + Storage<Item = ([usize; D], DATA)>,
+  L: Ord,
+{
+  /// Immutable reference of the element determined by the labels of each dimension.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{fixtures::coo_vec_5, labels::Labeled};
+  /// let labels = [vec![0, 1], vec![0, 1, 2], vec![0, 1, 2, 3], vec![0, 1, 2], vec![0, -1, -2]];
+  /// let labeled = Labeled::from_coo(coo_vec_5(), labels)?;
+  /// assert_eq!(labeled.value_by_labels([&1, &2, &3, &2, &-2]), Some(&7));
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn value_by_labels<Q>(&self, labels: [&Q; D]) -> Option<&DATA>
+  where
+    L: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    self.structure.value(self.labels.indcs_by_labels(labels)?)
+  }
+}
+
+impl<DS, IS, L, OS, const D: usize> Labeled<L, Csl<DS, IS, OS, D>, D>
+where
+  L: Ord,
+{
+  /// Attaches `labels` to a CSL structure.
+  ///
+  /// # Arguments
+  ///
+  /// * `csl`: CSL structure
+  /// * `labels`: Labels of each dimension
+  #[inline]
+  pub fn from_csl(csl: Csl<DS, IS, OS, D>, labels: [Vec<L>; D]) -> crate::Result<Self> {
+    Ok(Self { labels: Labels::new(csl.dims(), labels)?, structure: csl })
+  }
+}
+
+impl<DATA, DS, IS, L, OS, const D: usize> Labeled<L, Csl<DS, IS, OS, D>, D>
+where
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  L: Ord,
+  OS: AsRef<[usize]>,
+{
+  /// Immutable reference of the element determined by the labels of each dimension.
+  #[inline]
+  pub fn value_by_labels<Q>(&self, labels: [&Q; D]) -> Option<&DATA>
+  where
+    L: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    self.structure.value(self.labels.indcs_by_labels(labels)?)
+  }
+}
+
+impl<L, S, const D: usize> Deref for Labeled<L, S, D> {
+  type Target = S;
+
+  #[inline]
+  fn deref(&self) -> &S {
+    &self.structure
+  }
+}
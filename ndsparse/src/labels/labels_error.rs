@@ -0,0 +1,54 @@
+use core::fmt;
+
+/// Any error related to dimension labels
+#[cfg_attr(feature = "with-defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum LabelsError {
+  /// The number of labels of a dimension is different than the dimension length
+  ///
+  /// ```rust
+  /// use ndsparse::{fixtures::csl_vec_4, labels::{Labeled, LabelsError}};
+  /// let labels = [vec!["a"], vec!["b"; 3], vec!["c"; 4], vec!["d"; 5]];
+  /// let labeled = Labeled::from_csl(csl_vec_4(), labels);
+  /// let err = LabelsError::DiffLength { dim: 0, expected: 2, found: 1 };
+  /// assert_eq!(labeled.err(), Some(ndsparse::Error::Labels(err)));
+  /// ```
+  DiffLength {
+    /// The dimension
+    dim: usize,
+    /// Dimension length
+    expected: usize,
+    /// Number of labels
+    found: usize,
+  },
+
+  /// The same label was used more than once in a dimension
+  ///
+  /// ```rust
+  /// use ndsparse::{fixtures::coo_vec_5, labels::{Labeled, LabelsError}};
+  /// let labels = [vec![0, 1], vec![0, 1, 1], vec![0, 1, 2, 3], vec![0, 1, 2], vec![0, 1, 2]];
+  /// let labeled = Labeled::from_coo(coo_vec_5(), labels);
+  /// let err = LabelsError::DuplicatedLabel { dim: 1 };
+  /// assert_eq!(labeled.err(), Some(ndsparse::Error::Labels(err)));
+  /// ```
+  DuplicatedLabel {
+    /// The dimension
+    dim: usize,
+  },
+}
+
+impl fmt::Display for LabelsError {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match *self {
+      Self::DiffLength { dim, expected, found } => {
+        write!(f, "DiffLength (dimension {}, expected: {}, found: {})", dim, expected, found)
+      }
+      Self::DuplicatedLabel { dim } => write!(f, "DuplicatedLabel (dimension {})", dim),
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LabelsError {}
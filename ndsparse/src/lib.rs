@@ -3,19 +3,34 @@
 //! This crate provides structures to store and retrieve N-dimensional sparse data.
 
 #![cfg_attr(not(feature = "std"), no_std)]
+#![forbid(unsafe_code)]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
 pub mod coo;
 pub mod csl;
-pub mod doc_tests;
+#[cfg(feature = "decomp")]
+pub mod decomp;
+#[cfg(feature = "alloc")]
+pub mod delta;
 mod error;
+pub mod fixtures;
+#[cfg(feature = "alloc")]
+pub mod gpu;
+#[cfg(feature = "alloc")]
+pub mod labels;
+#[cfg(feature = "alloc")]
+pub mod mask;
+mod memory_usage;
+#[cfg(feature = "with-prost")]
+pub mod proto;
 mod utils;
 
 /// Shorcut of core::result::Result<T, ndsparse::Error>;
 pub type Result<T> = core::result::Result<T, Error>;
 
 pub use error::*;
+pub use memory_usage::*;
 #[cfg(feature = "with-rayon")]
 pub use utils::{ParallelIteratorWrapper, ParallelProducerWrapper};
@@ -0,0 +1,155 @@
+//! Run-length encoded occupancy masks.
+//!
+//! [`RleMask`] only stores where elements are, not their values, and replaces the innermost index
+//! of every element by runs of consecutive indices. A line with millions of adjacent elements is
+//! then represented by a single run, which is useful for masking very large tensors whose
+//! occupancy is clustered, e.g., segmentation masks or padded batches.
+
+use crate::csl::{line_idx, line_ranges, Csl, CslVec};
+use alloc::vec::Vec;
+use cl_traits::Storage;
+use core::ops::Range;
+
+/// Binary occupancy of a `D`-dimensional space stored as runs of consecutive innermost indices.
+/// See the [module](self) documentation for more information.
+///
+/// Lines follow the same layout of [`Csl`], therefore, [`offs`](#method.offs) delimits the runs
+/// of each line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RleMask<const D: usize> {
+  dims: [usize; D],
+  offs: Vec<usize>,
+  runs: Vec<Range<usize>>,
+}
+
+impl<const D: usize> RleMask<D> {
+  /// Creates a mask with the positions of all stored elements of `csl`, regardless of their
+  /// values.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::{fixtures::csl_array_4, mask::RleMask};
+  /// let mask = RleMask::from_csl(&csl_array_4());
+  /// assert_eq!(mask.nnz(), 9);
+  /// assert_eq!(mask.runs().len(), 8);
+  /// assert_eq!(mask.line([0, 0, 3, 0]), Some(&[3..5][..]));
+  /// ```
+  #[inline]
+  pub fn from_csl<DATA, DS, IS, OS>(csl: &Csl<DS, IS, OS, D>) -> Self
+  where
+    DS: AsRef<[DATA]> + Storage<Item = DATA>,
+    IS: AsRef<[usize]>,
+    OS: AsRef<[usize]>,
+  {
+    let indcs = csl.indcs();
+    let mut offs = Vec::with_capacity(csl.offs().len());
+    let mut runs: Vec<Range<usize>> = Vec::new();
+    offs.push(0);
+    for range in line_ranges(csl.offs()) {
+      let line_start = runs.len();
+      for &idx in indcs.get(range).unwrap_or_default() {
+        match runs.get_mut(line_start..).and_then(|line_runs| line_runs.last_mut()) {
+          Some(run) if run.end == idx => run.end = idx + 1,
+          _ => runs.push(idx..idx + 1),
+        }
+      }
+      offs.push(runs.len());
+    }
+    Self { dims: *csl.dims(), offs, runs }
+  }
+
+  /// If the position `indcs` is occupied.
+  ///
+  /// # Arguments
+  ///
+  /// * `indcs`: Indices of all dimensions
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::{fixtures::csl_array_4, mask::RleMask};
+  /// let mask = RleMask::from_csl(&csl_array_4());
+  /// assert!(mask.contains([0, 0, 3, 4]));
+  /// assert!(!mask.contains([0, 0, 3, 2]));
+  /// assert!(!mask.contains([0, 7, 3, 4]));
+  /// ```
+  #[inline]
+  pub fn contains(&self, indcs: [usize; D]) -> bool {
+    let (innermost_idx, runs) = match (indcs.last(), self.line(indcs)) {
+      (Some(idx), Some(runs)) => (*idx, runs),
+      _ => return false,
+    };
+    let idx = runs.partition_point(|run| run.end <= innermost_idx);
+    runs.get(idx).is_some_and(|run| run.contains(&innermost_idx))
+  }
+
+  /// The definitions of all dimensions.
+  #[inline]
+  pub fn dims(&self) -> &[usize; D] {
+    &self.dims
+  }
+
+  /// Runs of the line that contains `indcs`. The innermost index is ignored.
+  ///
+  /// # Arguments
+  ///
+  /// * `indcs`: Indices of all dimensions
+  #[inline]
+  pub fn line(&self, indcs: [usize; D]) -> Option<&[Range<usize>]> {
+    let line = line_idx(&self.dims, &indcs)?;
+    let start = *self.offs.get(line)?;
+    let end = *self.offs.get(line.checked_add(1)?)?;
+    self.runs.get(start..end)
+  }
+
+  /// Number of occupied positions.
+  #[inline]
+  pub fn nnz(&self) -> usize {
+    self.runs.iter().map(|run| run.end - run.start).sum()
+  }
+
+  /// Offsets of the runs of each line.
+  #[inline]
+  pub fn offs(&self) -> &[usize] {
+    &self.offs
+  }
+
+  /// Half-open ranges of consecutive occupied innermost indices of all lines.
+  #[inline]
+  pub fn runs(&self) -> &[Range<usize>] {
+    &self.runs
+  }
+
+  /// Expands all runs into a validated pattern, i.e., a structure without values.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// # fn main() -> ndsparse::Result<()> {
+  /// use ndsparse::{csl::CslVec, fixtures::csl_array_4, mask::RleMask};
+  /// let csl = csl_array_4();
+  /// let pattern = RleMask::from_csl(&csl).to_csl()?;
+  /// assert_eq!(pattern.indcs(), csl.indcs());
+  /// assert_eq!(pattern.offs(), csl.offs());
+  /// let dense = CslVec::new([1, 1000], vec![(); 1000], (0..1000).collect(), vec![0, 1000])?;
+  /// let mask = RleMask::from_csl(&dense);
+  /// assert_eq!(mask.runs(), &[0..1000]);
+  /// assert_eq!(mask.to_csl()?, dense);
+  /// # Ok(()) }
+  /// ```
+  #[inline]
+  pub fn to_csl(&self) -> crate::Result<CslVec<(), D>> {
+    let nnz = self.nnz();
+    let mut indcs = Vec::with_capacity(nnz);
+    let mut offs = Vec::with_capacity(self.offs.len());
+    offs.push(0);
+    for range in line_ranges(&self.offs) {
+      for run in self.runs.get(range).unwrap_or_default() {
+        indcs.extend(run.clone());
+      }
+      offs.push(indcs.len());
+    }
+    CslVec::new(self.dims, alloc::vec![(); nnz], indcs, offs)
+  }
+}
@@ -0,0 +1,56 @@
+use core::fmt;
+
+/// Number of bytes occupied by the elements of a structure.
+///
+/// Element counts are based on the lengths of the storages, therefore, unused capacity isn't
+/// taken into consideration. The alternate `Debug` output (`{:#?}`) also prints the
+/// [`total`](#method.total).
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
+pub struct MemoryUsage {
+  /// Bytes of the stored data
+  pub data_bytes: usize,
+  /// Bytes of the stored indices
+  pub indcs_bytes: usize,
+  /// Bytes of the stored offsets
+  pub offs_bytes: usize,
+  /// Size of the structure itself plus any padding between elements. Storages that live inline,
+  /// like arrays, are also part of this value.
+  pub overhead: usize,
+}
+
+impl MemoryUsage {
+  /// Sum of all fields.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use ndsparse::MemoryUsage;
+  /// let usage = MemoryUsage { data_bytes: 1, indcs_bytes: 2, offs_bytes: 3, overhead: 4 };
+  /// assert_eq!(usage.total(), 10);
+  /// ```
+  #[inline]
+  pub fn total(&self) -> usize {
+    self
+      .data_bytes
+      .saturating_add(self.indcs_bytes)
+      .saturating_add(self.offs_bytes)
+      .saturating_add(self.overhead)
+  }
+}
+
+impl fmt::Debug for MemoryUsage {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let alternate = f.alternate();
+    let mut debug = f.debug_struct("MemoryUsage");
+    let _ = debug
+      .field("data_bytes", &self.data_bytes)
+      .field("indcs_bytes", &self.indcs_bytes)
+      .field("offs_bytes", &self.offs_bytes)
+      .field("overhead", &self.overhead);
+    if alternate {
+      let _ = debug.field("total", &self.total());
+    }
+    debug.finish()
+  }
+}
@@ -0,0 +1,326 @@
+//! Protocol Buffers schema and codec.
+//!
+//! The messages of this module mirror `proto/ndsparse.proto`, a stable and canonical
+//! representation of the CSL and COO structures that can be shared with other languages or
+//! services, e.g., gRPC.
+//!
+//! # Example
+//!
+//! ```rust
+//! # fn main() -> ndsparse::Result<()> {
+//! use ndsparse::{fixtures::{coo_vec_5, csl_vec_4}, proto};
+//! let coo = coo_vec_5();
+//! let csl = csl_vec_4();
+//! assert_eq!(proto::decode_coo::<i32, 5>(&proto::encode_coo(&coo))?, coo);
+//! assert_eq!(proto::decode_csl::<i32, 4>(&proto::encode_csl(&csl))?, csl);
+//! # Ok(()) }
+//! ```
+
+use crate::{
+  coo::{Coo, CooVec},
+  csl::{Csl, CslVec},
+};
+use alloc::vec::Vec;
+use cl_traits::Storage;
+use core::convert::TryFrom;
+use prost::Message;
+
+/// Repeated `bool` values.
+#[derive(Clone, PartialEq, Message)]
+pub struct Bools {
+  /// Values
+  #[prost(bool, repeated, tag = "1")]
+  pub values: Vec<bool>,
+}
+
+/// Repeated `f32` values.
+#[derive(Clone, PartialEq, Message)]
+pub struct F32s {
+  /// Values
+  #[prost(float, repeated, tag = "1")]
+  pub values: Vec<f32>,
+}
+
+/// Repeated `f64` values.
+#[derive(Clone, PartialEq, Message)]
+pub struct F64s {
+  /// Values
+  #[prost(double, repeated, tag = "1")]
+  pub values: Vec<f64>,
+}
+
+/// Repeated `i32` values.
+#[derive(Clone, PartialEq, Message)]
+pub struct I32s {
+  /// Values
+  #[prost(sint32, repeated, tag = "1")]
+  pub values: Vec<i32>,
+}
+
+/// Repeated `i64` values.
+#[derive(Clone, PartialEq, Message)]
+pub struct I64s {
+  /// Values
+  #[prost(sint64, repeated, tag = "1")]
+  pub values: Vec<i64>,
+}
+
+/// Repeated `u32` values.
+#[derive(Clone, PartialEq, Message)]
+pub struct U32s {
+  /// Values
+  #[prost(uint32, repeated, tag = "1")]
+  pub values: Vec<u32>,
+}
+
+/// Repeated `u64` values.
+#[derive(Clone, PartialEq, Message)]
+pub struct U64s {
+  /// Values
+  #[prost(uint64, repeated, tag = "1")]
+  pub values: Vec<u64>,
+}
+
+/// Stored elements of a structure.
+#[derive(Clone, PartialEq, Message)]
+pub struct Data {
+  /// Values of one of the supported types
+  #[prost(oneof = "DataValues", tags = "1, 2, 3, 4, 5, 6, 7")]
+  pub values: Option<DataValues>,
+}
+
+/// All supported types of [`Data`].
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub enum DataValues {
+  /// `bool`
+  #[prost(message, tag = "1")]
+  Bool(Bools),
+  /// `f32`
+  #[prost(message, tag = "2")]
+  F32(F32s),
+  /// `f64`
+  #[prost(message, tag = "3")]
+  F64(F64s),
+  /// `i32`
+  #[prost(message, tag = "4")]
+  I32(I32s),
+  /// `i64`
+  #[prost(message, tag = "5")]
+  I64(I64s),
+  /// `u32`
+  #[prost(message, tag = "6")]
+  U32(U32s),
+  /// `u64`
+  #[prost(message, tag = "7")]
+  U64(U64s),
+}
+
+/// COO message. `indcs` contains the flattened indices of all elements.
+#[derive(Clone, PartialEq, Message)]
+pub struct CooMessage {
+  /// Dimensions
+  #[prost(uint64, repeated, tag = "1")]
+  pub dims: Vec<u64>,
+  /// Data
+  #[prost(message, optional, tag = "2")]
+  pub data: Option<Data>,
+  /// Flattened indices
+  #[prost(uint64, repeated, tag = "3")]
+  pub indcs: Vec<u64>,
+}
+
+impl CooMessage {
+  /// Creates a message from any COO structure.
+  #[inline]
+  pub fn from_coo<DATA, DS, const D: usize>(coo: &Coo<DS, D>) -> Self
+  where
+    DATA: ProtoData,
+    DS: AsRef<[<DS as Storage>::Item]> + Storage<Item = ([usize; D], DATA)>,
+  {
+    let data = coo.data();
+    let indcs = data.iter().flat_map(|(indcs, _)| indcs.iter().copied().map(to_u64)).collect();
+    let values: Vec<DATA> = data.iter().map(|(_, value)| value.clone()).collect();
+    Self {
+      dims: coo.dims().iter().copied().map(to_u64).collect(),
+      data: Some(DATA::into_proto(values)),
+      indcs,
+    }
+  }
+
+  /// Validates and converts this message into a COO structure.
+  #[inline]
+  pub fn to_coo<DATA, const D: usize>(self) -> crate::Result<CooVec<DATA, D>>
+  where
+    DATA: ProtoData,
+  {
+    let dims = to_array(&self.dims)?;
+    let values = DATA::from_proto(self.data.ok_or(crate::Error::InvalidProtoMessage)?)?;
+    if self.indcs.len() != values.len().saturating_mul(D) {
+      return Err(crate::Error::InvalidProtoMessage);
+    }
+    let mut data = Vec::with_capacity(values.len());
+    let mut indcs_chunks = self.indcs.chunks(D.max(1));
+    for value in values {
+      let indcs = if D == 0 { [0; D] } else { to_array(indcs_chunks.next().unwrap_or_default())? };
+      data.push((indcs, value));
+    }
+    Coo::new(dims, data)
+  }
+}
+
+/// CSL message. `offs` always starts at zero.
+#[derive(Clone, PartialEq, Message)]
+pub struct CslMessage {
+  /// Dimensions
+  #[prost(uint64, repeated, tag = "1")]
+  pub dims: Vec<u64>,
+  /// Data
+  #[prost(message, optional, tag = "2")]
+  pub data: Option<Data>,
+  /// Indices
+  #[prost(uint64, repeated, tag = "3")]
+  pub indcs: Vec<u64>,
+  /// Offsets
+  #[prost(uint64, repeated, tag = "4")]
+  pub offs: Vec<u64>,
+}
+
+impl CslMessage {
+  /// Creates a message from any CSL structure.
+  #[inline]
+  pub fn from_csl<DATA, DS, IS, OS, const D: usize>(csl: &Csl<DS, IS, OS, D>) -> Self
+  where
+    DATA: ProtoData,
+    DS: AsRef<[DATA]> + Storage<Item = DATA>,
+    IS: AsRef<[usize]>,
+    OS: AsRef<[usize]>,
+  {
+    let first_off = csl.offs().first().copied().unwrap_or_default();
+    Self {
+      dims: csl.dims().iter().copied().map(to_u64).collect(),
+      data: Some(DATA::into_proto(csl.data().to_vec())),
+      indcs: csl.indcs().iter().copied().map(to_u64).collect(),
+      offs: csl.offs().iter().map(|&off| to_u64(off.saturating_sub(first_off))).collect(),
+    }
+  }
+
+  /// Validates and converts this message into a CSL structure.
+  #[inline]
+  pub fn to_csl<DATA, const D: usize>(self) -> crate::Result<CslVec<DATA, D>>
+  where
+    DATA: ProtoData,
+  {
+    let dims = to_array(&self.dims)?;
+    let data = DATA::from_proto(self.data.ok_or(crate::Error::InvalidProtoMessage)?)?;
+    let indcs = to_usize_vec(&self.indcs)?;
+    let offs = to_usize_vec(&self.offs)?;
+    Csl::new(dims, data, indcs, offs)
+  }
+}
+
+/// Types that can be stored in [`Data`].
+pub trait ProtoData: Clone + Sized {
+  /// Wraps `values` into the respective [`DataValues`] variant.
+  fn into_proto(values: Vec<Self>) -> Data;
+
+  /// Unwraps the values of `data`, returning an error if the variant doesn't match `Self`.
+  fn from_proto(data: Data) -> crate::Result<Vec<Self>>;
+}
+
+macro_rules! impl_proto_data {
+  ($($ty:ty, $variant:ident, $wrapper:ident;)*) => {
+    $(
+      impl ProtoData for $ty {
+        #[inline]
+        fn into_proto(values: Vec<Self>) -> Data {
+          Data { values: Some(DataValues::$variant($wrapper { values })) }
+        }
+
+        #[inline]
+        fn from_proto(data: Data) -> crate::Result<Vec<Self>> {
+          if let Some(DataValues::$variant(wrapper)) = data.values {
+            Ok(wrapper.values)
+          } else {
+            Err(crate::Error::InvalidProtoMessage)
+          }
+        }
+      }
+    )*
+  };
+}
+
+impl_proto_data!(
+  bool, Bool, Bools;
+  f32, F32, F32s;
+  f64, F64, F64s;
+  i32, I32, I32s;
+  i64, I64, I64s;
+  u32, U32, U32s;
+  u64, U64, U64s;
+);
+
+/// Decodes and validates a COO structure encoded by [`encode_coo`].
+#[inline]
+pub fn decode_coo<DATA, const D: usize>(bytes: &[u8]) -> crate::Result<CooVec<DATA, D>>
+where
+  DATA: ProtoData,
+{
+  CooMessage::decode(bytes).map_err(|_err| crate::Error::InvalidProtoMessage)?.to_coo()
+}
+
+/// Decodes and validates a CSL structure encoded by [`encode_csl`].
+#[inline]
+pub fn decode_csl<DATA, const D: usize>(bytes: &[u8]) -> crate::Result<CslVec<DATA, D>>
+where
+  DATA: ProtoData,
+{
+  CslMessage::decode(bytes).map_err(|_err| crate::Error::InvalidProtoMessage)?.to_csl()
+}
+
+/// Encodes any COO structure into Protocol Buffers bytes.
+#[inline]
+pub fn encode_coo<DATA, DS, const D: usize>(coo: &Coo<DS, D>) -> Vec<u8>
+where
+  DATA: ProtoData,
+  DS: AsRef<[<DS as Storage>::Item]> + Storage<Item = ([usize; D], DATA)>,
+{
+  CooMessage::from_coo(coo).encode_to_vec()
+}
+
+/// Encodes any CSL structure into Protocol Buffers bytes.
+#[inline]
+pub fn encode_csl<DATA, DS, IS, OS, const D: usize>(csl: &Csl<DS, IS, OS, D>) -> Vec<u8>
+where
+  DATA: ProtoData,
+  DS: AsRef<[DATA]> + Storage<Item = DATA>,
+  IS: AsRef<[usize]>,
+  OS: AsRef<[usize]>,
+{
+  CslMessage::from_csl(csl).encode_to_vec()
+}
+
+#[inline]
+fn to_array<const D: usize>(slice: &[u64]) -> crate::Result<[usize; D]> {
+  if slice.len() != D {
+    return Err(crate::Error::InvalidProtoMessage);
+  }
+  cl_traits::try_create_array(|idx| {
+    slice
+      .get(idx)
+      .and_then(|&elem| usize::try_from(elem).ok())
+      .ok_or(crate::Error::InvalidProtoMessage)
+  })
+}
+
+#[inline]
+fn to_u64(n: usize) -> u64 {
+  n as u64
+}
+
+#[inline]
+fn to_usize_vec(slice: &[u64]) -> crate::Result<Vec<usize>> {
+  slice
+    .iter()
+    .map(|&elem| usize::try_from(elem).map_err(|_err| crate::Error::InvalidProtoMessage))
+    .collect()
+}
@@ -8,40 +8,58 @@ pub struct ParallelIteratorWrapper<I>(pub(crate) I);
 #[derive(Debug)]
 pub struct ParallelProducerWrapper<I>(pub(crate) I);
 
+// Upper bound of each generated dimension length
+#[cfg(any(feature = "with-arbitrary", feature = "with-quickcheck"))]
+const ARBITRARY_MAX_DIM_LEN: usize = 8;
+// Upper bound of the product of all generated dimensions
+#[cfg(any(feature = "with-arbitrary", feature = "with-quickcheck"))]
+const ARBITRARY_MAX_LEN: usize = 4096;
+
+// Valid dimensions where an arbitrary number of outermost dimensions are empty. The remaining
+// lengths are kept small to avoid instances that take too long to generate.
+#[cfg(feature = "with-arbitrary")]
 #[inline]
-pub(crate) fn are_in_ascending_order<'a, F, T, U>(slice: &'a [T], cb: F) -> bool
-where
-  F: Fn(&'a T, &'a T) -> [&'a U; 2],
-  T: 'a,
-  U: PartialOrd + 'a,
-{
-  windows2(slice).all(|x| {
-    let [a, b] = cb(&x[0], &x[1]);
-    a <= b
-  })
+pub(crate) fn arbitrary_dims<const D: usize>(
+  u: &mut arbitrary::Unstructured<'_>,
+) -> arbitrary::Result<[usize; D]> {
+  let mut dims = [0; D];
+  let cut_point = u.int_in_range(0..=D)?;
+  let mut product: usize = 1;
+  for dim in dims.iter_mut().skip(cut_point) {
+    let max = (ARBITRARY_MAX_LEN / product).clamp(1, ARBITRARY_MAX_DIM_LEN);
+    *dim = u.int_in_range(1..=max)?;
+    product = product.saturating_mul(*dim);
+  }
+  Ok(dims)
 }
 
+// Same rules of `arbitrary_dims` where lengths are also bounded by the size of `g`.
+#[cfg(feature = "with-quickcheck")]
 #[inline]
-pub(crate) fn are_in_upper_bound<T>(slice: &[T], upper_bound: &T) -> bool
-where
-  T: PartialOrd,
-{
-  slice.iter().all(|x| x < upper_bound)
+pub(crate) fn quickcheck_dims<const D: usize>(g: &mut quickcheck::Gen) -> [usize; D] {
+  use quickcheck::Arbitrary;
+  let mut dims = [0; D];
+  let cut_point = usize::arbitrary(g) % D.saturating_add(1);
+  let max_dim_len = g.size().clamp(1, ARBITRARY_MAX_DIM_LEN);
+  let mut product: usize = 1;
+  for dim in dims.iter_mut().skip(cut_point) {
+    let max = (ARBITRARY_MAX_LEN / product).clamp(1, max_dim_len);
+    *dim = usize::arbitrary(g) % max + 1;
+    product = product.saturating_mul(*dim);
+  }
+  dims
 }
 
+// Same rules of `max_nnz` but with a wider type that avoids most overflows.
 #[inline]
-pub(crate) fn has_duplicates<T>(slice: &[T]) -> bool
-where
-  T: PartialEq,
-{
-  for (a_idx, a) in slice.iter().enumerate() {
-    for b in slice.iter().skip(a_idx.saturating_add(1)) {
-      if a == b {
-        return true;
-      }
-    }
+pub(crate) fn checked_max_nnz<const D: usize>(dims: &[usize; D]) -> Option<u128> {
+  if dims == &cl_traits::default_array() {
+    return Some(0);
   }
-  false
+  if D == 1 {
+    return dims.first().map(|&first| first as u128);
+  }
+  dims.iter().filter(|&&dim| dim != 0).try_fold(1u128, |acc, &dim| acc.checked_mul(dim as u128))
 }
 
 #[inline]
@@ -49,7 +67,7 @@ pub(crate) fn max_nnz<const D: usize>(dims: &[usize; D]) -> usize {
   if dims == &cl_traits::default_array() {
     return 0;
   }
-  if let Some(first) = dims.get(0).copied() {
+  if let Some(first) = dims.first().copied() {
     if D == 1 {
       return first;
     }
@@ -63,6 +81,119 @@ pub(crate) fn max_nnz<const D: usize>(dims: &[usize; D]) -> usize {
   0
 }
 
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn inverse_permutation(perm: &[usize], len: usize) -> Option<alloc::vec::Vec<usize>> {
+  if perm.len() != len {
+    return None;
+  }
+  let mut inverse = alloc::vec![usize::MAX; len];
+  for (idx, elem) in perm.iter().copied().enumerate() {
+    let inverse_elem = inverse.get_mut(elem)?;
+    if *inverse_elem != usize::MAX {
+      return None;
+    }
+    *inverse_elem = idx;
+  }
+  Some(inverse)
+}
+
+// Copy of `indcs` with `value` inserted at the position `axis`.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn insert_axis<const D: usize, const TD: usize>(
+  indcs: &[usize; D],
+  axis: usize,
+  value: usize,
+) -> [usize; TD] {
+  cl_traits::create_array(|idx| match idx.cmp(&axis) {
+    core::cmp::Ordering::Less => indcs.get(idx).copied().unwrap_or_default(),
+    core::cmp::Ordering::Equal => value,
+    core::cmp::Ordering::Greater => indcs.get(idx - 1).copied().unwrap_or_default(),
+  })
+}
+
+// Copy of `indcs` without the element at the position `axis`.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn remove_axis<const D: usize, const TD: usize>(
+  indcs: &[usize; D],
+  axis: usize,
+) -> [usize; TD] {
+  cl_traits::create_array(|idx| {
+    let from = if idx < axis { idx } else { idx + 1 };
+    indcs.get(from).copied().unwrap_or_default()
+  })
+}
+
+// Position of `idx` after a cyclic shift of `amount` positions in a dimension of length `len`.
+// Negative amounts shift towards the beginning.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn roll_idx(idx: usize, len: usize, amount: isize) -> usize {
+  if len == 0 {
+    return idx;
+  }
+  let magnitude = amount.unsigned_abs() % len;
+  let shift = if amount < 0 && magnitude != 0 { len - magnitude } else { magnitude };
+  if idx >= len - shift {
+    idx - (len - shift)
+  } else {
+    idx + shift
+  }
+}
+
+// Small xorshift64* generator seeded through SplitMix64. Unlike the generators of `rand`, its
+// output doesn't change between releases, which keeps seeded instances reproducible.
+#[cfg(feature = "with-rand")]
+#[derive(Debug)]
+pub(crate) struct XorShift64(u64);
+
+#[cfg(feature = "with-rand")]
+impl XorShift64 {
+  #[inline]
+  pub(crate) fn new(seed: u64) -> Self {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    // Zero is the only state that xorshift can't leave
+    Self(if z == 0 { 0x9E37_79B9_7F4A_7C15 } else { z })
+  }
+}
+
+#[cfg(feature = "with-rand")]
+impl rand::RngCore for XorShift64 {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    (self.next_u64() >> 32) as u32
+  }
+
+  #[inline]
+  fn next_u64(&mut self) -> u64 {
+    let mut x = self.0;
+    x ^= x >> 12;
+    x ^= x << 25;
+    x ^= x >> 27;
+    self.0 = x;
+    x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+  }
+
+  #[inline]
+  fn fill_bytes(&mut self, dest: &mut [u8]) {
+    for chunk in dest.chunks_mut(8) {
+      let bytes = self.next_u64().to_le_bytes();
+      chunk.copy_from_slice(bytes.get(..chunk.len()).unwrap_or_default());
+    }
+  }
+
+  #[inline]
+  fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+    self.fill_bytes(dest);
+    Ok(())
+  }
+}
+
 #[cfg(feature = "with-rand")]
 #[inline]
 pub(crate) fn valid_random_dims<R, const D: usize>(rng: &mut R, upper_bound: usize) -> [usize; D]
@@ -90,5 +221,5 @@ where
 
 #[inline]
 pub(crate) fn windows2<T>(slice: &[T]) -> impl Iterator<Item = [&T; 2]> {
-  slice.windows(2).filter_map(|value| Some([value.get(0)?, value.get(1)?]))
+  slice.windows(2).filter_map(|value| Some([value.first()?, value.get(1)?]))
 }
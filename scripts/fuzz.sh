@@ -3,4 +3,6 @@
 set -euxo pipefail
 
 cargo fuzz run --fuzz-dir ndsparse-fuzz coo -- -runs=100000
-cargo fuzz run --fuzz-dir ndsparse-fuzz csl -- -runs=100000
\ No newline at end of file
+cargo fuzz run --fuzz-dir ndsparse-fuzz csl -- -runs=100000
+cargo fuzz run --fuzz-dir ndsparse-fuzz valid_coo -- -runs=100000
+cargo fuzz run --fuzz-dir ndsparse-fuzz valid_csl -- -runs=100000
@@ -0,0 +1,9 @@
+#!/usr/bin/env bash
+
+set -euxo pipefail
+
+export MIRIFLAGS="-Zmiri-strict-provenance"
+
+cargo miri setup
+cargo miri test --no-default-features -p ndsparse
+cargo miri test --no-default-features --features alloc -p ndsparse
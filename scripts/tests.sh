@@ -15,9 +15,25 @@ $rt clippy -Aclippy::default_numeric_fallback,-Aclippy::integer_arithmetic,-Aunu
 
 $rt test-generic ndsparse
 $rt test-with-features ndsparse alloc
+$rt test-with-features ndsparse decomp
 $rt test-with-features ndsparse std
+$rt test-with-features ndsparse with-arbitrary
+$rt test-with-features ndsparse with-arrow
+$rt test-with-features ndsparse with-bytemuck
+$rt test-with-features ndsparse with-defmt
+$rt test-with-features ndsparse with-half
+$rt test-with-features ndsparse with-matrixmultiply
+$rt test-with-features ndsparse with-nalgebra
+$rt test-with-features ndsparse with-npz
+$rt test-with-features ndsparse with-num-complex
+$rt test-with-features ndsparse with-prost
+$rt test-with-features ndsparse with-quickcheck
 $rt test-with-features ndsparse with-rand
+$rt test-with-features ndsparse with-rand-distr
 $rt test-with-features ndsparse with-rayon
 $rt test-with-features ndsparse with-serde
+$rt test-with-features ndsparse with-sprs
 
+$rt test-with-features ndsparse-bindings with-capi
+$rt test-with-features ndsparse-bindings with-uniffi
 $rt test-with-features ndsparse-bindings with-wasm-bindgen